@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+    println!("cargo:rerun-if-changed=src/migrations/schema_migrations.sql");
+
+    let manifest_dir = PathBuf::from(
+        std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo"),
+    );
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+
+    let migrations = ger_codegen::Migrations::read(manifest_dir.join("migrations"))
+        .unwrap_or_else(|e| panic!("cannot read the migrations: {}", e));
+    // the table the backend records applied migrations in is a part of the schema as well.
+    let schema_migrations =
+        std::fs::read_to_string(manifest_dir.join("src/migrations/schema_migrations.sql"))
+            .expect("cannot read schema_migrations.sql");
+    let schema = migrations
+        .schema()
+        .map(|schema| schema + &schema_migrations)
+        .unwrap_or_else(|e| panic!("cannot read the migrations: {}", e));
+    let database = ger_codegen::Database::parse(&schema)
+        .and_then(|d| d.rename("t_user_role", "Role").rust())
+        .unwrap_or_else(|e| panic!("cannot generate the database module: {}", e));
+
+    // the schema is kept for the tests checking the statements against it.
+    std::fs::write(out_dir.join("schema.sql"), schema).expect("cannot write schema.sql");
+    std::fs::write(out_dir.join("database.rs"), database).expect("cannot write database.rs");
+    std::fs::write(out_dir.join("migrations.rs"), migrations.rust())
+        .expect("cannot write migrations.rs");
+}
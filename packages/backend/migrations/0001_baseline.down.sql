@@ -0,0 +1,48 @@
+-- drops everything created by the baseline, tables are dropped in the reverse order they are
+-- created in so references to them are dropped first.
+drop table if exists forum_post_votes;
+drop table if exists forum_post_views;
+drop table if exists forum_post_reply_votes;
+drop table if exists forum_post_replies;
+drop table if exists forum_posts;
+drop table if exists forum_categories;
+drop table if exists grade_point_mappings;
+drop table if exists student_grade_amendments;
+drop table if exists student_grades;
+drop table if exists student_scores;
+drop table if exists student_assignments;
+drop table if exists student_subject_comments;
+drop table if exists student_transactions;
+drop table if exists student_enrollment_waitlists;
+drop table if exists student_enrollments;
+drop table if exists opening_subjects_in_semester_additional_eligible_students;
+drop table if exists opening_subjects_in_semester_eligible_majors;
+drop table if exists opening_subjects_in_semester_subject_descriptions;
+drop table if exists opening_subjects_in_semester_professors;
+drop table if exists opening_subjects_in_semester_schedules;
+drop table if exists major_subjects;
+drop table if exists major_credit_specifications;
+drop table if exists student_representative_id_sequences;
+drop table if exists student_names;
+drop table if exists students;
+drop table if exists professor_names;
+drop table if exists professors;
+drop table if exists subject_schedules;
+drop table if exists subjects;
+drop table if exists grading_criteria_grades;
+drop table if exists grading_criterias;
+drop table if exists user_notifications;
+drop table if exists user_sessions;
+drop table if exists users;
+drop table if exists rooms;
+drop table if exists buildings;
+drop table if exists semester_registration_periods;
+drop table if exists semesters;
+drop table if exists academic_years;
+drop table if exists majors;
+drop table if exists curriculum_tuition_rates;
+drop table if exists curriculums;
+drop table if exists faculties;
+
+drop type if exists t_user_role;
+drop type if exists t_day_of_week;
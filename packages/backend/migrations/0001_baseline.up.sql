@@ -1,54 +1,10 @@
--- create database ger;
-
 -- extensions helping with searches.
 create extension if not exists pgroonga;
 
-set timezone to 'Asia/Bangkok';
-
-drop type t_day_of_week cascade;
-drop type t_user_role cascade;
-
 -- types for day of week.
 create type t_day_of_week as enum ('sunday', 'monday', 'tuesday', 'wednesday', 'thursday', 'friday', 'saturday');
 create type t_user_role as enum ('admin', 'student', 'professor');
 
-drop table faculties cascade;
-drop table curriculums cascade;
-drop table majors cascade;
-drop table academic_years cascade;
-drop table semesters cascade;
-drop table buildings cascade;
-drop table rooms cascade;
-drop table users cascade;
-drop table user_sessions cascade;
-drop table grading_criterias cascade;
-drop table grading_criteria_grades cascade;
-drop table subjects cascade;
-drop table subject_schedules cascade;
-drop table students cascade;
-drop table student_names cascade;
-drop table professors cascade;
-drop table professor_names cascade;
-drop table major_credit_specifications cascade;
-drop table major_subjects cascade;
-drop table opening_subjects_in_semester_schedules cascade;
-drop table opening_subjects_in_semester_professors cascade;
-drop table opening_subjects_in_semester_subject_descriptions cascade;
-drop table opening_subjects_in_semester_eligible_majors cascade;
-drop table opening_subjects_in_semester_additional_eligible_students cascade;
-drop table student_enrollments cascade;
-drop table student_transactions cascade;
-drop table student_subject_comments cascade;
-drop table student_assignments cascade;
-drop table student_scores cascade;
-
-drop table forum_categories cascade;
-drop table forum_posts cascade;
-drop table forum_post_replies cascade;
-drop table forum_post_reply_votes cascade;
-drop table forum_post_views cascade;
-drop table forum_post_votes cascade;
-
 -- available faculties in the university.
 create table faculties (
     faculty_id text not null unique,
@@ -72,6 +28,16 @@ create table curriculums (
     foreign key (faculty_id) references faculties(faculty_id)
 );
 
+-- tuition of a curriculum, students are charged for every enrolled credit plus a flat fee
+-- in every semester they enroll in.
+create table curriculum_tuition_rates (
+    curriculum_id text not null references curriculums(curriculum_id) unique,
+    curriculum_tuition_rate_per_credit numeric(8, 2) not null,
+    curriculum_tuition_rate_flat_fee numeric(8, 2) not null,
+    curriculum_tuition_rate_created_timestamp timestamptz not null default now(),
+    primary key (curriculum_id)
+);
+
 -- majors in the faculty that you could take in the curriculum.
 create table majors (
     major_id text not null unique,
@@ -104,6 +70,23 @@ create table semesters (
     foreign key (academic_year_id) references academic_years(academic_year_id)
 );
 
+-- when students could enroll in, drop and withdraw from subjects in a semester, every period
+-- starts inclusively and ends exclusively.
+create table semester_registration_periods (
+    semester_id text not null references semesters(semester_id) unique,
+    -- early registration is only opened to students who are at least in this year of study
+    early_registration_minimum_study_year int not null,
+    early_registration_start_timestamp timestamptz not null,
+    early_registration_end_timestamp timestamptz not null,
+    general_registration_start_timestamp timestamptz not null,
+    general_registration_end_timestamp timestamptz not null,
+    add_drop_start_timestamp timestamptz not null,
+    add_drop_end_timestamp timestamptz not null,
+    withdrawal_start_timestamp timestamptz not null,
+    withdrawal_end_timestamp timestamptz not null,
+    primary key (semester_id)
+);
+
 -- buildings in the uni
 create table buildings (
     building_id text not null unique,
@@ -145,6 +128,17 @@ create table user_sessions (
     foreign key (user_session_user_id) references users(user_id) on delete cascade
 );
 
+-- notifications sent to users by the system, such as getting promoted from a waitlist.
+create table user_notifications (
+    user_notification_id text not null unique,
+    user_id text not null,
+    user_notification_content text not null,
+    user_notification_is_read boolean not null default false,
+    user_notification_created_timestamp timestamptz not null default now(),
+    primary key (user_notification_id),
+    foreign key (user_id) references users(user_id) on delete cascade
+);
+
 create index if not exists pgroonga_users_index on users using pgroonga (
     user_username pgroonga_text_full_text_search_ops_v2
 );
@@ -248,6 +242,16 @@ create index if not exists pgroonga_student_names_index on student_names using p
     student_last_name pgroonga_text_full_text_search_ops_v2
 );
 
+-- running number of the last student_representative_id given to students of a major
+-- who start in the same academic year, this row is locked while new ids are taken so
+-- concurrent signups never get the same id.
+create table student_representative_id_sequences (
+    major_id text not null references majors(major_id),
+    first_academic_year_id text not null references academic_years(academic_year_id),
+    student_representative_id_sequence_last_number int not null,
+    primary key (major_id, first_academic_year_id)
+);
+
 -- credit specifications for a major, this is how many credits you have to take
 -- so that you can graduate.
 create table major_credit_specifications (
@@ -318,9 +322,25 @@ create table student_enrollments (
     semester_id text not null references semesters(semester_id),
     subject_id text not null references subjects(subject_id),
     student_id text not null references students(student_id),
+    -- withdrawn enrollments are kept so they show up as "W" on the transcript, they do not take
+    -- seats or time slots anymore.
+    student_enrollment_is_withdrawn boolean not null default false,
+    student_enrollment_withdrawn_timestamp timestamptz,
     primary key (semester_id, subject_id, student_id)
 );
 
+-- students waiting for a seat in a full opening subject, first come first serve.
+create table student_enrollment_waitlists (
+    semester_id text not null references semesters(semester_id),
+    subject_id text not null references subjects(subject_id),
+    student_id text not null references students(student_id),
+    student_enrollment_waitlist_created_timestamp timestamptz not null default clock_timestamp(),
+    primary key (semester_id, subject_id, student_id)
+);
+
+-- invoices of students in each semester, a student has at most one unpaid invoice in a semester
+-- which is kept up to date with the enrollments, adding subjects after paying issues another
+-- invoice for the rest.
 create table student_transactions (
     semester_id text not null references semesters(semester_id),
     student_id text not null references students(student_id),
@@ -329,7 +349,7 @@ create table student_transactions (
     student_transaction_price numeric(8, 2) not null,
     student_transaction_created_timestamp timestamptz not null default now(),
     student_transaction_finished_timestamp timestamptz,
-    primary key (semester_id, student_id)
+    primary key (student_transaction_id)
 );
 
 create table student_subject_comments (
@@ -362,6 +382,60 @@ create table student_scores (
     primary key (semester_id, subject_id, student_id, assignment_id)
 );
 
+-- final grades of students in opening subjects, computed from the scores and the grading criteria
+-- of the subject and kept as a snapshot, so changing the criteria later does not change grades
+-- that are already released.
+create table student_grades (
+    semester_id text not null references semesters(semester_id),
+    subject_id text not null references subjects(subject_id),
+    student_id text not null references students(student_id),
+    -- percentage of the total score of every assignment
+    student_grade_total_score numeric(6, 2) not null,
+    student_grade_computed_alphabet text not null,
+    -- set by the professor to replace the computed grade
+    student_grade_overridden_alphabet text,
+    student_grade_override_reason text,
+    student_grade_updated_timestamp timestamptz not null default now(),
+    primary key (semester_id, subject_id, student_id)
+);
+
+-- every change made to a grade after the grades of the subject are released.
+create table student_grade_amendments (
+    student_grade_amendment_id text not null unique,
+    semester_id text not null,
+    subject_id text not null,
+    student_id text not null,
+    -- who made the change
+    user_id text not null references users(user_id),
+    student_grade_amendment_previous_alphabet text not null,
+    student_grade_amendment_new_alphabet text not null,
+    student_grade_amendment_reason text not null,
+    student_grade_amendment_created_timestamp timestamptz not null default now(),
+    primary key (student_grade_amendment_id),
+    foreign key (semester_id, subject_id, student_id) references student_grades(semester_id, subject_id, student_id)
+);
+
+-- grade points of every letter grade used to calculate gpa and gpax, letter grades that are not
+-- in here such as "W" do not count towards them.
+create table grade_point_mappings (
+    grade_point_mapping_alphabet text not null unique,
+    grade_point_mapping_point numeric(3, 2) not null,
+    primary key (grade_point_mapping_alphabet)
+);
+
+insert into grade_point_mappings (
+    grade_point_mapping_alphabet,
+    grade_point_mapping_point
+) values
+    ('A', 4.00),
+    ('B+', 3.50),
+    ('B', 3.00),
+    ('C+', 2.50),
+    ('C', 2.00),
+    ('D+', 1.50),
+    ('D', 1.00),
+    ('F', 0.00);
+
 create table forum_categories (
     forum_category_id text not null unique,
     forum_category_name text not null unique,
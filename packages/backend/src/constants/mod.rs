@@ -61,6 +61,13 @@ lazy_static! {
     pub static ref JWT_TOKEN_AUDIENCE_NAME: String = "ger.com".to_string();
     pub static ref ARGON2_PEPPER_STRING: String =
         dotenvy::var("GER_ARGON2_PEPPER").expect("cannot load argon2 pepper string");
+    pub static ref PAYMENT_WEBHOOK_SECRET: String =
+        dotenvy::var("GER_PAYMENT_WEBHOOK_SECRET").expect("cannot load payment webhook secret");
+    /// Whether [post_mock_payment](crate::routes::payments::post_mock_payment::handler) is served,
+    /// it is only turned on with `GER_ENABLE_MOCK_PAYMENTS=true` on local environments.
+    pub static ref ENABLE_MOCK_PAYMENTS: bool = dotenvy::var("GER_ENABLE_MOCK_PAYMENTS")
+        .map(|v| v == "true")
+        .unwrap_or(false);
     pub static ref COMRAK_OPTIONS: ComrakOptions = {
         let mut options = ComrakOptions::default();
         options.extension.strikethrough = true;
@@ -96,6 +103,9 @@ pub const ACCESS_TOKEN_HEADER_NAME: &'static str = "x-access-token";
 /// The name of header that carries refresh token
 pub const REFRESH_TOKEN_HEADER_NAME: &'static str = "x-refresh-token";
 
+/// The name of header that carries the signature of payment gateway webhooks
+pub const PAYMENT_SIGNATURE_HEADER_NAME: &'static str = "x-payment-signature";
+
 /// Default page when page is missing
 pub const DEFAULT_PAGE: i32 = 1;
 
@@ -108,3 +118,20 @@ pub const MAX_PAGE_SIZE: i32 = 100;
 /// Default trending window for
 /// [get_trending_posts_list](crate::routes::forum::get_trending_posts_list::handler)
 pub const DEFAULT_TRENDING_WINDOW: i32 = 24;
+
+/// Largest file accepted by
+/// [post_signup_import](crate::routes::students::post_signup_import::handler), in bytes.
+pub const MAX_SIGNUP_IMPORT_FILE_SIZE: usize = 5 * 1024 * 1024;
+
+/// Language of names shown when the requested language is not specified or not available.
+pub const DEFAULT_NAME_LANGUAGE: &'static str = "en";
+
+/// Grade given when the total score does not reach any grade of the grading criteria.
+pub const FALLBACK_GRADE_ALPHABET: &'static str = "F";
+
+/// Grade shown in transcripts for subjects the student withdrew from.
+pub const WITHDRAWN_GRADE_ALPHABET: &'static str = "W";
+
+/// Professors could only read comments of a subject once at least this many students submitted
+/// them, so no comment could be traced back to a student.
+pub const MINIMUM_COMMENT_RESPONSE_COUNT: i64 = 5;
@@ -97,6 +97,31 @@ where
     Ok(Some(number))
 }
 
+/// Serialize and deserialize `time::Time` as a time of day such as `13:30`, used in class
+/// schedules.
+pub mod time_of_day {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use time::{format_description::FormatItem, macros::format_description};
+
+    const FORMAT: &[FormatItem<'static>] = format_description!("[hour]:[minute]");
+
+    pub fn serialize<S>(time: &time::Time, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let formatted = time.format(FORMAT).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&formatted)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<time::Time, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        time::Time::parse(&raw, FORMAT).map_err(serde::de::Error::custom)
+    }
+}
+
 /// How to order the response that have return type as `Array`
 #[derive(Default, Serialize, Deserialize, ToSchema, TS, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
@@ -0,0 +1,22 @@
+//! Structs of the tables and enums of the enum types the migrations create, generated by
+//! `build.rs` with `ger-codegen`, and the inserts of rows deriving `ToRow`.
+
+use deadpool_postgres::GenericClient;
+use ger_from_row::{Insert, ToRow};
+
+include!(concat!(env!("OUT_DIR"), "/database.rs"));
+
+/// Insert `rows` in as few statements as postgres allows. The statements are not cached because
+/// the number of rows differs between calls.
+pub async fn insert_rows<T: ToRow>(
+    client: &impl GenericClient,
+    rows: &[T],
+) -> Result<(), tokio_postgres::Error> {
+    for insert in Insert::chunks(rows) {
+        let statement = client.prepare_typed(&insert.sql(), &insert.types()).await?;
+
+        client.execute(&statement, &insert.params()).await?;
+    }
+
+    Ok(())
+}
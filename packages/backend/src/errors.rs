@@ -32,6 +32,62 @@ pub enum HttpError {
     Forbidden,
     #[display(fmt = "invalid authentication credentials")]
     InvalidAuthenticationCredentials,
+    #[display(fmt = "semester not found")]
+    SemesterNotFound,
+    #[display(fmt = "subject not found")]
+    SubjectNotFound,
+    #[display(fmt = "schedule conflicts with other opening subjects")]
+    ScheduleConflict,
+    #[display(fmt = "subject is not opened in the semester")]
+    OpeningSubjectNotFound,
+    #[display(fmt = "you are not eligible to enroll in this subject")]
+    NotEligible,
+    #[display(fmt = "subject clashes with another enrolled subject")]
+    EnrollmentTimeClash,
+    #[display(fmt = "you are already enrolled in this subject")]
+    AlreadyEnrolled,
+    #[display(fmt = "enrollment not found")]
+    EnrollmentNotFound,
+    #[display(fmt = "you are already in the waitlist of this subject")]
+    AlreadyWaitlisted,
+    #[display(fmt = "registration is closed")]
+    RegistrationClosed,
+    #[display(fmt = "early registration is only opened to senior students")]
+    EarlyRegistrationNotAllowed,
+    #[display(fmt = "registration periods not found")]
+    RegistrationPeriodsNotFound,
+    #[display(fmt = "please pay the unpaid invoices of previous semesters first")]
+    UnpaidInvoice,
+    #[display(fmt = "invalid webhook signature")]
+    InvalidWebhookSignature,
+    #[display(fmt = "transaction not found")]
+    TransactionNotFound,
+    #[display(fmt = "curriculum not found")]
+    CurriculumNotFound,
+    #[display(fmt = "grading criteria not found")]
+    GradingCriteriaNotFound,
+    #[display(fmt = "you do not teach this subject in the semester")]
+    NotTeaching,
+    #[display(fmt = "assignment not found")]
+    AssignmentNotFound,
+    #[display(fmt = "scores have to be between zero and the full score of the assignment")]
+    ScoreExceedsFullScore,
+    #[display(fmt = "some students are not enrolled in this subject")]
+    StudentNotEnrolled,
+    #[display(fmt = "grades of this subject are already released, use an amendment instead")]
+    GradesAlreadyReleased,
+    #[display(fmt = "grades of this subject are not released yet")]
+    GradesNotReleased,
+    #[display(fmt = "grade not found")]
+    GradeNotFound,
+    #[display(fmt = "student not found")]
+    StudentNotFound,
+    #[display(fmt = "you have already commented on this subject")]
+    CommentAlreadySubmitted,
+    #[display(fmt = "import file is too large")]
+    ImportFileTooLarge,
+    #[display(fmt = "import file has to be a csv or xlsx file")]
+    UnsupportedImportFile,
 }
 
 /// Struct for formatting error into beautified json
@@ -58,6 +114,34 @@ impl HttpError {
             HttpError::InvalidAuthenticationCredentials => {
                 "invalid authentication credentials".to_string()
             }
+            HttpError::SemesterNotFound => "semester not found".to_string(),
+            HttpError::SubjectNotFound => "subject not found".to_string(),
+            HttpError::ScheduleConflict => "schedule conflict".to_string(),
+            HttpError::OpeningSubjectNotFound => "opening subject not found".to_string(),
+            HttpError::NotEligible => "not eligible".to_string(),
+            HttpError::EnrollmentTimeClash => "enrollment time clash".to_string(),
+            HttpError::AlreadyEnrolled => "already enrolled".to_string(),
+            HttpError::EnrollmentNotFound => "enrollment not found".to_string(),
+            HttpError::AlreadyWaitlisted => "already waitlisted".to_string(),
+            HttpError::RegistrationClosed => "registration closed".to_string(),
+            HttpError::EarlyRegistrationNotAllowed => "early registration not allowed".to_string(),
+            HttpError::RegistrationPeriodsNotFound => "registration periods not found".to_string(),
+            HttpError::UnpaidInvoice => "unpaid invoice".to_string(),
+            HttpError::InvalidWebhookSignature => "invalid webhook signature".to_string(),
+            HttpError::TransactionNotFound => "transaction not found".to_string(),
+            HttpError::CurriculumNotFound => "curriculum not found".to_string(),
+            HttpError::GradingCriteriaNotFound => "grading criteria not found".to_string(),
+            HttpError::NotTeaching => "not teaching".to_string(),
+            HttpError::AssignmentNotFound => "assignment not found".to_string(),
+            HttpError::ScoreExceedsFullScore => "score exceeds full score".to_string(),
+            HttpError::StudentNotEnrolled => "student not enrolled".to_string(),
+            HttpError::GradesAlreadyReleased => "grades already released".to_string(),
+            HttpError::GradesNotReleased => "grades not released".to_string(),
+            HttpError::GradeNotFound => "grade not found".to_string(),
+            HttpError::StudentNotFound => "student not found".to_string(),
+            HttpError::CommentAlreadySubmitted => "comment already submitted".to_string(),
+            HttpError::ImportFileTooLarge => "import file too large".to_string(),
+            HttpError::UnsupportedImportFile => "unsupported import file".to_string(),
         }
     }
 
@@ -84,6 +168,34 @@ impl HttpError {
             HttpError::NoData => StatusCode::BAD_REQUEST,
             HttpError::Forbidden => StatusCode::FORBIDDEN,
             HttpError::InvalidAuthenticationCredentials => StatusCode::BAD_REQUEST,
+            HttpError::SemesterNotFound => StatusCode::NOT_FOUND,
+            HttpError::SubjectNotFound => StatusCode::NOT_FOUND,
+            HttpError::ScheduleConflict => StatusCode::CONFLICT,
+            HttpError::OpeningSubjectNotFound => StatusCode::NOT_FOUND,
+            HttpError::NotEligible => StatusCode::FORBIDDEN,
+            HttpError::EnrollmentTimeClash => StatusCode::CONFLICT,
+            HttpError::AlreadyEnrolled => StatusCode::CONFLICT,
+            HttpError::EnrollmentNotFound => StatusCode::NOT_FOUND,
+            HttpError::AlreadyWaitlisted => StatusCode::CONFLICT,
+            HttpError::RegistrationClosed => StatusCode::FORBIDDEN,
+            HttpError::EarlyRegistrationNotAllowed => StatusCode::FORBIDDEN,
+            HttpError::RegistrationPeriodsNotFound => StatusCode::NOT_FOUND,
+            HttpError::UnpaidInvoice => StatusCode::PAYMENT_REQUIRED,
+            HttpError::InvalidWebhookSignature => StatusCode::UNAUTHORIZED,
+            HttpError::TransactionNotFound => StatusCode::NOT_FOUND,
+            HttpError::CurriculumNotFound => StatusCode::NOT_FOUND,
+            HttpError::GradingCriteriaNotFound => StatusCode::NOT_FOUND,
+            HttpError::NotTeaching => StatusCode::FORBIDDEN,
+            HttpError::AssignmentNotFound => StatusCode::NOT_FOUND,
+            HttpError::ScoreExceedsFullScore => StatusCode::BAD_REQUEST,
+            HttpError::StudentNotEnrolled => StatusCode::BAD_REQUEST,
+            HttpError::GradesAlreadyReleased => StatusCode::CONFLICT,
+            HttpError::GradesNotReleased => StatusCode::CONFLICT,
+            HttpError::GradeNotFound => StatusCode::NOT_FOUND,
+            HttpError::StudentNotFound => StatusCode::NOT_FOUND,
+            HttpError::CommentAlreadySubmitted => StatusCode::CONFLICT,
+            HttpError::ImportFileTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            HttpError::UnsupportedImportFile => StatusCode::UNSUPPORTED_MEDIA_TYPE,
         }
     }
 }
@@ -138,8 +250,8 @@ impl From<argon2::password_hash::Error> for HttpError {
     }
 }
 
-impl From<anyhow::Error> for HttpError {
-    fn from(error: anyhow::Error) -> Self {
+impl From<ger_from_row::Error> for HttpError {
+    fn from(error: ger_from_row::Error) -> Self {
         HttpError::InternalServerError {
             cause: error.to_string(),
         }
@@ -16,6 +16,8 @@ pub struct AuthenticatedClaims {
 }
 
 pub mod admins;
+pub mod professors;
+pub mod students;
 pub mod users;
 
 /// A function to check whether a token is valid inside of the headers.
@@ -0,0 +1,26 @@
+use actix_web::{dev::Payload, FromRequest, HttpRequest};
+use futures_util::future::{ready, Ready};
+
+use crate::{database::Role, errors::HttpError};
+
+use super::{validate_tokens_in_header, AuthenticatedClaims};
+
+pub struct AuthenticatedProfessorClaims(pub AuthenticatedClaims);
+
+impl FromRequest for AuthenticatedProfessorClaims {
+    type Error = HttpError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let claims = match validate_tokens_in_header(req) {
+            Ok(c) => c,
+            Err(e) => return ready(Err(e)),
+        };
+
+        if claims.access_token.claims.rle != Role::Professor {
+            return ready(Err(HttpError::Forbidden));
+        }
+
+        ready(Ok(AuthenticatedProfessorClaims(claims)))
+    }
+}
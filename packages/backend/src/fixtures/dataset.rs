@@ -0,0 +1,568 @@
+use std::collections::HashSet;
+
+use rust_decimal::Decimal;
+use serde::{de::Error as _, Deserialize, Deserializer};
+use time::{format_description::well_known::Rfc3339, macros::format_description};
+
+use crate::{database::DayOfWeek, services::names::PersonName};
+
+use super::FixtureError;
+
+fn timestamp<'de, D: Deserializer<'de>>(deserializer: D) -> Result<time::OffsetDateTime, D::Error> {
+    let datetime = toml::value::Datetime::deserialize(deserializer)?;
+    time::OffsetDateTime::parse(&datetime.to_string(), &Rfc3339).map_err(D::Error::custom)
+}
+
+fn date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<time::Date, D::Error> {
+    let datetime = toml::value::Datetime::deserialize(deserializer)?;
+    time::Date::parse(
+        &datetime.to_string(),
+        format_description!("[year]-[month]-[day]"),
+    )
+    .map_err(D::Error::custom)
+}
+
+fn time_of_day<'de, D: Deserializer<'de>>(deserializer: D) -> Result<time::Time, D::Error> {
+    let datetime = toml::value::Datetime::deserialize(deserializer)?;
+    time::Time::parse(
+        &datetime.to_string(),
+        format_description!("[hour]:[minute]:[second]"),
+    )
+    .map_err(D::Error::custom)
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Faculty {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TuitionRate {
+    pub per_credit: Decimal,
+    pub flat_fee: Decimal,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Curriculum {
+    pub id: String,
+    pub faculty: String,
+    pub name: String,
+    pub tuition_rate: Option<TuitionRate>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Major {
+    pub id: String,
+    pub curriculum: String,
+    /// Two digits put in the `student_representative_id` of the students of the major.
+    pub representative_id: String,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AcademicYear {
+    pub id: String,
+    /// The year in anno domini.
+    pub year: i32,
+    #[serde(deserialize_with = "timestamp")]
+    pub start: time::OffsetDateTime,
+    #[serde(deserialize_with = "timestamp")]
+    pub end: time::OffsetDateTime,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Semester {
+    pub id: String,
+    pub academic_year: String,
+    #[serde(deserialize_with = "timestamp")]
+    pub start: time::OffsetDateTime,
+    #[serde(deserialize_with = "timestamp")]
+    pub end: time::OffsetDateTime,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Room {
+    pub id: String,
+    pub name: String,
+    pub capacity: i32,
+    #[serde(default = "first_floor")]
+    pub floor: i16,
+}
+
+fn first_floor() -> i16 {
+    1
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Building {
+    pub id: String,
+    pub name: String,
+    /// Longitude and latitude.
+    pub coordinates: (f64, f64),
+    #[serde(default)]
+    pub rooms: Vec<Room>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Admin {
+    pub id: String,
+    pub username: String,
+    pub email: String,
+    /// Plain password, it is hashed the same way signing up does when the fixtures are loaded.
+    pub password: String,
+    #[serde(deserialize_with = "date")]
+    pub birthdate: time::Date,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Professor {
+    pub id: String,
+    pub username: String,
+    pub email: String,
+    pub password: String,
+    #[serde(deserialize_with = "date")]
+    pub birthdate: time::Date,
+    #[serde(default)]
+    pub professions: String,
+    pub names: Vec<PersonName>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Student {
+    pub id: String,
+    pub username: String,
+    pub email: String,
+    pub password: String,
+    #[serde(deserialize_with = "date")]
+    pub birthdate: time::Date,
+    pub nid: String,
+    pub major: String,
+    /// The professor advising the student.
+    pub advisor: String,
+    pub first_academic_year: String,
+    pub previous_school_name: String,
+    pub previous_school_gpa: Decimal,
+    pub names: Vec<PersonName>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Subject {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub credit: i32,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GradingCriteriaGrade {
+    pub alphabet: String,
+    pub minimum_score: Decimal,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GradingCriteria {
+    pub id: String,
+    /// The professor owning the criteria.
+    pub owner: String,
+    pub name: String,
+    pub grades: Vec<GradingCriteriaGrade>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EligibleMajor {
+    pub major: String,
+    pub academic_year: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schedule {
+    pub room: String,
+    pub day: DayOfWeek,
+    #[serde(deserialize_with = "time_of_day")]
+    pub start: time::Time,
+    #[serde(deserialize_with = "time_of_day")]
+    pub end: time::Time,
+}
+
+/// A subject opened in a semester.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Offering {
+    pub semester: String,
+    pub subject: String,
+    pub grading_criteria: String,
+    pub capacity: i32,
+    pub professors: Vec<String>,
+    #[serde(default)]
+    pub eligible_majors: Vec<EligibleMajor>,
+    #[serde(default)]
+    pub schedules: Vec<Schedule>,
+    /// Students enrolled in the subject.
+    #[serde(default)]
+    pub enrollments: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ForumCategory {
+    pub id: String,
+    pub name: String,
+    pub representative_id: String,
+    #[serde(default)]
+    pub description: String,
+    /// The user owning the category.
+    pub owner: String,
+    #[serde(default = "black")]
+    pub color_theme: String,
+}
+
+fn black() -> String {
+    "#000000".to_string()
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ForumPostReply {
+    pub id: String,
+    pub author: String,
+    pub content: String,
+    #[serde(deserialize_with = "timestamp")]
+    pub created: time::OffsetDateTime,
+    #[serde(default)]
+    pub upvotes: Vec<String>,
+    #[serde(default)]
+    pub downvotes: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ForumPost {
+    pub id: String,
+    pub category: String,
+    pub author: String,
+    pub name: String,
+    pub content: String,
+    #[serde(deserialize_with = "timestamp")]
+    pub created: time::OffsetDateTime,
+    /// Users who have seen the post.
+    #[serde(default)]
+    pub views: Vec<String>,
+    #[serde(default)]
+    pub upvotes: Vec<String>,
+    #[serde(default)]
+    pub downvotes: Vec<String>,
+    #[serde(default)]
+    pub replies: Vec<ForumPostReply>,
+}
+
+/// A dataset declared in toml, every table is a list of rows such as `[[faculties]]`. Rows refer
+/// to each other by their `id`, and only to rows of the same dataset.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Fixtures {
+    pub faculties: Vec<Faculty>,
+    pub curriculums: Vec<Curriculum>,
+    pub majors: Vec<Major>,
+    pub academic_years: Vec<AcademicYear>,
+    pub semesters: Vec<Semester>,
+    pub buildings: Vec<Building>,
+    pub admins: Vec<Admin>,
+    pub professors: Vec<Professor>,
+    pub students: Vec<Student>,
+    pub subjects: Vec<Subject>,
+    pub grading_criterias: Vec<GradingCriteria>,
+    pub offerings: Vec<Offering>,
+    pub forum_categories: Vec<ForumCategory>,
+    pub forum_posts: Vec<ForumPost>,
+}
+
+/// Check that a row refers to a row of the dataset.
+fn check_reference(
+    known: &HashSet<&str>,
+    row: &str,
+    kind: &str,
+    id: &str,
+) -> Result<(), FixtureError> {
+    if known.contains(id) {
+        return Ok(());
+    }
+
+    Err(FixtureError::Invalid {
+        cause: format!(
+            "{} refers to {} `{}` that is not in the fixtures",
+            row, kind, id
+        ),
+    })
+}
+
+/// Check that every user votes once.
+fn check_votes(row: &str, upvotes: &[String], downvotes: &[String]) -> Result<(), FixtureError> {
+    let mut voters = HashSet::new();
+
+    match upvotes.iter().chain(downvotes).find(|u| !voters.insert(*u)) {
+        Some(user) => Err(FixtureError::Invalid {
+            cause: format!("user `{}` votes on {} more than once", user, row),
+        }),
+        None => Ok(()),
+    }
+}
+
+impl Fixtures {
+    /// Parse a dataset and check that every row refers to rows that are in it.
+    pub fn parse(toml: &str) -> Result<Fixtures, FixtureError> {
+        let fixtures = toml::from_str::<Fixtures>(toml)?;
+        fixtures.validate()?;
+
+        Ok(fixtures)
+    }
+
+    fn validate(&self) -> Result<(), FixtureError> {
+        let faculties = self
+            .faculties
+            .iter()
+            .map(|f| f.id.as_str())
+            .collect::<HashSet<_>>();
+        let curriculums = self
+            .curriculums
+            .iter()
+            .map(|c| c.id.as_str())
+            .collect::<HashSet<_>>();
+        let majors = self
+            .majors
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect::<HashSet<_>>();
+        let academic_years = self
+            .academic_years
+            .iter()
+            .map(|a| a.id.as_str())
+            .collect::<HashSet<_>>();
+        let semesters = self
+            .semesters
+            .iter()
+            .map(|s| s.id.as_str())
+            .collect::<HashSet<_>>();
+        let rooms = self
+            .buildings
+            .iter()
+            .flat_map(|b| b.rooms.iter().map(|r| r.id.as_str()))
+            .collect::<HashSet<_>>();
+        let professors = self
+            .professors
+            .iter()
+            .map(|p| p.id.as_str())
+            .collect::<HashSet<_>>();
+        let students = self
+            .students
+            .iter()
+            .map(|s| s.id.as_str())
+            .collect::<HashSet<_>>();
+        let subjects = self
+            .subjects
+            .iter()
+            .map(|s| s.id.as_str())
+            .collect::<HashSet<_>>();
+        let grading_criterias = self
+            .grading_criterias
+            .iter()
+            .map(|g| g.id.as_str())
+            .collect::<HashSet<_>>();
+        let forum_categories = self
+            .forum_categories
+            .iter()
+            .map(|c| c.id.as_str())
+            .collect::<HashSet<_>>();
+        let users = self
+            .admins
+            .iter()
+            .map(|a| a.id.as_str())
+            .chain(professors.iter().copied())
+            .chain(students.iter().copied())
+            .collect::<HashSet<_>>();
+
+        for curriculum in &self.curriculums {
+            let row = format!("curriculum `{}`", curriculum.id);
+            check_reference(&faculties, &row, "faculty", &curriculum.faculty)?;
+        }
+
+        for major in &self.majors {
+            let row = format!("major `{}`", major.id);
+            check_reference(&curriculums, &row, "curriculum", &major.curriculum)?;
+        }
+
+        for semester in &self.semesters {
+            let row = format!("semester `{}`", semester.id);
+            check_reference(
+                &academic_years,
+                &row,
+                "academic year",
+                &semester.academic_year,
+            )?;
+        }
+
+        for student in &self.students {
+            let row = format!("student `{}`", student.id);
+            check_reference(&majors, &row, "major", &student.major)?;
+            check_reference(&professors, &row, "professor", &student.advisor)?;
+            check_reference(
+                &academic_years,
+                &row,
+                "academic year",
+                &student.first_academic_year,
+            )?;
+        }
+
+        for grading_criteria in &self.grading_criterias {
+            let row = format!("grading criteria `{}`", grading_criteria.id);
+            check_reference(&professors, &row, "professor", &grading_criteria.owner)?;
+        }
+
+        for offering in &self.offerings {
+            let row = format!(
+                "offering of subject `{}` in semester `{}`",
+                offering.subject, offering.semester
+            );
+            check_reference(&semesters, &row, "semester", &offering.semester)?;
+            check_reference(&subjects, &row, "subject", &offering.subject)?;
+            check_reference(
+                &grading_criterias,
+                &row,
+                "grading criteria",
+                &offering.grading_criteria,
+            )?;
+
+            for professor in &offering.professors {
+                check_reference(&professors, &row, "professor", professor)?;
+            }
+            for eligible_major in &offering.eligible_majors {
+                check_reference(&majors, &row, "major", &eligible_major.major)?;
+                check_reference(
+                    &academic_years,
+                    &row,
+                    "academic year",
+                    &eligible_major.academic_year,
+                )?;
+            }
+            for schedule in &offering.schedules {
+                check_reference(&rooms, &row, "room", &schedule.room)?;
+            }
+            for student in &offering.enrollments {
+                check_reference(&students, &row, "student", student)?;
+            }
+        }
+
+        for category in &self.forum_categories {
+            let row = format!("forum category `{}`", category.id);
+            check_reference(&users, &row, "user", &category.owner)?;
+        }
+
+        for post in &self.forum_posts {
+            let row = format!("forum post `{}`", post.id);
+            check_reference(&forum_categories, &row, "forum category", &post.category)?;
+            check_reference(&users, &row, "user", &post.author)?;
+            check_votes(&row, &post.upvotes, &post.downvotes)?;
+
+            for user in post
+                .views
+                .iter()
+                .chain(&post.upvotes)
+                .chain(&post.downvotes)
+            {
+                check_reference(&users, &row, "user", user)?;
+            }
+
+            for reply in &post.replies {
+                let row = format!("forum post reply `{}`", reply.id);
+                check_reference(&users, &row, "user", &reply.author)?;
+                check_votes(&row, &reply.upvotes, &reply.downvotes)?;
+
+                for user in reply.upvotes.iter().chain(&reply.downvotes) {
+                    check_reference(&users, &row, "user", user)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Fixtures;
+
+    #[test]
+    fn test_parse_demo() {
+        let fixtures = Fixtures::parse(include_str!("../../fixtures/demo.toml")).unwrap();
+
+        assert_eq!(fixtures.students.len(), 4);
+        assert_eq!(fixtures.buildings[0].rooms[1].floor, 2);
+        assert_eq!(fixtures.forum_posts[0].replies.len(), 2);
+    }
+
+    #[test]
+    fn test_validate() {
+        let fixtures = r##"
+            [[faculties]]
+            id = "science"
+            name = "Science"
+
+            [[curriculums]]
+            id = "bachelor"
+            faculty = "engineering"
+            name = "Bachelor"
+        "##;
+        assert_eq!(
+            Fixtures::parse(fixtures).err().unwrap().to_string(),
+            "invalid fixtures: curriculum `bachelor` refers to faculty `engineering` that is not in the fixtures"
+        );
+
+        let fixtures = r##"
+            [[admins]]
+            id = "admin"
+            username = "admin"
+            email = "admin@ger.com"
+            password = "password"
+            birthdate = 1990-01-01
+
+            [[forum_categories]]
+            id = "news"
+            name = "News"
+            representative_id = "news"
+            owner = "admin"
+
+            [[forum_posts]]
+            id = "post"
+            category = "news"
+            author = "admin"
+            name = "Hello"
+            content = "Hello"
+            created = 2022-06-01T09:00:00+07:00
+            upvotes = ["admin"]
+            downvotes = ["admin"]
+        "##;
+        assert_eq!(
+            Fixtures::parse(fixtures).err().unwrap().to_string(),
+            "invalid fixtures: user `admin` votes on forum post `post` more than once"
+        );
+
+        assert!(Fixtures::parse("[[faculties]]\nid = \"science\"\n").is_err());
+    }
+}
@@ -0,0 +1,697 @@
+use std::collections::HashMap;
+
+use argon2::{password_hash::SaltString, PasswordHasher};
+use deadpool_postgres::{Client, GenericClient};
+use ger_from_row::ToRow;
+use rand_core::OsRng;
+use rust_decimal::Decimal;
+
+use crate::{
+    constants::{create_argon2_context, AD_BE_YEAR_DIFFERENCE, ARGON2_PEPPER_STRING},
+    database::{insert_rows, DayOfWeek, Role},
+    services::{
+        names::PersonName,
+        signups::{generate_representative_id, NewStudent, NewStudentName, NewUser},
+    },
+};
+
+use super::{dataset::Fixtures, FixtureError};
+
+#[derive(ToRow)]
+#[torow(table = "faculties")]
+struct NewFaculty<'a> {
+    faculty_id: &'a str,
+    faculty_name: &'a str,
+}
+
+#[derive(ToRow)]
+#[torow(table = "curriculums")]
+struct NewCurriculum<'a> {
+    curriculum_id: &'a str,
+    faculty_id: &'a str,
+    curriculum_name: &'a str,
+}
+
+#[derive(ToRow)]
+#[torow(table = "curriculum_tuition_rates")]
+struct NewCurriculumTuitionRate<'a> {
+    curriculum_id: &'a str,
+    curriculum_tuition_rate_per_credit: Decimal,
+    curriculum_tuition_rate_flat_fee: Decimal,
+}
+
+#[derive(ToRow)]
+#[torow(table = "majors")]
+struct NewMajor<'a> {
+    major_id: &'a str,
+    major_representative_id: &'a str,
+    curriculum_id: &'a str,
+    major_name: &'a str,
+}
+
+#[derive(ToRow)]
+#[torow(table = "academic_years")]
+struct NewAcademicYear<'a> {
+    academic_year_id: &'a str,
+    academic_year_anno_domini_year: String,
+    academic_year_start_timestamp: time::OffsetDateTime,
+    academic_year_end_timestamp: time::OffsetDateTime,
+}
+
+#[derive(ToRow)]
+#[torow(table = "semesters")]
+struct NewSemester<'a> {
+    semester_id: &'a str,
+    academic_year_id: &'a str,
+    semester_start_timestamp: time::OffsetDateTime,
+    semester_end_timestamp: time::OffsetDateTime,
+}
+
+#[derive(ToRow)]
+#[torow(table = "buildings")]
+struct NewBuilding<'a> {
+    building_id: &'a str,
+    building_name: &'a str,
+    building_coordinates: geo_types::Point<f64>,
+    building_created_timestamp: time::OffsetDateTime,
+}
+
+#[derive(ToRow)]
+#[torow(table = "rooms")]
+struct NewRoom<'a> {
+    room_id: &'a str,
+    building_id: &'a str,
+    room_name: &'a str,
+    room_capacity: i32,
+    room_floor: i16,
+}
+
+#[derive(ToRow)]
+#[torow(table = "professors")]
+struct NewProfessor<'a> {
+    professor_id: &'a str,
+    professor_professions: &'a str,
+}
+
+#[derive(ToRow)]
+#[torow(table = "professor_names")]
+struct NewProfessorName<'a> {
+    professor_name_id: String,
+    professor_id: &'a str,
+    professor_name_language: &'a str,
+    professor_first_name: &'a str,
+    professor_middle_name: &'a str,
+    professor_last_name: &'a str,
+}
+
+#[derive(ToRow)]
+#[torow(table = "student_representative_id_sequences")]
+struct NewStudentRepresentativeIdSequence<'a> {
+    major_id: &'a str,
+    first_academic_year_id: &'a str,
+    student_representative_id_sequence_last_number: i32,
+}
+
+#[derive(ToRow)]
+#[torow(table = "subjects")]
+struct NewSubject<'a> {
+    subject_id: &'a str,
+    subject_name: &'a str,
+    subject_description: &'a str,
+    subject_credit: i32,
+    subject_created_timestamp: time::OffsetDateTime,
+}
+
+#[derive(ToRow)]
+#[torow(table = "grading_criterias")]
+struct NewGradingCriteria<'a> {
+    grading_criteria_id: &'a str,
+    user_id: &'a str,
+    grading_criteria_name: &'a str,
+    grading_criteria_created_timestamp: time::OffsetDateTime,
+}
+
+#[derive(ToRow)]
+#[torow(table = "grading_criteria_grades")]
+struct NewGradingCriteriaGrade<'a> {
+    grading_criteria_grade_id: String,
+    grading_criteria_id: &'a str,
+    grading_criteria_grade_alphabet: &'a str,
+    grading_criteria_grade_minimum_score: Decimal,
+}
+
+#[derive(ToRow)]
+#[torow(table = "opening_subjects_in_semester_subject_descriptions")]
+struct NewOpeningSubject<'a> {
+    semester_id: &'a str,
+    subject_id: &'a str,
+    grading_criteria_id: &'a str,
+    subject_capacity: i32,
+}
+
+#[derive(ToRow)]
+#[torow(table = "opening_subjects_in_semester_professors")]
+struct NewOpeningSubjectProfessor<'a> {
+    semester_id: &'a str,
+    subject_id: &'a str,
+    professor_id: &'a str,
+}
+
+#[derive(ToRow)]
+#[torow(table = "opening_subjects_in_semester_eligible_majors")]
+struct NewOpeningSubjectEligibleMajor<'a> {
+    semester_id: &'a str,
+    subject_id: &'a str,
+    major_id: &'a str,
+    academic_year_id: &'a str,
+}
+
+#[derive(ToRow)]
+#[torow(table = "opening_subjects_in_semester_schedules")]
+struct NewOpeningSubjectSchedule<'a> {
+    semester_id: &'a str,
+    subject_id: &'a str,
+    room_id: &'a str,
+    day_of_week: &'a DayOfWeek,
+    start_time_of_day: time::Time,
+    end_time_of_day: time::Time,
+}
+
+#[derive(ToRow)]
+#[torow(table = "student_enrollments")]
+struct NewStudentEnrollment<'a> {
+    semester_id: &'a str,
+    subject_id: &'a str,
+    student_id: &'a str,
+}
+
+#[derive(ToRow)]
+#[torow(table = "forum_categories")]
+struct NewForumCategory<'a> {
+    forum_category_id: &'a str,
+    forum_category_name: &'a str,
+    forum_category_representative_id: &'a str,
+    forum_category_description: &'a str,
+    user_id: &'a str,
+    forum_category_color_theme: &'a str,
+}
+
+#[derive(ToRow)]
+#[torow(table = "forum_posts")]
+struct NewForumPost<'a> {
+    forum_post_id: &'a str,
+    forum_post_name: &'a str,
+    user_id: &'a str,
+    forum_category_id: &'a str,
+    forum_post_content: &'a str,
+    forum_post_created_timestamp: time::OffsetDateTime,
+    forum_post_last_active_timestamp: time::OffsetDateTime,
+}
+
+#[derive(ToRow)]
+#[torow(table = "forum_post_replies")]
+struct NewForumPostReply<'a> {
+    forum_post_reply_id: &'a str,
+    forum_post_id: &'a str,
+    user_id: &'a str,
+    forum_post_reply_content: &'a str,
+    forum_post_reply_created_timestamp: time::OffsetDateTime,
+}
+
+#[derive(ToRow)]
+#[torow(table = "forum_post_views")]
+struct NewForumPostView<'a> {
+    forum_post_id: &'a str,
+    user_id: &'a str,
+}
+
+#[derive(ToRow)]
+#[torow(table = "forum_post_votes")]
+struct NewForumPostVote<'a> {
+    forum_post_id: &'a str,
+    user_id: &'a str,
+    forum_post_vote_increment: i16,
+}
+
+#[derive(ToRow)]
+#[torow(table = "forum_post_reply_votes")]
+struct NewForumPostReplyVote<'a> {
+    forum_post_reply_id: &'a str,
+    user_id: &'a str,
+    forum_post_reply_vote_increment: i16,
+}
+
+/// Hashes of the passwords of the fixtures, users sharing a password share its hash since hashing
+/// is what takes most of the time of loading.
+struct Passwords<'a> {
+    context: argon2::Argon2<'static>,
+    hashes: HashMap<&'a str, String>,
+}
+
+impl<'a> Passwords<'a> {
+    fn new() -> Result<Self, FixtureError> {
+        let context =
+            create_argon2_context(&ARGON2_PEPPER_STRING).map_err(|e| FixtureError::Password {
+                cause: e.to_string(),
+            })?;
+
+        Ok(Self {
+            context,
+            hashes: HashMap::new(),
+        })
+    }
+
+    fn hash(&mut self, password: &'a str) -> Result<String, FixtureError> {
+        if let Some(hash) = self.hashes.get(password) {
+            return Ok(hash.clone());
+        }
+
+        let salt = SaltString::generate(&mut OsRng);
+        let hash = self
+            .context
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|e| FixtureError::Password {
+                cause: e.to_string(),
+            })?
+            .to_string();
+        self.hashes.insert(password, hash.clone());
+
+        Ok(hash)
+    }
+}
+
+/// `1` for every upvote and `-1` for every downvote.
+fn vote_increments<'a>(
+    upvotes: &'a [String],
+    downvotes: &'a [String],
+) -> impl Iterator<Item = (&'a str, i16)> {
+    upvotes
+        .iter()
+        .map(|u| (u.as_str(), 1))
+        .chain(downvotes.iter().map(|u| (u.as_str(), -1)))
+}
+
+/// Load every row of `fixtures` in one transaction, into a database the migrations are applied
+/// to. Students get their `student_representative_id` in the order they are written in, the same
+/// way signing them up in that order would.
+pub async fn load(client: &mut Client, fixtures: &Fixtures) -> Result<(), FixtureError> {
+    let now = time::OffsetDateTime::now_utc();
+    let mut passwords = Passwords::new()?;
+
+    let faculties = fixtures
+        .faculties
+        .iter()
+        .map(|f| NewFaculty {
+            faculty_id: &f.id,
+            faculty_name: &f.name,
+        })
+        .collect::<Vec<_>>();
+
+    let curriculums = fixtures
+        .curriculums
+        .iter()
+        .map(|c| NewCurriculum {
+            curriculum_id: &c.id,
+            faculty_id: &c.faculty,
+            curriculum_name: &c.name,
+        })
+        .collect::<Vec<_>>();
+
+    let tuition_rates = fixtures
+        .curriculums
+        .iter()
+        .filter_map(|c| {
+            c.tuition_rate.as_ref().map(|r| NewCurriculumTuitionRate {
+                curriculum_id: &c.id,
+                curriculum_tuition_rate_per_credit: r.per_credit,
+                curriculum_tuition_rate_flat_fee: r.flat_fee,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let majors = fixtures
+        .majors
+        .iter()
+        .map(|m| NewMajor {
+            major_id: &m.id,
+            major_representative_id: &m.representative_id,
+            curriculum_id: &m.curriculum,
+            major_name: &m.name,
+        })
+        .collect::<Vec<_>>();
+
+    let academic_years = fixtures
+        .academic_years
+        .iter()
+        .map(|a| NewAcademicYear {
+            academic_year_id: &a.id,
+            academic_year_anno_domini_year: a.year.to_string(),
+            academic_year_start_timestamp: a.start,
+            academic_year_end_timestamp: a.end,
+        })
+        .collect::<Vec<_>>();
+
+    let semesters = fixtures
+        .semesters
+        .iter()
+        .map(|s| NewSemester {
+            semester_id: &s.id,
+            academic_year_id: &s.academic_year,
+            semester_start_timestamp: s.start,
+            semester_end_timestamp: s.end,
+        })
+        .collect::<Vec<_>>();
+
+    let buildings = fixtures
+        .buildings
+        .iter()
+        .map(|b| NewBuilding {
+            building_id: &b.id,
+            building_name: &b.name,
+            building_coordinates: geo_types::Point::new(b.coordinates.0, b.coordinates.1),
+            building_created_timestamp: now,
+        })
+        .collect::<Vec<_>>();
+
+    let rooms = fixtures
+        .buildings
+        .iter()
+        .flat_map(|b| {
+            b.rooms.iter().map(move |r| NewRoom {
+                room_id: &r.id,
+                building_id: &b.id,
+                room_name: &r.name,
+                room_capacity: r.capacity,
+                room_floor: r.floor,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut users = vec![];
+    for admin in &fixtures.admins {
+        users.push(NewUser {
+            user_id: &admin.id,
+            user_username: admin.username.clone(),
+            user_email: admin.email.clone(),
+            user_password: passwords.hash(&admin.password)?,
+            user_role: Role::Admin,
+            user_birthdate: admin.birthdate,
+        });
+    }
+    for professor in &fixtures.professors {
+        users.push(NewUser {
+            user_id: &professor.id,
+            user_username: professor.username.clone(),
+            user_email: professor.email.clone(),
+            user_password: passwords.hash(&professor.password)?,
+            user_role: Role::Professor,
+            user_birthdate: professor.birthdate,
+        });
+    }
+    for student in &fixtures.students {
+        users.push(NewUser {
+            user_id: &student.id,
+            user_username: student.username.clone(),
+            user_email: student.email.clone(),
+            user_password: passwords.hash(&student.password)?,
+            user_role: Role::Student,
+            user_birthdate: student.birthdate,
+        });
+    }
+
+    let professors = fixtures
+        .professors
+        .iter()
+        .map(|p| NewProfessor {
+            professor_id: &p.id,
+            professor_professions: &p.professions,
+        })
+        .collect::<Vec<_>>();
+
+    let professor_names = fixtures
+        .professors
+        .iter()
+        .flat_map(|p| {
+            p.names.iter().map(move |n: &PersonName| NewProfessorName {
+                professor_name_id: format!("{}-{}", p.id, n.language),
+                professor_id: &p.id,
+                professor_name_language: &n.language,
+                professor_first_name: &n.first_name,
+                professor_middle_name: &n.middle_name,
+                professor_last_name: &n.last_name,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let major_representative_ids = fixtures
+        .majors
+        .iter()
+        .map(|m| (m.id.as_str(), m.representative_id.as_str()))
+        .collect::<HashMap<_, _>>();
+    let years = fixtures
+        .academic_years
+        .iter()
+        .map(|a| (a.id.as_str(), a.year))
+        .collect::<HashMap<_, _>>();
+
+    // running numbers of every major and first academic year, in the order the groups appear.
+    let mut sequences: Vec<NewStudentRepresentativeIdSequence> = vec![];
+    let mut students = Vec::with_capacity(fixtures.students.len());
+    for student in &fixtures.students {
+        let sequence = match sequences.iter_mut().find(|s| {
+            s.major_id == student.major && s.first_academic_year_id == student.first_academic_year
+        }) {
+            Some(sequence) => sequence,
+            None => {
+                sequences.push(NewStudentRepresentativeIdSequence {
+                    major_id: &student.major,
+                    first_academic_year_id: &student.first_academic_year,
+                    student_representative_id_sequence_last_number: 0,
+                });
+                sequences.last_mut().unwrap()
+            }
+        };
+        sequence.student_representative_id_sequence_last_number += 1;
+
+        let representative_id = generate_representative_id(
+            years[student.first_academic_year.as_str()] + AD_BE_YEAR_DIFFERENCE as i32,
+            major_representative_ids[student.major.as_str()],
+            sequence.student_representative_id_sequence_last_number as usize,
+        )
+        .map_err(|e| FixtureError::Invalid {
+            cause: format!("student `{}`: {}", student.id, e),
+        })?;
+
+        students.push(NewStudent {
+            student_id: &student.id,
+            student_representative_id: representative_id,
+            student_nid: &student.nid,
+            student_previous_school_name: &student.previous_school_name,
+            student_previous_school_gpa: student.previous_school_gpa,
+            major_id: &student.major,
+            professor_id: &student.advisor,
+            first_academic_year_id: &student.first_academic_year,
+        });
+    }
+
+    let student_names = fixtures
+        .students
+        .iter()
+        .flat_map(|s| {
+            s.names.iter().map(move |n| NewStudentName {
+                student_name_id: format!("{}-{}", s.id, n.language),
+                student_id: &s.id,
+                student_name_language: &n.language,
+                student_first_name: &n.first_name,
+                student_middle_name: &n.middle_name,
+                student_last_name: &n.last_name,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let subjects = fixtures
+        .subjects
+        .iter()
+        .map(|s| NewSubject {
+            subject_id: &s.id,
+            subject_name: &s.name,
+            subject_description: &s.description,
+            subject_credit: s.credit,
+            subject_created_timestamp: now,
+        })
+        .collect::<Vec<_>>();
+
+    let grading_criterias = fixtures
+        .grading_criterias
+        .iter()
+        .map(|g| NewGradingCriteria {
+            grading_criteria_id: &g.id,
+            user_id: &g.owner,
+            grading_criteria_name: &g.name,
+            grading_criteria_created_timestamp: now,
+        })
+        .collect::<Vec<_>>();
+
+    let grading_criteria_grades = fixtures
+        .grading_criterias
+        .iter()
+        .flat_map(|g| {
+            g.grades
+                .iter()
+                .enumerate()
+                .map(move |(i, grade)| NewGradingCriteriaGrade {
+                    grading_criteria_grade_id: format!("{}-{}", g.id, i),
+                    grading_criteria_id: &g.id,
+                    grading_criteria_grade_alphabet: &grade.alphabet,
+                    grading_criteria_grade_minimum_score: grade.minimum_score,
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let mut opening_subjects = vec![];
+    let mut opening_subject_professors = vec![];
+    let mut opening_subject_eligible_majors = vec![];
+    let mut opening_subject_schedules = vec![];
+    let mut enrollments = vec![];
+    for offering in &fixtures.offerings {
+        let semester_id = offering.semester.as_str();
+        let subject_id = offering.subject.as_str();
+
+        opening_subjects.push(NewOpeningSubject {
+            semester_id,
+            subject_id,
+            grading_criteria_id: &offering.grading_criteria,
+            subject_capacity: offering.capacity,
+        });
+        opening_subject_professors.extend(offering.professors.iter().map(|p| {
+            NewOpeningSubjectProfessor {
+                semester_id,
+                subject_id,
+                professor_id: p,
+            }
+        }));
+        opening_subject_eligible_majors.extend(offering.eligible_majors.iter().map(|e| {
+            NewOpeningSubjectEligibleMajor {
+                semester_id,
+                subject_id,
+                major_id: &e.major,
+                academic_year_id: &e.academic_year,
+            }
+        }));
+        opening_subject_schedules.extend(offering.schedules.iter().map(|s| {
+            NewOpeningSubjectSchedule {
+                semester_id,
+                subject_id,
+                room_id: &s.room,
+                day_of_week: &s.day,
+                start_time_of_day: s.start,
+                end_time_of_day: s.end,
+            }
+        }));
+        enrollments.extend(offering.enrollments.iter().map(|s| NewStudentEnrollment {
+            semester_id,
+            subject_id,
+            student_id: s,
+        }));
+    }
+
+    let forum_categories = fixtures
+        .forum_categories
+        .iter()
+        .map(|c| NewForumCategory {
+            forum_category_id: &c.id,
+            forum_category_name: &c.name,
+            forum_category_representative_id: &c.representative_id,
+            forum_category_description: &c.description,
+            user_id: &c.owner,
+            forum_category_color_theme: &c.color_theme,
+        })
+        .collect::<Vec<_>>();
+
+    let mut forum_posts = vec![];
+    let mut forum_post_replies = vec![];
+    let mut forum_post_views = vec![];
+    let mut forum_post_votes = vec![];
+    let mut forum_post_reply_votes = vec![];
+    for post in &fixtures.forum_posts {
+        // a post is last active when its latest reply is written.
+        let last_active = post
+            .replies
+            .iter()
+            .map(|r| r.created)
+            .fold(post.created, |latest, created| latest.max(created));
+
+        forum_posts.push(NewForumPost {
+            forum_post_id: &post.id,
+            forum_post_name: &post.name,
+            user_id: &post.author,
+            forum_category_id: &post.category,
+            forum_post_content: &post.content,
+            forum_post_created_timestamp: post.created,
+            forum_post_last_active_timestamp: last_active,
+        });
+        forum_post_views.extend(post.views.iter().map(|u| NewForumPostView {
+            forum_post_id: &post.id,
+            user_id: u,
+        }));
+        forum_post_votes.extend(vote_increments(&post.upvotes, &post.downvotes).map(
+            |(user_id, increment)| NewForumPostVote {
+                forum_post_id: &post.id,
+                user_id,
+                forum_post_vote_increment: increment,
+            },
+        ));
+
+        for reply in &post.replies {
+            forum_post_replies.push(NewForumPostReply {
+                forum_post_reply_id: &reply.id,
+                forum_post_id: &post.id,
+                user_id: &reply.author,
+                forum_post_reply_content: &reply.content,
+                forum_post_reply_created_timestamp: reply.created,
+            });
+            forum_post_reply_votes.extend(vote_increments(&reply.upvotes, &reply.downvotes).map(
+                |(user_id, increment)| NewForumPostReplyVote {
+                    forum_post_reply_id: &reply.id,
+                    user_id,
+                    forum_post_reply_vote_increment: increment,
+                },
+            ));
+        }
+    }
+
+    let transaction = client.transaction().await?;
+
+    insert_rows(&transaction, &faculties).await?;
+    insert_rows(&transaction, &curriculums).await?;
+    insert_rows(&transaction, &tuition_rates).await?;
+    insert_rows(&transaction, &majors).await?;
+    insert_rows(&transaction, &academic_years).await?;
+    insert_rows(&transaction, &semesters).await?;
+    insert_rows(&transaction, &buildings).await?;
+    insert_rows(&transaction, &rooms).await?;
+    insert_rows(&transaction, &users).await?;
+    insert_rows(&transaction, &professors).await?;
+    insert_rows(&transaction, &professor_names).await?;
+    insert_rows(&transaction, &students).await?;
+    insert_rows(&transaction, &student_names).await?;
+    insert_rows(&transaction, &sequences).await?;
+    insert_rows(&transaction, &subjects).await?;
+    insert_rows(&transaction, &grading_criterias).await?;
+    insert_rows(&transaction, &grading_criteria_grades).await?;
+    insert_rows(&transaction, &opening_subjects).await?;
+    insert_rows(&transaction, &opening_subject_professors).await?;
+    insert_rows(&transaction, &opening_subject_eligible_majors).await?;
+    insert_rows(&transaction, &opening_subject_schedules).await?;
+    insert_rows(&transaction, &enrollments).await?;
+    insert_rows(&transaction, &forum_categories).await?;
+    insert_rows(&transaction, &forum_posts).await?;
+    insert_rows(&transaction, &forum_post_replies).await?;
+    insert_rows(&transaction, &forum_post_views).await?;
+    insert_rows(&transaction, &forum_post_votes).await?;
+    insert_rows(&transaction, &forum_post_reply_votes).await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! Datasets declared in toml files such as `fixtures/demo.toml`, loaded into a database that the
+//! migrations are applied to. Tests load them into a schema of their own with [fresh_schema], and
+//! `ger fixtures load <file>` loads them into the database of `.env.local` for local development.
+//!
+//! Ids of the rows that are not written in the file, such as names and grades, are made from the
+//! ids that are, so loading the same file twice gives the same database.
+
+mod dataset;
+mod load;
+
+use derive_more::{Display, Error};
+
+use crate::migrations::MigrationError;
+
+pub use dataset::Fixtures;
+pub use load::load;
+
+#[derive(Debug, Display, Error)]
+pub enum FixtureError {
+    #[display(fmt = "cannot read the fixtures: {}", _0)]
+    Toml(toml::de::Error),
+    #[display(fmt = "invalid fixtures: {}", cause)]
+    Invalid { cause: String },
+    #[display(fmt = "{}", _0)]
+    Postgres(tokio_postgres::Error),
+    #[display(fmt = "cannot get a postgres connection: {}", _0)]
+    Pool(deadpool_postgres::PoolError),
+    #[display(fmt = "cannot create a postgres pool: {}", _0)]
+    CreatePool(deadpool_postgres::CreatePoolError),
+    #[display(fmt = "{}", _0)]
+    Migration(MigrationError),
+    #[display(fmt = "cannot hash a password of the fixtures: {}", cause)]
+    Password { cause: String },
+}
+
+impl From<toml::de::Error> for FixtureError {
+    fn from(e: toml::de::Error) -> Self {
+        Self::Toml(e)
+    }
+}
+
+impl From<tokio_postgres::Error> for FixtureError {
+    fn from(e: tokio_postgres::Error) -> Self {
+        Self::Postgres(e)
+    }
+}
+
+impl From<deadpool_postgres::PoolError> for FixtureError {
+    fn from(e: deadpool_postgres::PoolError) -> Self {
+        Self::Pool(e)
+    }
+}
+
+impl From<deadpool_postgres::CreatePoolError> for FixtureError {
+    fn from(e: deadpool_postgres::CreatePoolError) -> Self {
+        Self::CreatePool(e)
+    }
+}
+
+impl From<MigrationError> for FixtureError {
+    fn from(e: MigrationError) -> Self {
+        Self::Migration(e)
+    }
+}
+
+/// Create `schema` from scratch, dropping it first if it exists, apply the migrations to it and
+/// load `fixtures` into it. Every connection of the returned pool uses the schema, so tests
+/// running at the same time do not see each other's rows as long as their schemas differ.
+#[cfg(test)]
+pub async fn fresh_schema(
+    config: &deadpool_postgres::Config,
+    schema: &str,
+    fixtures: &Fixtures,
+) -> Result<deadpool_postgres::Pool, FixtureError> {
+    let mut config = config.clone();
+    config.options = Some(format!("-c search_path={},public", schema));
+
+    let pool = config.create_pool(
+        Some(deadpool_postgres::Runtime::Tokio1),
+        tokio_postgres::NoTls,
+    )?;
+    let mut client = pool.get().await?;
+
+    client
+        .batch_execute(&format!(
+            "drop schema if exists {0} cascade; create schema {0};",
+            schema
+        ))
+        .await?;
+    crate::migrations::migrate(&mut client).await?;
+    load(&mut client, fixtures).await?;
+
+    Ok(pool)
+}
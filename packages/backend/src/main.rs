@@ -5,14 +5,22 @@ use tracing_actix_web::TracingLogger;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
-use crate::{openapi::apidoc::ApiDoc, shared_app_data::SharedAppData, startup::*};
+use crate::{
+    constants::{ENABLE_MOCK_PAYMENTS, PAYMENT_WEBHOOK_SECRET},
+    openapi::apidoc::ApiDoc,
+    shared_app_data::SharedAppData,
+    startup::*,
+};
 
 mod constants;
 mod database;
 mod errors;
 mod extractors;
+mod fixtures;
+mod migrations;
 mod openapi;
 mod routes;
+mod services;
 mod shared_app_data;
 mod startup;
 
@@ -27,10 +35,30 @@ async fn main() -> std::io::Result<()> {
         .create_pool(Some(Runtime::Tokio1), NoTls)
         .expect("cannot create postgres pool from a given config");
 
+    // `ger migrate` and `ger fixtures` commands change the database without starting the server.
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if !args.is_empty() {
+        if let Err(e) = run_command(&pool, &args).await {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+
+        return Ok(());
+    }
+
     // log setup, guard has to stay there, cannot be dropped, if dropped could result in weird
     // behavior of logging.
     let _guard = init_telemetry();
 
+    // payment webhooks signed with an empty secret could be forged by anyone.
+    assert!(
+        !PAYMENT_WEBHOOK_SECRET.is_empty(),
+        "GER_PAYMENT_WEBHOOK_SECRET cannot be empty"
+    );
+
+    // migrations setup, instances starting together wait for the first one to apply them.
+    apply_migrations(&pool).await;
+
     // openapi setup
     let openapi = ApiDoc::openapi();
 
@@ -120,6 +148,10 @@ async fn main() -> std::io::Result<()> {
                 "/users",
                 web::get().to(crate::routes::users::get_users_list::handler),
             )
+            .route(
+                "/users/notifications",
+                web::get().to(crate::routes::users::get_notifications_list::handler),
+            )
             .route(
                 "/users/{user_id}/profile-image",
                 web::get().to(crate::routes::users::get_user_profile_image::handler),
@@ -128,6 +160,184 @@ async fn main() -> std::io::Result<()> {
                 "/students/signup",
                 web::post().to(crate::routes::students::signup::handler),
             )
+            .route(
+                "/students/signup/import",
+                web::post().to(crate::routes::students::post_signup_import::handler),
+            )
+            .route(
+                "/students/waitlists",
+                web::get().to(crate::routes::students::get_waitlists_list::handler),
+            )
+            .route(
+                "/students/invoices",
+                web::get().to(crate::routes::students::get_invoices_list::handler),
+            )
+            .route(
+                "/curriculums/{curriculum_id}/tuition-rate",
+                web::put().to(crate::routes::curriculums::put_tuition_rate::handler),
+            )
+            .route(
+                "/payments/webhook",
+                web::post().to(crate::routes::payments::post_payment_webhook::handler),
+            )
+            .configure(|config| {
+                // the mock gateway lets admins mark invoices paid, it is never served in production.
+                if *ENABLE_MOCK_PAYMENTS {
+                    config.route(
+                        "/payments/mock",
+                        web::post().to(crate::routes::payments::post_mock_payment::handler),
+                    );
+                }
+            })
+            .route(
+                "/grading-criterias",
+                web::get()
+                    .to(crate::routes::grading_criterias::get_grading_criterias_list::handler),
+            )
+            .route(
+                "/grading-criterias",
+                web::post().to(crate::routes::grading_criterias::post_grading_criteria::handler),
+            )
+            .route(
+                "/grading-criterias/{grading_criteria_id}/clone",
+                web::post()
+                    .to(crate::routes::grading_criterias::post_grading_criteria_clone::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/grading-criteria",
+                web::put()
+                    .to(crate::routes::semesters::subjects::put_subject_grading_criteria::handler),
+            )
+            .route(
+                "/students/scores",
+                web::get().to(crate::routes::students::get_scores_list::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/assignments",
+                web::get().to(crate::routes::semesters::subjects::get_assignments_list::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/assignments",
+                web::post().to(crate::routes::semesters::subjects::post_assignment::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/assignments/{assignment_id}/scores",
+                web::get().to(crate::routes::semesters::subjects::get_assignment_scores::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/assignments/{assignment_id}/scores",
+                web::put().to(crate::routes::semesters::subjects::put_assignment_scores::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/grades",
+                web::get().to(crate::routes::semesters::subjects::get_subject_grades::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/grades/compute",
+                web::post()
+                    .to(crate::routes::semesters::subjects::post_grades_computation::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/grades/release",
+                web::post().to(crate::routes::semesters::subjects::post_grades_release::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/grades/{student_id}",
+                web::put().to(crate::routes::semesters::subjects::put_grade_override::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/grades/{student_id}/amendments",
+                web::post().to(crate::routes::semesters::subjects::post_grade_amendment::handler),
+            )
+            .route(
+                "/students/grades",
+                web::get().to(crate::routes::students::get_grades_list::handler),
+            )
+            .route(
+                "/students/{student_id}/transcript",
+                web::get().to(crate::routes::students::get_transcript::handler),
+            )
+            .route(
+                "/students/{student_id}/transcript/print",
+                web::get().to(crate::routes::students::get_transcript_print::handler),
+            )
+            .route(
+                "/students/{student_id}/degree-audit",
+                web::get().to(crate::routes::students::get_degree_audit::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/comments",
+                web::get().to(crate::routes::semesters::subjects::get_subject_comments::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/comments",
+                web::post().to(crate::routes::semesters::subjects::post_subject_comment::handler),
+            )
+            .route(
+                "/comments",
+                web::get().to(crate::routes::comments::get_comments_list::handler),
+            )
+            .route(
+                "/professors",
+                web::get().to(crate::routes::professors::get_professors_list::handler),
+            )
+            .route(
+                "/professors",
+                web::post().to(crate::routes::professors::post_professor::handler),
+            )
+            .route(
+                "/professors/{professor_id}/advisees",
+                web::get().to(crate::routes::professors::get_advisees_list::handler),
+            )
+            .route(
+                "/students",
+                web::get().to(crate::routes::students::get_students_list::handler),
+            )
+            .route(
+                "/students/profile",
+                web::get().to(crate::routes::students::get_student_profile::handler),
+            )
+            .route(
+                "/students/{student_id}/profile",
+                web::get().to(crate::routes::students::get_student::handler),
+            )
+            .route(
+                "/grade-point-mappings",
+                web::get().to(
+                    crate::routes::grade_point_mappings::get_grade_point_mappings_list::handler,
+                ),
+            )
+            .route(
+                "/grade-point-mappings",
+                web::put()
+                    .to(crate::routes::grade_point_mappings::put_grade_point_mappings::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/registration-periods",
+                web::get().to(crate::routes::semesters::get_registration_periods::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/registration-periods",
+                web::put().to(crate::routes::semesters::put_registration_periods::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}",
+                web::put().to(crate::routes::semesters::subjects::put_opening_subject::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/validate",
+                web::post().to(
+                    crate::routes::semesters::subjects::post_opening_subject_validation::handler,
+                ),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/enrollment",
+                web::post().to(crate::routes::semesters::subjects::post_enrollment::handler),
+            )
+            .route(
+                "/semesters/{semester_id}/subjects/{subject_id}/enrollment",
+                web::delete().to(crate::routes::semesters::subjects::delete_enrollment::handler),
+            )
             .route(
                 "/forum/posts",
                 web::get().to(crate::routes::forum::posts::get_post_list::handler),
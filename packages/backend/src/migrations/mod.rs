@@ -0,0 +1,205 @@
+//! Versioned migrations of the schema in `migrations/`, embedded in the binary by `build.rs`.
+//!
+//! Applied migrations are recorded in `schema_migrations` with a checksum of their up migration,
+//! a migration changed after it is applied stops the backend instead of leaving databases with
+//! different schemas behind. Every migration runs in its own transaction while an advisory lock is
+//! held, so instances starting at the same time apply each migration once.
+
+use derive_more::{Display, Error};
+use sha2::{Digest, Sha256};
+use tokio_postgres::Client;
+
+/// A migration with the sql applying and reverting it.
+pub struct Migration {
+    pub version: i32,
+    pub name: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+}
+
+impl Migration {
+    /// Hex encoded sha256 of the up migration.
+    pub fn checksum(&self) -> String {
+        hex::encode(Sha256::digest(self.up.as_bytes()))
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/migrations.rs"));
+
+/// Key of the advisory lock held while migrating, no other advisory lock of the backend uses it.
+const MIGRATIONS_LOCK_KEY: i64 = 7_125_100_002_649;
+
+#[derive(Debug, Display, Error)]
+pub enum MigrationError {
+    #[display(fmt = "{}", _0)]
+    Postgres(tokio_postgres::Error),
+    #[display(
+        fmt = "migration {} is applied to the database but does not exist",
+        version
+    )]
+    Unknown { version: i32 },
+    #[display(
+        fmt = "migration {}_{} is changed after it is applied to the database",
+        version,
+        name
+    )]
+    ChecksumMismatch { version: i32, name: &'static str },
+}
+
+impl From<tokio_postgres::Error> for MigrationError {
+    fn from(e: tokio_postgres::Error) -> Self {
+        Self::Postgres(e)
+    }
+}
+
+/// Versions of the migrations applied to the database, after checking every one of them is
+/// unchanged.
+async fn verified_versions(client: &Client) -> Result<Vec<i32>, MigrationError> {
+    client
+        .batch_execute(include_str!("schema_migrations.sql"))
+        .await?;
+
+    let rows = client
+        .query(
+            r##"
+            select schema_migration_version, schema_migration_checksum
+            from schema_migrations
+            order by schema_migration_version
+            "##,
+            &[],
+        )
+        .await?;
+
+    let mut versions = Vec::with_capacity(rows.len());
+    for row in rows {
+        let version: i32 = row.get("schema_migration_version");
+        let checksum: String = row.get("schema_migration_checksum");
+
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.version == version)
+            .ok_or(MigrationError::Unknown { version })?;
+
+        if migration.checksum() != checksum {
+            return Err(MigrationError::ChecksumMismatch {
+                version,
+                name: migration.name,
+            });
+        }
+
+        versions.push(version);
+    }
+
+    Ok(versions)
+}
+
+async fn apply(client: &mut Client) -> Result<Vec<&'static Migration>, MigrationError> {
+    let versions = verified_versions(client).await?;
+    let mut applied = vec![];
+
+    for migration in MIGRATIONS.iter().filter(|m| !versions.contains(&m.version)) {
+        let transaction = client.transaction().await?;
+
+        transaction.batch_execute(migration.up).await?;
+        transaction
+            .execute(
+                r##"
+                insert into schema_migrations (
+                    schema_migration_version,
+                    schema_migration_name,
+                    schema_migration_checksum
+                ) values ($1, $2, $3)
+                "##,
+                &[&migration.version, &migration.name, &migration.checksum()],
+            )
+            .await?;
+        transaction.commit().await?;
+
+        applied.push(migration);
+    }
+
+    Ok(applied)
+}
+
+async fn revert(
+    client: &mut Client,
+    version: i32,
+) -> Result<Vec<&'static Migration>, MigrationError> {
+    let versions = verified_versions(client).await?;
+    let mut reverted = vec![];
+
+    for migration in MIGRATIONS
+        .iter()
+        .rev()
+        .filter(|m| m.version > version && versions.contains(&m.version))
+    {
+        let transaction = client.transaction().await?;
+
+        transaction.batch_execute(migration.down).await?;
+        transaction
+            .execute(
+                "delete from schema_migrations where schema_migration_version = $1",
+                &[&migration.version],
+            )
+            .await?;
+        transaction.commit().await?;
+
+        reverted.push(migration);
+    }
+
+    Ok(reverted)
+}
+
+/// Apply every migration not applied to the database yet, in order. Returns the migrations
+/// applied by this call.
+pub async fn migrate(client: &mut Client) -> Result<Vec<&'static Migration>, MigrationError> {
+    client
+        .execute("select pg_advisory_lock($1)", &[&MIGRATIONS_LOCK_KEY])
+        .await?;
+    let applied = apply(client).await;
+    client
+        .execute("select pg_advisory_unlock($1)", &[&MIGRATIONS_LOCK_KEY])
+        .await?;
+
+    applied
+}
+
+/// Revert every applied migration newer than `version` with its down migration, newest first.
+/// Reverting to `0` leaves an empty database. Returns the migrations reverted by this call.
+pub async fn rollback(
+    client: &mut Client,
+    version: i32,
+) -> Result<Vec<&'static Migration>, MigrationError> {
+    client
+        .execute("select pg_advisory_lock($1)", &[&MIGRATIONS_LOCK_KEY])
+        .await?;
+    let reverted = revert(client, version).await;
+    client
+        .execute("select pg_advisory_unlock($1)", &[&MIGRATIONS_LOCK_KEY])
+        .await?;
+
+    reverted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MIGRATIONS;
+
+    #[test]
+    fn test_migrations_are_embedded_in_order() {
+        assert_eq!(MIGRATIONS[0].version, 1);
+        assert_eq!(MIGRATIONS[0].name, "baseline");
+        assert!(MIGRATIONS
+            .windows(2)
+            .all(|m| m[1].version == m[0].version + 1));
+        assert!(MIGRATIONS
+            .iter()
+            .all(|m| !m.up.is_empty() && !m.down.is_empty()));
+    }
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(MIGRATIONS[0].checksum().len(), 64);
+        assert_eq!(MIGRATIONS[0].checksum(), MIGRATIONS[0].checksum());
+    }
+}
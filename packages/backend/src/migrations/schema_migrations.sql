@@ -0,0 +1,9 @@
+-- migrations applied to the database, created by the backend before it applies the migrations.
+-- the checksum is the sha256 of the up migration when it is applied.
+create table if not exists schema_migrations (
+    schema_migration_version int4 not null unique,
+    schema_migration_name text not null,
+    schema_migration_checksum text not null,
+    schema_migration_applied_timestamp timestamptz not null default now(),
+    primary key (schema_migration_version)
+);
@@ -11,6 +11,48 @@ use crate::{
         crate::routes::auth::refresh::handler,
         crate::routes::admin::signup::handler,
         crate::routes::students::signup::handler,
+        crate::routes::students::post_signup_import::handler,
+        crate::routes::students::get_invoices_list::handler,
+        crate::routes::curriculums::put_tuition_rate::handler,
+        crate::routes::payments::post_payment_webhook::handler,
+        crate::routes::payments::post_mock_payment::handler,
+        crate::routes::grading_criterias::get_grading_criterias_list::handler,
+        crate::routes::grading_criterias::post_grading_criteria::handler,
+        crate::routes::grading_criterias::post_grading_criteria_clone::handler,
+        crate::routes::semesters::subjects::put_subject_grading_criteria::handler,
+        crate::routes::students::get_scores_list::handler,
+        crate::routes::semesters::subjects::get_assignments_list::handler,
+        crate::routes::semesters::subjects::post_assignment::handler,
+        crate::routes::semesters::subjects::get_assignment_scores::handler,
+        crate::routes::semesters::subjects::put_assignment_scores::handler,
+        crate::routes::semesters::subjects::get_subject_grades::handler,
+        crate::routes::semesters::subjects::post_grades_computation::handler,
+        crate::routes::semesters::subjects::post_grades_release::handler,
+        crate::routes::semesters::subjects::put_grade_override::handler,
+        crate::routes::semesters::subjects::post_grade_amendment::handler,
+        crate::routes::students::get_grades_list::handler,
+        crate::routes::students::get_transcript::handler,
+        crate::routes::students::get_transcript_print::handler,
+        crate::routes::students::get_degree_audit::handler,
+        crate::routes::students::get_students_list::handler,
+        crate::routes::students::get_student_profile::handler,
+        crate::routes::students::get_student::handler,
+        crate::routes::grade_point_mappings::get_grade_point_mappings_list::handler,
+        crate::routes::grade_point_mappings::put_grade_point_mappings::handler,
+        crate::routes::semesters::subjects::get_subject_comments::handler,
+        crate::routes::semesters::subjects::post_subject_comment::handler,
+        crate::routes::comments::get_comments_list::handler,
+        crate::routes::professors::get_professors_list::handler,
+        crate::routes::professors::post_professor::handler,
+        crate::routes::professors::get_advisees_list::handler,
+        crate::routes::semesters::get_registration_periods::handler,
+        crate::routes::semesters::put_registration_periods::handler,
+        crate::routes::semesters::subjects::put_opening_subject::handler,
+        crate::routes::semesters::subjects::post_opening_subject_validation::handler,
+        crate::routes::semesters::subjects::post_enrollment::handler,
+        crate::routes::semesters::subjects::delete_enrollment::handler,
+        crate::routes::students::get_waitlists_list::handler,
+        crate::routes::users::get_notifications_list::handler,
         crate::routes::forum::posts::get_trending_posts_list::handler,
         crate::routes::forum::posts::get_post::handler,
         crate::routes::users::get_user_profile_image::handler,
@@ -29,7 +71,103 @@ use crate::{
             crate::constants::requests::Order,
             crate::routes::auth::signin::SigninRequestBody,
             crate::routes::students::signup::StudentSignupRequestBody,
-            crate::routes::students::signup::StudentSignupRequestBodyInner,
+            crate::services::signups::StudentSignupRequestBodyInner,
+            crate::routes::students::post_signup_import::PostSignupImportRequestQueries,
+            crate::routes::students::post_signup_import::PostSignupImportRequestBody,
+            crate::routes::students::post_signup_import::PostSignupImportResponseBody,
+            crate::routes::students::post_signup_import::PostSignupImportResponseBodyInner,
+            crate::services::signup_imports::ImportRowError,
+            crate::services::schedule_conflicts::ProposedSchedule,
+            crate::services::schedule_conflicts::ScheduleConflictKind,
+            crate::services::schedule_conflicts::ScheduleConflict,
+            crate::services::payments::PaymentStatus,
+            crate::services::payments::PaymentEvent,
+            crate::routes::students::get_invoices_list::GetInvoicesListResponseBody,
+            crate::routes::students::get_invoices_list::GetInvoicesListResponseBodyInner,
+            crate::routes::curriculums::put_tuition_rate::PutTuitionRateRequestParams,
+            crate::routes::curriculums::put_tuition_rate::PutTuitionRateRequestBody,
+            crate::routes::payments::post_mock_payment::PostMockPaymentRequestBody,
+            crate::services::grading_criterias::GradingCriteriaGrade,
+            crate::routes::grading_criterias::get_grading_criterias_list::GetGradingCriteriasListResponseBody,
+            crate::routes::grading_criterias::get_grading_criterias_list::GetGradingCriteriasListResponseBodyInner,
+            crate::routes::grading_criterias::post_grading_criteria::PostGradingCriteriaRequestBody,
+            crate::routes::grading_criterias::post_grading_criteria::PostGradingCriteriaResponseBody,
+            crate::routes::grading_criterias::post_grading_criteria_clone::PostGradingCriteriaCloneRequestParams,
+            crate::routes::grading_criterias::post_grading_criteria_clone::PostGradingCriteriaCloneRequestBody,
+            crate::routes::semesters::subjects::put_subject_grading_criteria::PutSubjectGradingCriteriaRequestParams,
+            crate::routes::semesters::subjects::put_subject_grading_criteria::PutSubjectGradingCriteriaRequestBody,
+            crate::services::gradebook::ScoreEntry,
+            crate::routes::students::get_scores_list::GetScoresListRequestQueries,
+            crate::routes::students::get_scores_list::GetScoresListResponseBody,
+            crate::routes::students::get_scores_list::GetScoresListResponseBodyInner,
+            crate::routes::semesters::subjects::get_assignments_list::GetAssignmentsListRequestParams,
+            crate::routes::semesters::subjects::get_assignments_list::GetAssignmentsListResponseBody,
+            crate::routes::semesters::subjects::get_assignments_list::GetAssignmentsListResponseBodyInner,
+            crate::routes::semesters::subjects::post_assignment::PostAssignmentRequestParams,
+            crate::routes::semesters::subjects::post_assignment::PostAssignmentRequestBody,
+            crate::routes::semesters::subjects::post_assignment::PostAssignmentResponseBody,
+            crate::routes::semesters::subjects::get_assignment_scores::AssignmentScoresRequestParams,
+            crate::routes::semesters::subjects::get_assignment_scores::GetAssignmentScoresResponseBody,
+            crate::routes::semesters::subjects::get_assignment_scores::GetAssignmentScoresResponseBodyInner,
+            crate::routes::semesters::subjects::put_assignment_scores::PutAssignmentScoresRequestBody,
+            crate::services::grades::SubjectGrade,
+            crate::routes::semesters::subjects::get_subject_grades::SubjectGradesRequestParams,
+            crate::routes::semesters::subjects::get_subject_grades::GetSubjectGradesResponseBody,
+            crate::routes::semesters::subjects::put_grade_override::StudentGradeRequestParams,
+            crate::routes::semesters::subjects::put_grade_override::StudentGradeChangeRequestBody,
+            crate::routes::students::get_grades_list::GetGradesListRequestQueries,
+            crate::routes::students::get_grades_list::GetGradesListResponseBody,
+            crate::routes::students::get_grades_list::GetGradesListResponseBodyInner,
+            crate::services::transcripts::GradePointMapping,
+            crate::services::transcripts::TranscriptSubject,
+            crate::services::transcripts::TranscriptSemester,
+            crate::services::transcripts::Transcript,
+            crate::routes::students::get_transcript::TranscriptRequestParams,
+            crate::services::degree_audits::MajorSubject,
+            crate::services::degree_audits::DegreeAuditGroup,
+            crate::services::degree_audits::DegreeAudit,
+            crate::routes::students::get_degree_audit::GetDegreeAuditRequestParams,
+            crate::services::students::StudentProfile,
+            crate::routes::students::get_student_profile::StudentNameLanguageRequestQueries,
+            crate::routes::students::get_student::GetStudentRequestParams,
+            crate::routes::students::get_students_list::GetStudentsListRequestQueries,
+            crate::routes::students::get_students_list::GetStudentsListResponseBody,
+            crate::routes::students::get_students_list::GetStudentsListResponseBodyInner,
+            crate::routes::grade_point_mappings::get_grade_point_mappings_list::GetGradePointMappingsListResponseBody,
+            crate::routes::grade_point_mappings::put_grade_point_mappings::PutGradePointMappingsRequestBody,
+            crate::routes::semesters::subjects::post_subject_comment::SubjectCommentsRequestParams,
+            crate::routes::semesters::subjects::post_subject_comment::PostSubjectCommentRequestBody,
+            crate::routes::semesters::subjects::get_subject_comments::GetSubjectCommentsResponseBody,
+            crate::routes::comments::get_comments_list::GetCommentsListRequestQueries,
+            crate::routes::comments::get_comments_list::GetCommentsListResponseBody,
+            crate::routes::comments::get_comments_list::GetCommentsListResponseBodyInner,
+            crate::services::names::PersonName,
+            crate::routes::professors::get_professors_list::GetProfessorsListRequestQueries,
+            crate::routes::professors::get_professors_list::GetProfessorsListResponseBody,
+            crate::routes::professors::get_professors_list::GetProfessorsListResponseBodyInner,
+            crate::routes::professors::post_professor::PostProfessorRequestBody,
+            crate::routes::professors::post_professor::PostProfessorResponseBody,
+            crate::routes::professors::get_advisees_list::GetAdviseesListRequestParams,
+            crate::routes::professors::get_advisees_list::GetAdviseesListResponseBody,
+            crate::routes::professors::get_advisees_list::GetAdviseesListResponseBodyInner,
+            crate::services::registration_periods::RegistrationPeriods,
+            crate::services::registration_periods::RegistrationPeriod,
+            crate::routes::semesters::get_registration_periods::GetRegistrationPeriodsRequestParams,
+            crate::routes::semesters::get_registration_periods::GetRegistrationPeriodsResponseBody,
+            crate::routes::semesters::put_registration_periods::PutRegistrationPeriodsRequestParams,
+            crate::routes::semesters::subjects::put_opening_subject::PutOpeningSubjectRequestParams,
+            crate::routes::semesters::subjects::put_opening_subject::PutOpeningSubjectRequestBody,
+            crate::routes::semesters::subjects::put_opening_subject::OpeningSubjectEligibleMajor,
+            crate::routes::semesters::subjects::post_opening_subject_validation::PostOpeningSubjectValidationResponseBody,
+            crate::routes::semesters::subjects::post_enrollment::PostEnrollmentRequestParams,
+            crate::routes::semesters::subjects::post_enrollment::EnrollmentStatus,
+            crate::routes::semesters::subjects::post_enrollment::PostEnrollmentResponseBody,
+            crate::routes::semesters::subjects::delete_enrollment::DeleteEnrollmentRequestParams,
+            crate::routes::students::get_waitlists_list::GetWaitlistsListResponseBody,
+            crate::routes::students::get_waitlists_list::GetWaitlistsListResponseBodyInner,
+            crate::routes::users::get_notifications_list::GetNotificationsListRequestQueries,
+            crate::routes::users::get_notifications_list::GetNotificationsListResponseBody,
+            crate::routes::users::get_notifications_list::GetNotificationsListResponseBodyInner,
             crate::routes::forum::posts::get_trending_posts_list::GetTrendingPostsListRequestQueries,
             crate::routes::users::get_users_list::GetUsersListRequestQueries,
             crate::routes::admin::signup::AdminSignupRequestBody,
@@ -74,8 +212,29 @@ use crate::{
         (
             name = "students"
         ),
+        (
+            name = "semesters"
+        ),
         (
             name = "users"
+        ),
+        (
+            name = "curriculums"
+        ),
+        (
+            name = "payments"
+        ),
+        (
+            name = "grading_criterias"
+        ),
+        (
+            name = "grade_point_mappings"
+        ),
+        (
+            name = "comments"
+        ),
+        (
+            name = "professors"
         )
     )
 )]
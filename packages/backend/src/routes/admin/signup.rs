@@ -113,21 +113,25 @@ pub async fn handler(
 #[cfg(test)]
 mod tests {
     use actix_web::{http::StatusCode, test, web, App};
-    use deadpool_postgres::Runtime;
     use serde_json::json;
-    use tokio_postgres::NoTls;
 
-    use crate::{shared_app_data::SharedAppData, startup::load_postgres_config};
+    use crate::{
+        fixtures::{fresh_schema, Fixtures},
+        shared_app_data::SharedAppData,
+        startup::load_postgres_config,
+    };
 
     use super::{handler, AdminSignupRequestBody};
 
     #[actix_web::test]
     async fn test_admin_signup() {
-        let postgres_config = load_postgres_config();
-        let pool = postgres_config
-            .create_pool(Some(Runtime::Tokio1), NoTls)
-            .unwrap();
-        let client = pool.get().await.unwrap();
+        let pool = fresh_schema(
+            &load_postgres_config(),
+            "test_admin_signup",
+            &Fixtures::default(),
+        )
+        .await
+        .unwrap();
 
         let app = test::init_service(
             App::new()
@@ -143,11 +147,6 @@ mod tests {
             time::OffsetDateTime::now_utc() - time::Duration::new(60 * 60 * 24 * 365 * 30, 0);
         let birthdate = birthdate.date();
 
-        client
-            .execute("delete from users where user_username = $1", &[&username])
-            .await
-            .unwrap();
-
         // empty username
         let request = test::TestRequest::post()
             .uri("/")
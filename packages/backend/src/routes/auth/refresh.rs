@@ -195,12 +195,11 @@ pub async fn handler(
 #[cfg(test)]
 mod tests {
     use actix_web::{http::StatusCode, test, web, App};
-    use deadpool_postgres::Runtime;
     use serde_json::json;
-    use tokio_postgres::NoTls;
 
     use crate::{
-        routes::admin::signup::AdminSignupRequestBody, shared_app_data::SharedAppData,
+        fixtures::{fresh_schema, Fixtures},
+        shared_app_data::SharedAppData,
         startup::load_postgres_config,
     };
 
@@ -208,51 +207,26 @@ mod tests {
 
     #[actix_web::test]
     async fn test_refresh() {
-        let postgres_config = load_postgres_config();
-        let pool = postgres_config
-            .create_pool(Some(Runtime::Tokio1), NoTls)
-            .unwrap();
-
-        let username = "refresh_tokener";
-        let email = "refresh_tokener@gmail.com";
-        let password = "refresh_tokener";
-        let birthdate =
-            time::OffsetDateTime::now_utc() - time::Duration::new(60 * 60 * 24 * 365 * 30, 0);
-        let birthdate = birthdate.date();
-
-        let client = pool.get().await.unwrap();
-
-        let row = client
-            .query_opt(
-                "select user_id from users where user_username = $1",
-                &[&username],
-            )
+        let fixtures = Fixtures::parse(
+            r##"
+            [[admins]]
+            id = "refresh_tokener"
+            username = "refresh_tokener"
+            email = "refresh_tokener@gmail.com"
+            password = "refresh_tokener"
+            birthdate = 1992-04-10
+            "##,
+        )
+        .unwrap();
+        let pool = fresh_schema(&load_postgres_config(), "test_refresh", &fixtures)
             .await
             .unwrap();
-
-        if let Some(r) = row {
-            client
-                .execute("delete from users where user_username = $1", &[&username])
-                .await
-                .unwrap();
-
-            client
-                .execute(
-                    "delete from user_sessions where user_session_user_id = $1",
-                    &[&r.get::<&str, String>("user_id")],
-                )
-                .await
-                .unwrap();
-        }
+        let client = pool.get().await.unwrap();
 
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(SharedAppData::new(pool.clone())))
                 .route("/refresh", web::post().to(handler))
-                .route(
-                    "/signup",
-                    web::post().to(crate::routes::admin::signup::handler),
-                )
                 .route(
                     "/signin",
                     web::post().to(crate::routes::auth::signin::handler),
@@ -260,18 +234,6 @@ mod tests {
         )
         .await;
 
-        let request = test::TestRequest::post()
-            .uri("/signup")
-            .set_json(AdminSignupRequestBody {
-                username: username.to_string(),
-                email: email.to_string(),
-                password: password.to_string(),
-                birthdate,
-            })
-            .to_request();
-
-        let _response = test::call_service(&app, request).await;
-
         let request = test::TestRequest::post()
             .uri("/signin")
             .set_json(json!({
@@ -315,10 +277,12 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::OK);
 
+        // the session keeps the refresh token given back by the call.
+        let new_refresh_token = response.headers().get("x-refresh-token").unwrap();
         let row = client
             .query_opt(
                 "select * from user_sessions where user_session_refresh_token = $1",
-                &[&refresh_token.to_str().unwrap()],
+                &[&new_refresh_token.to_str().unwrap()],
             )
             .await
             .unwrap();
@@ -143,90 +143,33 @@ pub async fn handler(
 mod tests {
     use super::*;
 
-    use crate::load_postgres_config;
+    use crate::{
+        fixtures::{fresh_schema, Fixtures},
+        load_postgres_config,
+    };
 
     use actix_web::{http::StatusCode, test, App};
-    use argon2::{password_hash::SaltString, PasswordHasher};
-    use deadpool_postgres::Runtime;
-    use rand_core::OsRng;
-    use tokio_postgres::NoTls;
 
     #[actix_web::test]
     async fn signin() {
-        let pool = load_postgres_config()
-            .create_pool(Some(Runtime::Tokio1), NoTls)
-            .expect("cannot create testing pool");
-
-        let client = pool.get().await.unwrap();
-
-        // 30 years old
-        let birthdate =
-            time::OffsetDateTime::now_utc() - time::Duration::new(30 * 365 * 24 * 60 * 60, 0);
-
         let username = "simple_user_signin";
-        let email = "simple_user_signin@gmail.com";
         let password = "aryastark";
 
-        let salt = SaltString::generate(&mut OsRng);
-        let argon2_context = create_argon2_context(&ARGON2_PEPPER_STRING).unwrap();
-        let hashed_password = argon2_context
-            .hash_password(password.as_bytes(), &salt)
-            .unwrap();
-
-        // testing preparation
-        let uid = client
-            .query_opt(
-                "select user_id from users where user_username = $1",
-                &[&username],
-            )
-            .await
-            .unwrap();
-
-        if let Some(u) = uid {
-            client
-                .execute("delete from users where user_username = $1", &[&username])
-                .await
-                .unwrap();
-
-            client
-                .execute(
-                    "delete from user_sessions where user_session_user_id = $1",
-                    &[&u.get::<&str, String>("user_id")],
-                )
-                .await
-                .unwrap();
-        }
-
-        client
-            .execute(
-                r##"
-                insert into users (
-                    user_id,
-                    user_username,
-                    user_email,
-                    user_password,
-                    user_role,
-                    user_birthdate
-                ) values (
-                    $1,
-                    $2,
-                    $3,
-                    $4,
-                    $5,
-                    $6
-                )
-                "##,
-                &[
-                    &randoid::randoid!(ID_LENGTH),
-                    &username,
-                    &email,
-                    &hashed_password.to_string(),
-                    &Role::Admin,
-                    &birthdate.date(),
-                ],
-            )
+        let fixtures = Fixtures::parse(
+            r##"
+            [[admins]]
+            id = "simple_user_signin"
+            username = "simple_user_signin"
+            email = "simple_user_signin@gmail.com"
+            password = "aryastark"
+            birthdate = 1992-04-10
+            "##,
+        )
+        .unwrap();
+        let pool = fresh_schema(&load_postgres_config(), "test_signin", &fixtures)
             .await
             .unwrap();
+        let client = pool.get().await.unwrap();
 
         let app = test::init_service(
             App::new()
@@ -0,0 +1,187 @@
+use actix_web::{web, HttpResponse};
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{
+        requests::{AuthenticationHeaders, SqlRange},
+        DEFAULT_PAGE, DEFAULT_PAGE_SIZE,
+    },
+    errors::HttpError,
+    extractors::admins::AuthenticatedAdminClaims,
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, IntoParams, ToSchema, TS)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct GetCommentsListRequestQueries {
+    /// Full text search query, every comment is returned when not specified.
+    #[serde(
+        default,
+        deserialize_with = "crate::constants::requests::empty_string_as_none"
+    )]
+    #[ts(optional)]
+    pub search: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub semester_id: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub subject_id: Option<String>,
+    /// page of the queried data. If less than `1`, will default to `1`
+    #[param(minimum = 1, default = json!(DEFAULT_PAGE))]
+    #[serde(
+        default,
+        deserialize_with = "crate::constants::requests::deserialize_page"
+    )]
+    #[ts(optional)]
+    pub page: Option<i32>,
+    /// size of page for each query. will use default page size if it is out of bounds.
+    #[param(minimum = 1, maximum = 100, default = json!(DEFAULT_PAGE_SIZE))]
+    #[serde(
+        default,
+        deserialize_with = "crate::constants::requests::deserialize_page_size"
+    )]
+    #[ts(optional)]
+    pub page_size: Option<i32>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetCommentsListResponseBody {
+    comments: Vec<GetCommentsListResponseBodyInner>,
+}
+
+#[derive(Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct GetCommentsListResponseBodyInner {
+    semester_id: String,
+    subject_id: String,
+    student_id: String,
+    comment: String,
+}
+
+/// Search comments of every subject with the full text search index, most relevant first.
+#[utoipa::path(
+    get,
+    path = "/comments",
+    tag = "comments",
+    operation_id = "get_comments_list",
+    params(AuthenticationHeaders, GetCommentsListRequestQueries),
+    responses(
+        (
+            status = 200,
+            description = "successfully search comments",
+            body = GetCommentsListResponseBody,
+            example = json!({ "comments": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    query: web::Query<GetCommentsListRequestQueries>,
+    data: web::Data<SharedAppData>,
+    _claims: AuthenticatedAdminClaims,
+) -> Result<HttpResponse, HttpError> {
+    let SqlRange { limit, offset } = SqlRange::from_page(
+        query.page.unwrap_or(DEFAULT_PAGE),
+        query.page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+    )?;
+
+    let client = data.pool.get().await?;
+
+    // with `$1::text is null or ... &@~ $1` the cached generic plan cannot use the pgroonga index
+    // and `pgroonga_score` comes back as 0, so searching is a statement of its own.
+    let rows = match query.search {
+        Some(ref search) => {
+            let statement = client
+                .prepare_typed_cached(
+                    r##"
+                    select
+                        semester_id,
+                        subject_id,
+                        student_id,
+                        student_comment as comment
+                    from student_subject_comments
+                    where
+                        student_comment &@~ $1 and
+                        ($2::text is null or semester_id = $2) and
+                        ($3::text is null or subject_id = $3)
+                    order by
+                        pgroonga_score(tableoid, ctid) desc,
+                        semester_id desc,
+                        subject_id asc
+                    limit $4
+                    offset $5
+                    "##,
+                    &[Type::TEXT, Type::TEXT, Type::TEXT, Type::INT4, Type::INT4],
+                )
+                .await?;
+
+            client
+                .query(
+                    &statement,
+                    &[
+                        search,
+                        &query.semester_id,
+                        &query.subject_id,
+                        &limit,
+                        &offset,
+                    ],
+                )
+                .await?
+        }
+        None => {
+            let statement = client
+                .prepare_typed_cached(
+                    r##"
+                    select
+                        semester_id,
+                        subject_id,
+                        student_id,
+                        student_comment as comment
+                    from student_subject_comments
+                    where
+                        ($1::text is null or semester_id = $1) and
+                        ($2::text is null or subject_id = $2)
+                    order by
+                        semester_id desc,
+                        subject_id asc
+                    limit $3
+                    offset $4
+                    "##,
+                    &[Type::TEXT, Type::TEXT, Type::INT4, Type::INT4],
+                )
+                .await?;
+
+            client
+                .query(
+                    &statement,
+                    &[&query.semester_id, &query.subject_id, &limit, &offset],
+                )
+                .await?
+        }
+    };
+
+    let comments = rows
+        .iter()
+        .map(|c| GetCommentsListResponseBodyInner::try_from(c))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(HttpResponse::Ok().json(GetCommentsListResponseBody { comments }))
+}
@@ -0,0 +1,122 @@
+use actix_web::{web, HttpResponse};
+use postgres_types::Type;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, responses::DefaultSuccessResponse},
+    errors::HttpError,
+    extractors::admins::AuthenticatedAdminClaims,
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct PutTuitionRateRequestParams {
+    pub curriculum_id: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PutTuitionRateRequestBody {
+    /// Charged for every enrolled credit.
+    #[schema(value_type = String, example = json!("1500.00"))]
+    pub per_credit: Decimal,
+    /// Charged once in every semester the student enrolls in anything.
+    #[schema(value_type = String, example = json!("3000.00"))]
+    pub flat_fee: Decimal,
+}
+
+/// Sets how much students in a curriculum are charged every semester. Invoices that are already
+/// issued are updated the next time the students change their enrollments.
+#[utoipa::path(
+    put,
+    path = "/curriculums/{curriculum_id}/tuition-rate",
+    tag = "curriculums",
+    operation_id = "put_tuition_rate",
+    params(AuthenticationHeaders, PutTuitionRateRequestParams),
+    request_body = PutTuitionRateRequestBody,
+    responses(
+        (
+            status = 200,
+            description = "tuition rate saved",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 400,
+            description = "input errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InputValidationError.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "curriculum not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::CurriculumNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<PutTuitionRateRequestParams>,
+    body: web::Json<PutTuitionRateRequestBody>,
+    data: web::Data<SharedAppData>,
+    _claims: AuthenticatedAdminClaims,
+) -> Result<HttpResponse, HttpError> {
+    if body.per_credit.is_sign_negative() || body.flat_fee.is_sign_negative() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let client = data.pool.get().await?;
+
+    let curriculum = client
+        .query_opt(
+            "select curriculum_id from curriculums where curriculum_id = $1",
+            &[&params.curriculum_id],
+        )
+        .await?;
+
+    if curriculum.is_none() {
+        return Err(HttpError::CurriculumNotFound);
+    }
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            insert into curriculum_tuition_rates (
+                curriculum_id,
+                curriculum_tuition_rate_per_credit,
+                curriculum_tuition_rate_flat_fee
+            ) values (
+                $1,
+                $2,
+                $3
+            ) on conflict (curriculum_id) do update set
+                curriculum_tuition_rate_per_credit = $2,
+                curriculum_tuition_rate_flat_fee = $3
+            "##,
+            &[Type::TEXT, Type::NUMERIC, Type::NUMERIC],
+        )
+        .await?;
+
+    client
+        .execute(
+            &statement,
+            &[&params.curriculum_id, &body.per_credit, &body.flat_fee],
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(DefaultSuccessResponse::default()))
+}
@@ -1,5 +1,5 @@
 use actix_web::{web, HttpResponse};
-use ger_from_row::FromRow;
+use ger_from_row::{FromRow, GenericClientExt};
 use postgres_types::Type;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
@@ -98,13 +98,9 @@ pub async fn handler(
         .await?;
 
     let category = client
-        .query_one(&statement, &[&params.category_representative_id])
-        .await
-        .map_err(|e| {
-            tracing::error!("{}", e);
-            HttpError::CategoryNotFound
-        })?;
+        .query_opt_as::<GetCategoryResponseBody>(&statement, &[&params.category_representative_id])
+        .await?
+        .ok_or(HttpError::CategoryNotFound)?;
 
-    let category = GetCategoryResponseBody::try_from(&category)?;
     Ok(HttpResponse::Ok().json(category))
 }
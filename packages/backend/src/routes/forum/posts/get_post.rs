@@ -1,6 +1,6 @@
 use actix_web::{web, HttpResponse};
 use comrak::markdown_to_html;
-use ger_from_row::FromRow;
+use ger_from_row::{FromRow, GenericClientExt};
 use postgres_types::Type;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
@@ -126,27 +126,12 @@ pub async fn handler(
         )
         .await?;
 
-    let row = client.query_opt(&statement, &[&params.post_id]).await?;
+    let mut post = client
+        .query_opt_as::<GetPostResponseBody>(&statement, &[&params.post_id])
+        .await?
+        .ok_or(HttpError::PostNotFound)?;
 
-    if let Some(r) = row {
-        let raw_content = r.try_get::<&str, String>("content")?;
-        let parsed_content = markdown_to_html(&raw_content.as_str(), &COMRAK_OPTIONS);
+    post.content = markdown_to_html(&post.content, &COMRAK_OPTIONS);
 
-        let post = GetPostResponseBody {
-            id: r.try_get::<&str, String>("id")?,
-            user_id: r.try_get::<&str, String>("user_id")?,
-            username: r.try_get::<&str, String>("username")?,
-            name: r.try_get::<&str, String>("name")?,
-            category_id: r.try_get::<&str, String>("category_id")?,
-            category_representative_id: r.try_get::<&str, String>("category_representative_id")?,
-            content: parsed_content,
-            created_timestamp: r.try_get::<&str, time::OffsetDateTime>("created_timestamp")?,
-            view_count: r.try_get::<&str, i64>("view_count")?,
-            vote_count: r.try_get::<&str, i64>("vote_count")?,
-        };
-
-        return Ok(HttpResponse::Ok().json(post));
-    }
-
-    Err(HttpError::PostNotFound)
+    Ok(HttpResponse::Ok().json(post))
 }
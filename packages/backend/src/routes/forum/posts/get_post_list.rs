@@ -267,10 +267,7 @@ pub async fn handler(
 
     let posts = client.query(&statement, &query_params).await?;
 
-    let posts = posts
-        .iter()
-        .map(|p| GetPostListResponseBodyInner::try_from(p))
-        .collect::<Result<Vec<_>, _>>()?;
+    let posts = GetPostListResponseBodyInner::from_rows(&posts)?;
 
     return Ok(HttpResponse::Ok().json(GetPostListResponseBody { posts }));
 }
@@ -0,0 +1,57 @@
+use actix_web::{web, HttpResponse};
+use serde::Serialize;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::users::AuthenticatedUserClaims,
+    services::transcripts::{get_grade_point_mappings, GradePointMapping},
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetGradePointMappingsListResponseBody {
+    mappings: Vec<GradePointMapping>,
+}
+
+/// Get grade points of every letter grade used to calculate gpa and gpax.
+#[utoipa::path(
+    get,
+    path = "/grade-point-mappings",
+    tag = "grade_point_mappings",
+    operation_id = "get_grade_point_mappings_list",
+    params(AuthenticationHeaders),
+    responses(
+        (
+            status = 200,
+            description = "successfully get grade point mappings",
+            body = GetGradePointMappingsListResponseBody,
+            example = json!({ "mappings": [{ "alphabet": "A", "point": "4.00" }] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    data: web::Data<SharedAppData>,
+    _claims: AuthenticatedUserClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    let mappings = get_grade_point_mappings(&client).await?;
+
+    Ok(HttpResponse::Ok().json(GetGradePointMappingsListResponseBody { mappings }))
+}
@@ -0,0 +1,2 @@
+pub mod get_grade_point_mappings_list;
+pub mod put_grade_point_mappings;
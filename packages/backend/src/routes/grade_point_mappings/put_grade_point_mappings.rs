@@ -0,0 +1,93 @@
+use actix_web::{web, HttpResponse};
+use postgres_types::Type;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, responses::DefaultSuccessResponse},
+    errors::HttpError,
+    extractors::admins::AuthenticatedAdminClaims,
+    services::transcripts::{validate_grade_point_mappings, GradePointMapping},
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema)]
+pub struct PutGradePointMappingsRequestBody {
+    pub mappings: Vec<GradePointMapping>,
+}
+
+/// Replaces grade points of every letter grade. Letter grades left out no longer count towards
+/// gpa and gpax, which changes every transcript at once.
+#[utoipa::path(
+    put,
+    path = "/grade-point-mappings",
+    tag = "grade_point_mappings",
+    operation_id = "put_grade_point_mappings",
+    params(AuthenticationHeaders),
+    request_body = PutGradePointMappingsRequestBody,
+    responses(
+        (
+            status = 200,
+            description = "grade point mappings replaced",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 400,
+            description = "input errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InputValidationError.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    body: web::Json<PutGradePointMappingsRequestBody>,
+    data: web::Data<SharedAppData>,
+    _claims: AuthenticatedAdminClaims,
+) -> Result<HttpResponse, HttpError> {
+    validate_grade_point_mappings(&body.mappings)?;
+
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    let statement = transaction
+        .prepare_typed_cached(
+            r##"
+            insert into grade_point_mappings (
+                grade_point_mapping_alphabet,
+                grade_point_mapping_point
+            ) values (
+                $1,
+                $2
+            )
+            "##,
+            &[Type::TEXT, Type::NUMERIC],
+        )
+        .await?;
+
+    transaction
+        .execute("delete from grade_point_mappings", &[])
+        .await?;
+
+    for mapping in body.mappings.iter() {
+        transaction
+            .execute(&statement, &[&mapping.alphabet.trim(), &mapping.point])
+            .await?;
+    }
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok().json(DefaultSuccessResponse::default()))
+}
@@ -0,0 +1,101 @@
+use actix_web::{web, HttpResponse};
+use postgres_types::Type;
+use serde::Serialize;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    services::grading_criterias::{get_grading_criteria_grades, GradingCriteriaGrade},
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetGradingCriteriasListResponseBody {
+    grading_criterias: Vec<GetGradingCriteriasListResponseBodyInner>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetGradingCriteriasListResponseBodyInner {
+    id: String,
+    name: String,
+    /// Ordered from the best grade to the worst grade.
+    grades: Vec<GradingCriteriaGrade>,
+    #[serde(with = "time::serde::rfc3339")]
+    #[ts(type = "string")]
+    created_timestamp: time::OffsetDateTime,
+}
+
+/// Get every grading criteria owned by the signed in professor, latest first.
+#[utoipa::path(
+    get,
+    path = "/grading-criterias",
+    tag = "grading_criterias",
+    operation_id = "get_grading_criterias_list",
+    params(AuthenticationHeaders),
+    responses(
+        (
+            status = 200,
+            description = "successfully get list of grading criterias",
+            body = GetGradingCriteriasListResponseBody,
+            example = json!({ "grading_criterias": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                grading_criteria_id,
+                grading_criteria_name,
+                grading_criteria_created_timestamp
+            from grading_criterias
+            where user_id = $1
+            order by grading_criteria_created_timestamp desc
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    let rows = client
+        .query(&statement, &[&claims.0.access_token.claims.uid])
+        .await?;
+
+    let mut grading_criterias = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let id = row.try_get::<&str, String>("grading_criteria_id")?;
+
+        grading_criterias.push(GetGradingCriteriasListResponseBodyInner {
+            grades: get_grading_criteria_grades(&client, &id).await?,
+            id,
+            name: row.try_get::<&str, String>("grading_criteria_name")?,
+            created_timestamp: row
+                .try_get::<&str, time::OffsetDateTime>("grading_criteria_created_timestamp")?,
+        });
+    }
+
+    Ok(HttpResponse::Ok().json(GetGradingCriteriasListResponseBody { grading_criterias }))
+}
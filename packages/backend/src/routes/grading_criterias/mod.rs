@@ -0,0 +1,3 @@
+pub mod get_grading_criterias_list;
+pub mod post_grading_criteria;
+pub mod post_grading_criteria_clone;
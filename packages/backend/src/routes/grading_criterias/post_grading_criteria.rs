@@ -0,0 +1,88 @@
+use actix_web::{web, HttpResponse};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    services::grading_criterias::{insert_grading_criteria, validate_grades, GradingCriteriaGrade},
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema)]
+pub struct PostGradingCriteriaRequestBody {
+    pub name: String,
+    /// Ordered from the best grade to the worst grade.
+    pub grades: Vec<GradingCriteriaGrade>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct PostGradingCriteriaResponseBody {
+    pub id: String,
+}
+
+/// Creates a grading criteria owned by the signed in professor, which could be reused by every
+/// subject the professor teaches. Minimum scores have to strictly decrease from the best grade to
+/// the worst grade and no letter could be repeated.
+#[utoipa::path(
+    post,
+    path = "/grading-criterias",
+    tag = "grading_criterias",
+    operation_id = "post_grading_criteria",
+    params(AuthenticationHeaders),
+    request_body = PostGradingCriteriaRequestBody,
+    responses(
+        (
+            status = 201,
+            description = "grading criteria created",
+            body = PostGradingCriteriaResponseBody
+        ),
+        (
+            status = 400,
+            description = "input errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InputValidationError.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    body: web::Json<PostGradingCriteriaRequestBody>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    if body.name.trim().is_empty() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    validate_grades(&body.grades)?;
+
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    let id = insert_grading_criteria(
+        &transaction,
+        &claims.0.access_token.claims.uid,
+        &body.name,
+        &body.grades,
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Created().json(PostGradingCriteriaResponseBody { id }))
+}
@@ -0,0 +1,95 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    routes::grading_criterias::post_grading_criteria::PostGradingCriteriaResponseBody,
+    services::grading_criterias::{get_grading_criteria_grades, insert_grading_criteria},
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct PostGradingCriteriaCloneRequestParams {
+    pub grading_criteria_id: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PostGradingCriteriaCloneRequestBody {
+    /// Name of the copy, defaults to the name of the original criteria.
+    pub name: Option<String>,
+}
+
+/// Copies any grading criteria, including ones owned by other professors, into a new criteria
+/// owned by the signed in professor.
+#[utoipa::path(
+    post,
+    path = "/grading-criterias/{grading_criteria_id}/clone",
+    tag = "grading_criterias",
+    operation_id = "post_grading_criteria_clone",
+    params(AuthenticationHeaders, PostGradingCriteriaCloneRequestParams),
+    request_body = PostGradingCriteriaCloneRequestBody,
+    responses(
+        (
+            status = 201,
+            description = "grading criteria cloned",
+            body = PostGradingCriteriaResponseBody
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "grading criteria not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::GradingCriteriaNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<PostGradingCriteriaCloneRequestParams>,
+    body: web::Json<PostGradingCriteriaCloneRequestBody>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    let original = transaction
+        .query_opt(
+            "select grading_criteria_name from grading_criterias where grading_criteria_id = $1",
+            &[&params.grading_criteria_id],
+        )
+        .await?
+        .ok_or(HttpError::GradingCriteriaNotFound)?;
+
+    let name = match &body.name {
+        Some(n) if !n.trim().is_empty() => n.clone(),
+        _ => original.try_get::<&str, String>("grading_criteria_name")?,
+    };
+    let grades = get_grading_criteria_grades(&transaction, &params.grading_criteria_id).await?;
+
+    let id = insert_grading_criteria(
+        &transaction,
+        &claims.0.access_token.claims.uid,
+        &name,
+        &grades,
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Created().json(PostGradingCriteriaResponseBody { id }))
+}
@@ -1,6 +1,13 @@
 pub mod admin;
 pub mod auth;
+pub mod comments;
+pub mod curriculums;
 pub mod forum;
+pub mod grade_point_mappings;
+pub mod grading_criterias;
 pub mod hello;
+pub mod payments;
+pub mod professors;
+pub mod semesters;
 pub mod students;
 pub mod users;
@@ -0,0 +1,2 @@
+pub mod post_mock_payment;
+pub mod post_payment_webhook;
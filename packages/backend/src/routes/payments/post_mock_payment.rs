@@ -0,0 +1,110 @@
+use actix_web::{web, HttpResponse};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::{
+    constants::{
+        requests::AuthenticationHeaders, responses::DefaultSuccessResponse, PAYMENT_WEBHOOK_SECRET,
+    },
+    errors::HttpError,
+    extractors::admins::AuthenticatedAdminClaims,
+    services::payments::{process_payment_event, sign_payload, PaymentEvent, PaymentStatus},
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema)]
+pub struct PostMockPaymentRequestBody {
+    pub transaction_id: String,
+    /// How much to pay, the whole invoice is paid when missing.
+    #[schema(value_type = Option<String>, example = json!("7500.00"))]
+    pub amount: Option<Decimal>,
+    /// Simulates a failed payment when `true`.
+    #[serde(default)]
+    pub is_failed: bool,
+}
+
+/// Stands in for the payment gateway on local environments. The payment event is signed with the
+/// webhook secret and goes through the same verification as
+/// [post_payment_webhook](crate::routes::payments::post_payment_webhook::handler). This is only
+/// served when `GER_ENABLE_MOCK_PAYMENTS` is `true`.
+#[utoipa::path(
+    post,
+    path = "/payments/mock",
+    tag = "payments",
+    operation_id = "post_mock_payment",
+    params(AuthenticationHeaders),
+    request_body = PostMockPaymentRequestBody,
+    responses(
+        (
+            status = 200,
+            description = "payment event processed",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "transaction not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::TransactionNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    body: web::Json<PostMockPaymentRequestBody>,
+    data: web::Data<SharedAppData>,
+    _claims: AuthenticatedAdminClaims,
+) -> Result<HttpResponse, HttpError> {
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    let amount = match body.amount {
+        Some(a) => a,
+        None => transaction
+            .query_opt(
+                "select student_transaction_price from student_transactions where student_transaction_id = $1",
+                &[&body.transaction_id],
+            )
+            .await?
+            .ok_or(HttpError::TransactionNotFound)?
+            .try_get::<&str, Decimal>("student_transaction_price")?,
+    };
+
+    let payload = serde_json::to_vec(&PaymentEvent {
+        transaction_id: body.transaction_id.clone(),
+        amount,
+        status: if body.is_failed {
+            PaymentStatus::Failed
+        } else {
+            PaymentStatus::Succeeded
+        },
+    })
+    .map_err(|e| HttpError::InternalServerError {
+        cause: e.to_string(),
+    })?;
+    let signature = sign_payload(PAYMENT_WEBHOOK_SECRET.as_bytes(), &payload);
+
+    process_payment_event(
+        &transaction,
+        PAYMENT_WEBHOOK_SECRET.as_bytes(),
+        &payload,
+        &signature,
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok().json(DefaultSuccessResponse::default()))
+}
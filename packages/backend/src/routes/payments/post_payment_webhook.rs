@@ -0,0 +1,82 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+
+use crate::{
+    constants::{
+        responses::DefaultSuccessResponse, PAYMENT_SIGNATURE_HEADER_NAME, PAYMENT_WEBHOOK_SECRET,
+    },
+    errors::HttpError,
+    services::payments::process_payment_event,
+    shared_app_data::SharedAppData,
+};
+
+/// Receives payment results from the payment gateway. The raw body has to be signed with
+/// HMAC-SHA256 using the shared webhook secret, and the hex signature is sent in the
+/// `x-payment-signature` header. Events of invoices that are already paid are ignored, so the
+/// gateway could retry safely.
+#[utoipa::path(
+    post,
+    path = "/payments/webhook",
+    tag = "payments",
+    operation_id = "post_payment_webhook",
+    params(
+        ("x-payment-signature" = String, Header, description = "hex HMAC-SHA256 signature of the body")
+    ),
+    request_body = PaymentEvent,
+    responses(
+        (
+            status = 200,
+            description = "payment event processed",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 400,
+            description = "input errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InputValidationError.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "invalid signature",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InvalidWebhookSignature.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "transaction not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::TransactionNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    request: HttpRequest,
+    body: web::Bytes,
+    data: web::Data<SharedAppData>,
+) -> Result<HttpResponse, HttpError> {
+    let signature = match request.headers().get(PAYMENT_SIGNATURE_HEADER_NAME) {
+        Some(s) => s.to_str().map_err(|_| HttpError::InvalidWebhookSignature)?,
+        None => return Err(HttpError::InvalidWebhookSignature),
+    };
+
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    process_payment_event(
+        &transaction,
+        PAYMENT_WEBHOOK_SECRET.as_bytes(),
+        &body,
+        signature,
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok().json(DefaultSuccessResponse::default()))
+}
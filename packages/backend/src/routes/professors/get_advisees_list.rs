@@ -0,0 +1,124 @@
+use actix_web::{web, HttpResponse};
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders, database::Role, errors::HttpError,
+    extractors::users::AuthenticatedUserClaims, shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct GetAdviseesListRequestParams {
+    pub professor_id: String,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetAdviseesListResponseBody {
+    advisees: Vec<GetAdviseesListResponseBodyInner>,
+}
+
+#[derive(Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct GetAdviseesListResponseBodyInner {
+    student_id: String,
+    student_representative_id: String,
+    major_id: String,
+    first_academic_year_id: String,
+    first_name: String,
+    middle_name: String,
+    last_name: String,
+}
+
+/// Get students the professor advises. Professors could only get their own advisees, admins could
+/// get advisees of every professor.
+#[utoipa::path(
+    get,
+    path = "/professors/{professor_id}/advisees",
+    tag = "professors",
+    operation_id = "get_advisees_list",
+    params(AuthenticationHeaders, GetAdviseesListRequestParams),
+    responses(
+        (
+            status = 200,
+            description = "successfully get list of advisees",
+            body = GetAdviseesListResponseBody,
+            example = json!({ "advisees": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "not allowed to see advisees of the professor",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Forbidden.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<GetAdviseesListRequestParams>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedUserClaims,
+) -> Result<HttpResponse, HttpError> {
+    let token_claims = &claims.0.access_token.claims;
+
+    match token_claims.rle {
+        Role::Admin => {}
+        Role::Professor if token_claims.uid == params.professor_id => {}
+        _ => return Err(HttpError::Forbidden),
+    }
+
+    let client = data.pool.get().await?;
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                students.student_id,
+                students.student_representative_id,
+                students.major_id,
+                students.first_academic_year_id,
+                coalesce(student_names.student_first_name, '') as first_name,
+                coalesce(student_names.student_middle_name, '') as middle_name,
+                coalesce(student_names.student_last_name, '') as last_name
+            from students
+            left join lateral (
+                select
+                    student_first_name,
+                    student_middle_name,
+                    student_last_name
+                from student_names
+                where student_names.student_id = students.student_id
+                order by lower(student_names.student_name_language) = 'en' desc
+                limit 1
+            ) as student_names on true
+            where students.professor_id = $1
+            order by students.student_representative_id asc
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    let advisees = client
+        .query(&statement, &[&params.professor_id])
+        .await?
+        .iter()
+        .map(|a| GetAdviseesListResponseBodyInner::try_from(a))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(HttpResponse::Ok().json(GetAdviseesListResponseBody { advisees }))
+}
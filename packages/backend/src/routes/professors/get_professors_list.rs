@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use actix_web::{web, HttpResponse};
+use postgres_types::Type;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{
+        requests::{AuthenticationHeaders, SqlRange},
+        DEFAULT_PAGE, DEFAULT_PAGE_SIZE,
+    },
+    errors::HttpError,
+    extractors::users::AuthenticatedUserClaims,
+    services::names::PersonName,
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, IntoParams, ToSchema, TS)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct GetProfessorsListRequestQueries {
+    /// Full text search on names of professors in every language.
+    #[serde(
+        default,
+        deserialize_with = "crate::constants::requests::empty_string_as_none"
+    )]
+    #[ts(optional)]
+    pub search: Option<String>,
+    /// page of the queried data. If less than `1`, will default to `1`
+    #[param(minimum = 1, default = json!(DEFAULT_PAGE))]
+    #[serde(
+        default,
+        deserialize_with = "crate::constants::requests::deserialize_page"
+    )]
+    #[ts(optional)]
+    pub page: Option<i32>,
+    /// size of page for each query. will use default page size if it is out of bounds.
+    #[param(minimum = 1, maximum = 100, default = json!(DEFAULT_PAGE_SIZE))]
+    #[serde(
+        default,
+        deserialize_with = "crate::constants::requests::deserialize_page_size"
+    )]
+    #[ts(optional)]
+    pub page_size: Option<i32>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetProfessorsListResponseBody {
+    professors: Vec<GetProfessorsListResponseBodyInner>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetProfessorsListResponseBodyInner {
+    id: String,
+    professions: String,
+    names: Vec<PersonName>,
+}
+
+/// Directory of professors with their names in every language.
+#[utoipa::path(
+    get,
+    path = "/professors",
+    tag = "professors",
+    operation_id = "get_professors_list",
+    params(AuthenticationHeaders, GetProfessorsListRequestQueries),
+    responses(
+        (
+            status = 200,
+            description = "successfully get list of professors",
+            body = GetProfessorsListResponseBody,
+            example = json!({ "professors": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    query: web::Query<GetProfessorsListRequestQueries>,
+    data: web::Data<SharedAppData>,
+    _claims: AuthenticatedUserClaims,
+) -> Result<HttpResponse, HttpError> {
+    let SqlRange { limit, offset } = SqlRange::from_page(
+        query.page.unwrap_or(DEFAULT_PAGE),
+        query.page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+    )?;
+
+    let client = data.pool.get().await?;
+
+    let names_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                professor_id,
+                professor_name_language,
+                professor_first_name,
+                professor_middle_name,
+                professor_last_name
+            from professor_names
+            where professor_id = any($1)
+            order by professor_name_language asc
+            "##,
+            &[Type::TEXT_ARRAY],
+        )
+        .await?;
+
+    let rows = match query.search {
+        Some(ref search) => {
+            let statement = client
+                .prepare_typed_cached(
+                    r##"
+                    select
+                        professors.professor_id,
+                        professors.professor_professions
+                    from professors
+                    where exists (
+                        select 1
+                        from professor_names
+                        where
+                            professor_names.professor_id = professors.professor_id and (
+                                professor_names.professor_first_name &@~ $1 or
+                                professor_names.professor_middle_name &@~ $1 or
+                                professor_names.professor_last_name &@~ $1
+                            )
+                    )
+                    order by professors.professor_id asc
+                    limit $2
+                    offset $3
+                    "##,
+                    &[Type::TEXT, Type::INT4, Type::INT4],
+                )
+                .await?;
+
+            client.query(&statement, &[search, &limit, &offset]).await?
+        }
+        None => {
+            let statement = client
+                .prepare_typed_cached(
+                    r##"
+                    select
+                        professor_id,
+                        professor_professions
+                    from professors
+                    order by professor_id asc
+                    limit $1
+                    offset $2
+                    "##,
+                    &[Type::INT4, Type::INT4],
+                )
+                .await?;
+
+            client.query(&statement, &[&limit, &offset]).await?
+        }
+    };
+
+    let mut professors = rows
+        .iter()
+        .map(|r| {
+            Ok(GetProfessorsListResponseBodyInner {
+                id: r.try_get("professor_id")?,
+                professions: r.try_get("professor_professions")?,
+                names: vec![],
+            })
+        })
+        .collect::<Result<Vec<_>, HttpError>>()?;
+
+    let professor_ids = professors.iter().map(|p| p.id.clone()).collect::<Vec<_>>();
+    let mut names = HashMap::<String, Vec<PersonName>>::new();
+
+    for row in client.query(&names_statement, &[&professor_ids]).await? {
+        names
+            .entry(row.try_get("professor_id")?)
+            .or_default()
+            .push(PersonName {
+                language: row.try_get("professor_name_language")?,
+                first_name: row.try_get("professor_first_name")?,
+                middle_name: row.try_get("professor_middle_name")?,
+                last_name: row.try_get("professor_last_name")?,
+            });
+    }
+
+    for professor in professors.iter_mut() {
+        professor.names = names.remove(&professor.id).unwrap_or_default();
+    }
+
+    Ok(HttpResponse::Ok().json(GetProfessorsListResponseBody { professors }))
+}
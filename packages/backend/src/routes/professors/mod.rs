@@ -0,0 +1,3 @@
+pub mod get_advisees_list;
+pub mod get_professors_list;
+pub mod post_professor;
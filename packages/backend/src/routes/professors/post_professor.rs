@@ -0,0 +1,215 @@
+use actix_web::{web, HttpResponse};
+use argon2::{password_hash::SaltString, PasswordHasher};
+use postgres_types::Type;
+use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    constants::{
+        create_argon2_context, requests::AuthenticationHeaders, ARGON2_PEPPER_STRING, ID_LENGTH,
+    },
+    database::Role,
+    errors::HttpError,
+    extractors::admins::AuthenticatedAdminClaims,
+    services::names::{normalize_names, PersonName},
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema)]
+pub struct PostProfessorRequestBody {
+    pub username: String,
+    pub email: String,
+    pub password: String,
+    #[schema(value_type = String, format = Date)]
+    #[serde(with = "time::Date")]
+    pub birthdate: time::Date,
+    #[serde(default)]
+    pub professions: String,
+    /// Names of the professor, at most one in each language.
+    pub names: Vec<PersonName>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct PostProfessorResponseBody {
+    /// `professor_id` of the new professor, which is also the `user_id`.
+    id: String,
+}
+
+/// Creates a professor account with the profile and the names of the professor, the new
+/// `professor_id` could be used as the advisor when signing up students.
+#[utoipa::path(
+    post,
+    path = "/professors",
+    tag = "professors",
+    operation_id = "post_professor",
+    params(AuthenticationHeaders),
+    request_body = PostProfessorRequestBody,
+    responses(
+        (
+            status = 201,
+            description = "professor created",
+            body = PostProfessorResponseBody,
+        ),
+        (
+            status = 400,
+            description = "input errors, or the username or email is already taken",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InputValidationError.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    body: web::Json<PostProfessorRequestBody>,
+    data: web::Data<SharedAppData>,
+    _claims: AuthenticatedAdminClaims,
+) -> Result<HttpResponse, HttpError> {
+    if body.username.trim().is_empty() || body.email.trim().is_empty() || body.password.is_empty() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let names = normalize_names(&body.names)?;
+
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    let possible_redundancies = transaction
+        .query(
+            "select user_id from users where user_username = $1 or user_email = $2",
+            &[&body.username.trim(), &body.email.trim()],
+        )
+        .await?;
+
+    if possible_redundancies.len() > 0 {
+        return Err(HttpError::InputValidationError);
+    }
+
+    // types are left for postgres to infer because `user_role` is an enum.
+    let insert_user_statement = transaction
+        .prepare_cached(
+            r##"
+            insert into users (
+                user_id,
+                user_username,
+                user_email,
+                user_password,
+                user_role,
+                user_birthdate
+            ) values (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6
+            )
+            "##,
+        )
+        .await?;
+
+    let insert_professor_statement = transaction
+        .prepare_typed_cached(
+            r##"
+            insert into professors (
+                professor_id,
+                professor_professions
+            ) values (
+                $1,
+                $2
+            )
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let insert_professor_name_statement = transaction
+        .prepare_typed_cached(
+            r##"
+            insert into professor_names (
+                professor_name_id,
+                professor_id,
+                professor_name_language,
+                professor_first_name,
+                professor_middle_name,
+                professor_last_name
+            ) values (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6
+            )
+            "##,
+            &[
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+            ],
+        )
+        .await?;
+
+    let context = create_argon2_context(&ARGON2_PEPPER_STRING)?;
+    let salt = SaltString::generate(&mut OsRng);
+    let password = context.hash_password(body.password.as_bytes(), salt.as_str())?;
+
+    let professor_id = randoid::randoid!(ID_LENGTH);
+
+    transaction
+        .execute(
+            &insert_user_statement,
+            &[
+                &professor_id,
+                &body.username.trim(),
+                &body.email.trim(),
+                &password.to_string(),
+                &Role::Professor,
+                &body.birthdate,
+            ],
+        )
+        .await?;
+
+    transaction
+        .execute(
+            &insert_professor_statement,
+            &[&professor_id, &body.professions.trim()],
+        )
+        .await?;
+
+    for name in names {
+        transaction
+            .execute(
+                &insert_professor_name_statement,
+                &[
+                    &randoid::randoid!(ID_LENGTH),
+                    &professor_id,
+                    &name.language,
+                    &name.first_name,
+                    &name.middle_name,
+                    &name.last_name,
+                ],
+            )
+            .await?;
+    }
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Created().json(PostProfessorResponseBody { id: professor_id }))
+}
@@ -0,0 +1,80 @@
+use actix_web::{web, HttpResponse};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::users::AuthenticatedUserClaims,
+    services::registration_periods::{
+        get_registration_periods, RegistrationPeriod, RegistrationPeriods,
+    },
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct GetRegistrationPeriodsRequestParams {
+    pub semester_id: String,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetRegistrationPeriodsResponseBody {
+    periods: RegistrationPeriods,
+    /// The window the semester is currently in, not exists when the registration is closed.
+    #[ts(optional)]
+    current_period: Option<RegistrationPeriod>,
+}
+
+/// Get registration windows of a semester and which one is currently opened.
+#[utoipa::path(
+    get,
+    path = "/semesters/{semester_id}/registration-periods",
+    tag = "semesters",
+    operation_id = "get_registration_periods",
+    params(AuthenticationHeaders, GetRegistrationPeriodsRequestParams),
+    responses(
+        (
+            status = 200,
+            description = "successfully get registration periods",
+            body = GetRegistrationPeriodsResponseBody
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "registration periods of the semester are not configured",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::RegistrationPeriodsNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<GetRegistrationPeriodsRequestParams>,
+    data: web::Data<SharedAppData>,
+    _claims: AuthenticatedUserClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    let periods = get_registration_periods(&client, &params.semester_id)
+        .await?
+        .ok_or(HttpError::RegistrationPeriodsNotFound)?;
+    let current_period = periods.get_period_at(time::OffsetDateTime::now_utc());
+
+    Ok(HttpResponse::Ok().json(GetRegistrationPeriodsResponseBody {
+        periods,
+        current_period,
+    }))
+}
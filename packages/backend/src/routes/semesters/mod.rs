@@ -0,0 +1,3 @@
+pub mod get_registration_periods;
+pub mod put_registration_periods;
+pub mod subjects;
@@ -0,0 +1,154 @@
+use actix_web::{web, HttpResponse};
+use postgres_types::Type;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, responses::DefaultSuccessResponse},
+    errors::HttpError,
+    extractors::admins::AuthenticatedAdminClaims,
+    services::registration_periods::RegistrationPeriods,
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct PutRegistrationPeriodsRequestParams {
+    pub semester_id: String,
+}
+
+/// Configures when students could enroll in, drop and withdraw from subjects of a semester. The
+/// windows must be in the order of early registration, general registration, add/drop and
+/// withdrawal, and must not overlap each other.
+#[utoipa::path(
+    put,
+    path = "/semesters/{semester_id}/registration-periods",
+    tag = "semesters",
+    operation_id = "put_registration_periods",
+    params(AuthenticationHeaders, PutRegistrationPeriodsRequestParams),
+    request_body = RegistrationPeriods,
+    responses(
+        (
+            status = 200,
+            description = "registration periods saved",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 400,
+            description = "input errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InputValidationError.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "semester not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::SemesterNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<PutRegistrationPeriodsRequestParams>,
+    body: web::Json<RegistrationPeriods>,
+    data: web::Data<SharedAppData>,
+    _claims: AuthenticatedAdminClaims,
+) -> Result<HttpResponse, HttpError> {
+    body.validate()?;
+
+    let client = data.pool.get().await?;
+
+    let semester = client
+        .query_opt(
+            "select semester_id from semesters where semester_id = $1",
+            &[&params.semester_id],
+        )
+        .await?;
+
+    if semester.is_none() {
+        return Err(HttpError::SemesterNotFound);
+    }
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            insert into semester_registration_periods (
+                semester_id,
+                early_registration_minimum_study_year,
+                early_registration_start_timestamp,
+                early_registration_end_timestamp,
+                general_registration_start_timestamp,
+                general_registration_end_timestamp,
+                add_drop_start_timestamp,
+                add_drop_end_timestamp,
+                withdrawal_start_timestamp,
+                withdrawal_end_timestamp
+            ) values (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6,
+                $7,
+                $8,
+                $9,
+                $10
+            ) on conflict (semester_id) do update set
+                early_registration_minimum_study_year = $2,
+                early_registration_start_timestamp = $3,
+                early_registration_end_timestamp = $4,
+                general_registration_start_timestamp = $5,
+                general_registration_end_timestamp = $6,
+                add_drop_start_timestamp = $7,
+                add_drop_end_timestamp = $8,
+                withdrawal_start_timestamp = $9,
+                withdrawal_end_timestamp = $10
+            "##,
+            &[
+                Type::TEXT,
+                Type::INT4,
+                Type::TIMESTAMPTZ,
+                Type::TIMESTAMPTZ,
+                Type::TIMESTAMPTZ,
+                Type::TIMESTAMPTZ,
+                Type::TIMESTAMPTZ,
+                Type::TIMESTAMPTZ,
+                Type::TIMESTAMPTZ,
+                Type::TIMESTAMPTZ,
+            ],
+        )
+        .await?;
+
+    client
+        .execute(
+            &statement,
+            &[
+                &params.semester_id,
+                &body.early_registration_minimum_study_year,
+                &body.early_registration_start_timestamp,
+                &body.early_registration_end_timestamp,
+                &body.general_registration_start_timestamp,
+                &body.general_registration_end_timestamp,
+                &body.add_drop_start_timestamp,
+                &body.add_drop_end_timestamp,
+                &body.withdrawal_start_timestamp,
+                &body.withdrawal_end_timestamp,
+            ],
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(DefaultSuccessResponse::default()))
+}
@@ -0,0 +1,130 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, responses::DefaultSuccessResponse},
+    errors::HttpError,
+    extractors::students::AuthenticatedStudentClaims,
+    services::{
+        billing::update_invoice,
+        enrollments::{
+            drop_enrollment, fill_seats_from_waitlist, lock_opening_subject, lock_student,
+            remove_from_waitlist, withdraw_enrollment,
+        },
+        registration_periods::get_current_registration_period,
+    },
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct DeleteEnrollmentRequestParams {
+    pub semester_id: String,
+    pub subject_id: String,
+}
+
+/// Drops the signed in student from an opening subject, or removes the student from the subject's
+/// waitlist. The freed seat is given to the first eligible student in the waitlist once the drop is
+/// saved. During the withdrawal period the enrollment is kept and marked as withdrawn
+/// instead, so it shows up as "W" on the transcript and is still charged.
+#[utoipa::path(
+    delete,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/enrollment",
+    tag = "semesters",
+    operation_id = "delete_enrollment",
+    params(AuthenticationHeaders, DeleteEnrollmentRequestParams),
+    responses(
+        (
+            status = 200,
+            description = "dropped successfully",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "registration is closed",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::RegistrationClosed.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "student is neither enrolled nor waitlisted in the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::EnrollmentNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<DeleteEnrollmentRequestParams>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedStudentClaims,
+) -> Result<HttpResponse, HttpError> {
+    let student_id = claims.0.access_token.claims.uid.as_str();
+
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    lock_student(&transaction, student_id).await?;
+    lock_opening_subject(&transaction, &params.semester_id, &params.subject_id).await?;
+
+    let period = get_current_registration_period(&transaction, &params.semester_id).await?;
+
+    let dropped = if period.is_enrollment_open() {
+        drop_enrollment(
+            &transaction,
+            &params.semester_id,
+            &params.subject_id,
+            student_id,
+        )
+        .await?
+    } else {
+        withdraw_enrollment(
+            &transaction,
+            &params.semester_id,
+            &params.subject_id,
+            student_id,
+        )
+        .await?
+    };
+
+    if !dropped {
+        if !remove_from_waitlist(
+            &transaction,
+            &params.semester_id,
+            &params.subject_id,
+            student_id,
+        )
+        .await?
+        {
+            return Err(HttpError::EnrollmentNotFound);
+        }
+    } else if period.is_enrollment_open() {
+        update_invoice(&transaction, &params.semester_id, student_id).await?;
+    }
+
+    transaction.commit().await?;
+
+    // the drop is already committed, a failed promotion leaves the students in the waitlist.
+    if dropped && period.is_enrollment_open() {
+        if let Err(e) =
+            fill_seats_from_waitlist(&mut client, &params.semester_id, &params.subject_id).await
+        {
+            tracing::error!("cannot fill seats from the waitlist: {}", e);
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(DefaultSuccessResponse::default()))
+}
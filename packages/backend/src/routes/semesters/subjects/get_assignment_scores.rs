@@ -0,0 +1,148 @@
+use actix_web::{web, HttpResponse};
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    services::{gradebook::get_assignment_full_score, professors::is_teaching},
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct AssignmentScoresRequestParams {
+    pub semester_id: String,
+    pub subject_id: String,
+    pub assignment_id: String,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetAssignmentScoresResponseBody {
+    scores: Vec<GetAssignmentScoresResponseBodyInner>,
+}
+
+#[derive(Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct GetAssignmentScoresResponseBodyInner {
+    student_id: String,
+    student_representative_id: String,
+    /// Not exists when the score is not entered yet.
+    #[schema(value_type = Option<String>, example = json!("8.50"))]
+    #[ts(optional, type = "string")]
+    score: Option<Decimal>,
+}
+
+/// Get scores of every enrolled student in an assignment, students without a score are included so
+/// the professor could see what is left to enter.
+#[utoipa::path(
+    get,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/assignments/{assignment_id}/scores",
+    tag = "semesters",
+    operation_id = "get_assignment_scores",
+    params(AuthenticationHeaders, AssignmentScoresRequestParams),
+    responses(
+        (
+            status = 200,
+            description = "successfully get scores of the assignment",
+            body = GetAssignmentScoresResponseBody,
+            example = json!({ "scores": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "professor does not teach the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::NotTeaching.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "assignment not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::AssignmentNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<AssignmentScoresRequestParams>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    if !is_teaching(
+        &client,
+        &params.semester_id,
+        &params.subject_id,
+        &claims.0.access_token.claims.uid,
+    )
+    .await?
+    {
+        return Err(HttpError::NotTeaching);
+    }
+
+    get_assignment_full_score(
+        &client,
+        &params.semester_id,
+        &params.subject_id,
+        &params.assignment_id,
+    )
+    .await?;
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_enrollments.student_id,
+                students.student_representative_id,
+                student_scores.student_score as score
+            from student_enrollments
+            inner join students on student_enrollments.student_id = students.student_id
+            left join student_scores on
+                student_enrollments.semester_id = student_scores.semester_id and
+                student_enrollments.subject_id = student_scores.subject_id and
+                student_enrollments.student_id = student_scores.student_id and
+                student_scores.assignment_id = $3
+            where
+                student_enrollments.semester_id = $1 and
+                student_enrollments.subject_id = $2 and
+                not student_enrollments.student_enrollment_is_withdrawn
+            order by students.student_representative_id asc
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let scores = client
+        .query(
+            &statement,
+            &[
+                &params.semester_id,
+                &params.subject_id,
+                &params.assignment_id,
+            ],
+        )
+        .await?
+        .iter()
+        .map(|s| GetAssignmentScoresResponseBodyInner::try_from(s))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(HttpResponse::Ok().json(GetAssignmentScoresResponseBody { scores }))
+}
@@ -0,0 +1,122 @@
+use actix_web::{web, HttpResponse};
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders, errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims, services::professors::is_teaching,
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct GetAssignmentsListRequestParams {
+    pub semester_id: String,
+    pub subject_id: String,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetAssignmentsListResponseBody {
+    assignments: Vec<GetAssignmentsListResponseBodyInner>,
+}
+
+#[derive(Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct GetAssignmentsListResponseBodyInner {
+    id: String,
+    name: String,
+    #[schema(value_type = String, example = json!("10.00"))]
+    #[ts(type = "string")]
+    full_score: Decimal,
+    /// How many students already have a score in the assignment.
+    #[ts(type = "number")]
+    score_count: i64,
+}
+
+/// Get every assignment of an opening subject the signed in professor teaches.
+#[utoipa::path(
+    get,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/assignments",
+    tag = "semesters",
+    operation_id = "get_assignments_list",
+    params(AuthenticationHeaders, GetAssignmentsListRequestParams),
+    responses(
+        (
+            status = 200,
+            description = "successfully get list of assignments",
+            body = GetAssignmentsListResponseBody,
+            example = json!({ "assignments": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "professor does not teach the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::NotTeaching.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<GetAssignmentsListRequestParams>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    if !is_teaching(
+        &client,
+        &params.semester_id,
+        &params.subject_id,
+        &claims.0.access_token.claims.uid,
+    )
+    .await?
+    {
+        return Err(HttpError::NotTeaching);
+    }
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_assignments.student_assignment_id as id,
+                student_assignments.student_assignment_name as name,
+                student_assignments.student_assignment_full_score as full_score,
+                count(student_scores.student_id) as score_count
+            from student_assignments
+            left join student_scores on
+                student_assignments.student_assignment_id = student_scores.assignment_id
+            where
+                student_assignments.semester_id = $1 and
+                student_assignments.subject_id = $2
+            group by student_assignments.student_assignment_id
+            order by student_assignments.student_assignment_name asc
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let assignments = client
+        .query(&statement, &[&params.semester_id, &params.subject_id])
+        .await?
+        .iter()
+        .map(|a| GetAssignmentsListResponseBodyInner::try_from(a))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(HttpResponse::Ok().json(GetAssignmentsListResponseBody { assignments }))
+}
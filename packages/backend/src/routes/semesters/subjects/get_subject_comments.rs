@@ -0,0 +1,111 @@
+use actix_web::{web, HttpResponse};
+use postgres_types::Type;
+use serde::Serialize;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, MINIMUM_COMMENT_RESPONSE_COUNT},
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    routes::semesters::subjects::post_subject_comment::SubjectCommentsRequestParams,
+    services::{
+        evaluations::{can_show_comments, get_anonymous_comments},
+        professors::is_teaching,
+    },
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetSubjectCommentsResponseBody {
+    #[ts(type = "number")]
+    response_count: i64,
+    #[ts(type = "number")]
+    minimum_response_count: i64,
+    /// Always empty until `response_count` reaches `minimum_response_count`.
+    comments: Vec<String>,
+}
+
+/// Get anonymous comments students gave to an opening subject the signed in professor teaches.
+/// Comments are only shown once enough students have commented.
+#[utoipa::path(
+    get,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/comments",
+    tag = "semesters",
+    operation_id = "get_subject_comments",
+    params(AuthenticationHeaders, SubjectCommentsRequestParams),
+    responses(
+        (
+            status = 200,
+            description = "successfully get comments of the subject",
+            body = GetSubjectCommentsResponseBody,
+            example = json!({ "response_count": 2, "minimum_response_count": MINIMUM_COMMENT_RESPONSE_COUNT, "comments": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "professor does not teach the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::NotTeaching.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<SubjectCommentsRequestParams>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    if !is_teaching(
+        &client,
+        &params.semester_id,
+        &params.subject_id,
+        &claims.0.access_token.claims.uid,
+    )
+    .await?
+    {
+        return Err(HttpError::NotTeaching);
+    }
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                count(*) as response_count
+            from student_subject_comments
+            where semester_id = $1 and subject_id = $2
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let response_count = client
+        .query_one(&statement, &[&params.semester_id, &params.subject_id])
+        .await?
+        .try_get::<&str, i64>("response_count")?;
+
+    let comments = if can_show_comments(response_count) {
+        get_anonymous_comments(&client, &params.semester_id, &params.subject_id).await?
+    } else {
+        vec![]
+    };
+
+    Ok(HttpResponse::Ok().json(GetSubjectCommentsResponseBody {
+        response_count,
+        minimum_response_count: MINIMUM_COMMENT_RESPONSE_COUNT,
+        comments,
+    }))
+}
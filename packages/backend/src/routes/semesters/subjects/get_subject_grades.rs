@@ -0,0 +1,95 @@
+use actix_web::{web, HttpResponse};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    services::{
+        grades::{get_subject_grades, lock_grades, SubjectGrade},
+        professors::is_teaching,
+    },
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct SubjectGradesRequestParams {
+    pub semester_id: String,
+    pub subject_id: String,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetSubjectGradesResponseBody {
+    pub is_released: bool,
+    pub grades: Vec<SubjectGrade>,
+}
+
+/// Get the snapshot of grades of an opening subject the signed in professor teaches, use
+/// [post_grades_computation](crate::routes::semesters::subjects::post_grades_computation::handler)
+/// to bring it up to date with the scores.
+#[utoipa::path(
+    get,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/grades",
+    tag = "semesters",
+    operation_id = "get_subject_grades",
+    params(AuthenticationHeaders, SubjectGradesRequestParams),
+    responses(
+        (
+            status = 200,
+            description = "successfully get grades of the subject",
+            body = GetSubjectGradesResponseBody,
+            example = json!({ "is_released": false, "grades": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "professor does not teach the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::NotTeaching.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<SubjectGradesRequestParams>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    if !is_teaching(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        &claims.0.access_token.claims.uid,
+    )
+    .await?
+    {
+        return Err(HttpError::NotTeaching);
+    }
+
+    let is_released = lock_grades(&transaction, &params.semester_id, &params.subject_id).await?;
+    let grades = get_subject_grades(&transaction, &params.semester_id, &params.subject_id).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok().json(GetSubjectGradesResponseBody {
+        is_released,
+        grades,
+    }))
+}
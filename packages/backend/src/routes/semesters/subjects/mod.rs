@@ -0,0 +1,16 @@
+pub mod delete_enrollment;
+pub mod get_assignment_scores;
+pub mod get_assignments_list;
+pub mod get_subject_comments;
+pub mod get_subject_grades;
+pub mod post_assignment;
+pub mod post_enrollment;
+pub mod post_grade_amendment;
+pub mod post_grades_computation;
+pub mod post_grades_release;
+pub mod post_opening_subject_validation;
+pub mod post_subject_comment;
+pub mod put_assignment_scores;
+pub mod put_grade_override;
+pub mod put_opening_subject;
+pub mod put_subject_grading_criteria;
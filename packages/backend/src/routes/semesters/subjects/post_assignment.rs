@@ -0,0 +1,159 @@
+use actix_web::{web, HttpResponse};
+use postgres_types::Type;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, ID_LENGTH},
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    services::{grades::lock_grades, professors::is_teaching},
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct PostAssignmentRequestParams {
+    pub semester_id: String,
+    pub subject_id: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PostAssignmentRequestBody {
+    pub name: String,
+    #[schema(value_type = String, example = json!("10.00"))]
+    pub full_score: Decimal,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct PostAssignmentResponseBody {
+    id: String,
+}
+
+/// Creates an assignment in an opening subject the signed in professor teaches.
+#[utoipa::path(
+    post,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/assignments",
+    tag = "semesters",
+    operation_id = "post_assignment",
+    params(AuthenticationHeaders, PostAssignmentRequestParams),
+    request_body = PostAssignmentRequestBody,
+    responses(
+        (
+            status = 201,
+            description = "assignment created",
+            body = PostAssignmentResponseBody
+        ),
+        (
+            status = 400,
+            description = "input errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InputValidationError.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "professor does not teach the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::NotTeaching.get_error_struct())
+        ),
+        (
+            status = 409,
+            description = "grades are already released",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::GradesAlreadyReleased.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<PostAssignmentRequestParams>,
+    body: web::Json<PostAssignmentRequestBody>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    // the full score is stored as `numeric(6, 2)`.
+    if body.name.trim().is_empty()
+        || body.full_score <= Decimal::ZERO
+        || body.full_score.round_dp(2) >= Decimal::from(10_000)
+    {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    if !is_teaching(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        &claims.0.access_token.claims.uid,
+    )
+    .await?
+    {
+        return Err(HttpError::NotTeaching);
+    }
+
+    if lock_grades(&transaction, &params.semester_id, &params.subject_id).await? {
+        return Err(HttpError::GradesAlreadyReleased);
+    }
+
+    let statement = transaction
+        .prepare_typed_cached(
+            r##"
+            insert into student_assignments (
+                student_assignment_id,
+                student_assignment_name,
+                subject_id,
+                semester_id,
+                student_assignment_full_score
+            ) values (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5
+            )
+            "##,
+            &[
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::NUMERIC,
+            ],
+        )
+        .await?;
+
+    let id = randoid::randoid!(ID_LENGTH);
+
+    transaction
+        .execute(
+            &statement,
+            &[
+                &id,
+                &body.name.trim(),
+                &params.subject_id,
+                &params.semester_id,
+                &body.full_score,
+            ],
+        )
+        .await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Created().json(PostAssignmentResponseBody { id }))
+}
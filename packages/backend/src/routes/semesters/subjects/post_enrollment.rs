@@ -0,0 +1,225 @@
+use actix_web::{web, HttpResponse};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::students::AuthenticatedStudentClaims,
+    services::{
+        billing::{has_unpaid_invoices_in_other_semesters, update_invoice},
+        enrollments::{
+            add_to_waitlist, count_enrollments, enroll, find_time_clash, get_waitlist_position,
+            is_eligible, is_enrolled, lock_opening_subject, lock_student, remove_from_waitlist,
+        },
+        registration_periods::check_enrollment_open,
+    },
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct PostEnrollmentRequestParams {
+    pub semester_id: String,
+    pub subject_id: String,
+}
+
+/// Whether the student got a seat or was put into the waitlist.
+#[derive(Serialize, ToSchema, TS, PartialEq)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum EnrollmentStatus {
+    Enrolled,
+    Waitlisted,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct PostEnrollmentResponseBody {
+    status: EnrollmentStatus,
+    /// 1-indexed position in the waitlist, only exists when the student is waitlisted.
+    #[ts(optional, type = "number")]
+    waitlist_position: Option<i64>,
+}
+
+/// Enrolls the signed in student into an opening subject. Enrolling is only opened during early
+/// registration for senior students, general registration and add/drop. The student has to be
+/// eligible for the subject and it must not clash with the rest of the student's subjects in the semester. When the
+/// subject is full, the student is put at the end of the subject's waitlist instead and will be
+/// enrolled automatically once a seat is available. Students who have not paid the invoices of
+/// previous semesters could not enroll, and the invoice of the semester is updated right away.
+#[utoipa::path(
+    post,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/enrollment",
+    tag = "semesters",
+    operation_id = "post_enrollment",
+    params(AuthenticationHeaders, PostEnrollmentRequestParams),
+    responses(
+        (
+            status = 201,
+            description = "enrolled successfully",
+            body = PostEnrollmentResponseBody,
+            example = json!({ "status": "enrolled" })
+        ),
+        (
+            status = 202,
+            description = "subject is full, student is put in the waitlist",
+            body = PostEnrollmentResponseBody,
+            example = json!({ "status": "waitlisted", "waitlist_position": 3 })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 402,
+            description = "invoices of previous semesters are not paid",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::UnpaidInvoice.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "registration is closed or student is not eligible for the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::NotEligible.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "subject is not opened in the semester",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::OpeningSubjectNotFound.get_error_struct())
+        ),
+        (
+            status = 409,
+            description = "subject clashes with another subject, already enrolled or already waitlisted",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::EnrollmentTimeClash.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<PostEnrollmentRequestParams>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedStudentClaims,
+) -> Result<HttpResponse, HttpError> {
+    let student_id = claims.0.access_token.claims.uid.as_str();
+
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    check_enrollment_open(&transaction, &params.semester_id, student_id).await?;
+
+    lock_student(&transaction, student_id).await?;
+
+    if has_unpaid_invoices_in_other_semesters(&transaction, &params.semester_id, student_id).await?
+    {
+        return Err(HttpError::UnpaidInvoice);
+    }
+
+    let subject_capacity =
+        lock_opening_subject(&transaction, &params.semester_id, &params.subject_id).await?;
+
+    if is_enrolled(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        student_id,
+    )
+    .await?
+    {
+        return Err(HttpError::AlreadyEnrolled);
+    }
+
+    if !is_eligible(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        student_id,
+    )
+    .await?
+    {
+        return Err(HttpError::NotEligible);
+    }
+
+    if let Some(clashing_subject_id) = find_time_clash(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        student_id,
+    )
+    .await?
+    {
+        tracing::debug!(
+            "subject {} clashes with enrolled subject {}",
+            params.subject_id,
+            clashing_subject_id
+        );
+        return Err(HttpError::EnrollmentTimeClash);
+    }
+
+    let enrollment_count =
+        count_enrollments(&transaction, &params.semester_id, &params.subject_id).await?;
+
+    if enrollment_count >= subject_capacity as i64 {
+        if get_waitlist_position(
+            &transaction,
+            &params.semester_id,
+            &params.subject_id,
+            student_id,
+        )
+        .await?
+        .is_some()
+        {
+            return Err(HttpError::AlreadyWaitlisted);
+        }
+
+        let waitlist_position = add_to_waitlist(
+            &transaction,
+            &params.semester_id,
+            &params.subject_id,
+            student_id,
+        )
+        .await?;
+
+        transaction.commit().await?;
+
+        return Ok(HttpResponse::Accepted().json(PostEnrollmentResponseBody {
+            status: EnrollmentStatus::Waitlisted,
+            waitlist_position: Some(waitlist_position),
+        }));
+    }
+
+    // a student could still be in the waitlist when a seat is freed while the student's subjects
+    // clashed with this one, the waitlist entry has to go or the student is promoted again.
+    remove_from_waitlist(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        student_id,
+    )
+    .await?;
+    enroll(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        student_id,
+    )
+    .await?;
+    update_invoice(&transaction, &params.semester_id, student_id).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Created().json(PostEnrollmentResponseBody {
+        status: EnrollmentStatus::Enrolled,
+        waitlist_position: None,
+    }))
+}
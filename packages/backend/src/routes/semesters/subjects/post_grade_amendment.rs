@@ -0,0 +1,201 @@
+use actix_web::{web, HttpResponse};
+use postgres_types::Type;
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, responses::DefaultSuccessResponse, ID_LENGTH},
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    routes::semesters::subjects::put_grade_override::{
+        StudentGradeChangeRequestBody, StudentGradeRequestParams,
+    },
+    services::{
+        grades::{
+            get_grade_alphabet, get_subject_grading_criteria_grades, is_valid_alphabet,
+            lock_grades, override_grade,
+        },
+        notifications::notify,
+        professors::is_teaching,
+    },
+    shared_app_data::SharedAppData,
+};
+
+/// Changes a released grade of a student. Every amendment is recorded with the previous grade,
+/// the new grade, the reason and who made it, and the student gets a notification.
+#[utoipa::path(
+    post,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/grades/{student_id}/amendments",
+    tag = "semesters",
+    operation_id = "post_grade_amendment",
+    params(AuthenticationHeaders, StudentGradeRequestParams),
+    request_body = StudentGradeChangeRequestBody,
+    responses(
+        (
+            status = 201,
+            description = "grade amended",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 400,
+            description = "input errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InputValidationError.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "professor does not teach the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::NotTeaching.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "student has no grade in the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::GradeNotFound.get_error_struct())
+        ),
+        (
+            status = 409,
+            description = "grades are not released yet, override the grade instead",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::GradesNotReleased.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<StudentGradeRequestParams>,
+    body: web::Json<StudentGradeChangeRequestBody>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    let professor_id = claims.0.access_token.claims.uid.as_str();
+
+    if body.reason.trim().is_empty() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    if !is_teaching(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        professor_id,
+    )
+    .await?
+    {
+        return Err(HttpError::NotTeaching);
+    }
+
+    if !lock_grades(&transaction, &params.semester_id, &params.subject_id).await? {
+        return Err(HttpError::GradesNotReleased);
+    }
+
+    let grades =
+        get_subject_grading_criteria_grades(&transaction, &params.semester_id, &params.subject_id)
+            .await?;
+
+    if !is_valid_alphabet(&body.alphabet, &grades) {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let previous_alphabet = get_grade_alphabet(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        &params.student_id,
+    )
+    .await?;
+
+    if previous_alphabet == body.alphabet {
+        return Err(HttpError::InputValidationError);
+    }
+
+    override_grade(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        &params.student_id,
+        &body.alphabet,
+        body.reason.trim(),
+    )
+    .await?;
+
+    let statement = transaction
+        .prepare_typed_cached(
+            r##"
+            insert into student_grade_amendments (
+                student_grade_amendment_id,
+                semester_id,
+                subject_id,
+                student_id,
+                user_id,
+                student_grade_amendment_previous_alphabet,
+                student_grade_amendment_new_alphabet,
+                student_grade_amendment_reason
+            ) values (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6,
+                $7,
+                $8
+            )
+            "##,
+            &[
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+            ],
+        )
+        .await?;
+
+    transaction
+        .execute(
+            &statement,
+            &[
+                &randoid::randoid!(ID_LENGTH),
+                &params.semester_id,
+                &params.subject_id,
+                &params.student_id,
+                &professor_id,
+                &previous_alphabet,
+                &body.alphabet,
+                &body.reason.trim(),
+            ],
+        )
+        .await?;
+
+    notify(
+        &transaction,
+        &params.student_id,
+        &format!(
+            "grade of subject {} in semester {} has been amended from {} to {}",
+            params.subject_id, params.semester_id, previous_alphabet, body.alphabet
+        ),
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Created().json(DefaultSuccessResponse::default()))
+}
@@ -0,0 +1,91 @@
+use actix_web::{web, HttpResponse};
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    routes::semesters::subjects::get_subject_grades::{
+        GetSubjectGradesResponseBody, SubjectGradesRequestParams,
+    },
+    services::{
+        grades::{compute_grades, get_subject_grades, lock_grades},
+        professors::is_teaching,
+    },
+    shared_app_data::SharedAppData,
+};
+
+/// Computes grades of every enrolled student from the scores and the attached grading criteria,
+/// and returns them as a preview. Overridden grades are kept. Grades could only be computed
+/// before they are released.
+#[utoipa::path(
+    post,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/grades/compute",
+    tag = "semesters",
+    operation_id = "post_grades_computation",
+    params(AuthenticationHeaders, SubjectGradesRequestParams),
+    responses(
+        (
+            status = 200,
+            description = "grades computed",
+            body = GetSubjectGradesResponseBody,
+            example = json!({ "is_released": false, "grades": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "professor does not teach the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::NotTeaching.get_error_struct())
+        ),
+        (
+            status = 409,
+            description = "grades are already released",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::GradesAlreadyReleased.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<SubjectGradesRequestParams>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    if !is_teaching(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        &claims.0.access_token.claims.uid,
+    )
+    .await?
+    {
+        return Err(HttpError::NotTeaching);
+    }
+
+    if lock_grades(&transaction, &params.semester_id, &params.subject_id).await? {
+        return Err(HttpError::GradesAlreadyReleased);
+    }
+
+    compute_grades(&transaction, &params.semester_id, &params.subject_id).await?;
+    let grades = get_subject_grades(&transaction, &params.semester_id, &params.subject_id).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok().json(GetSubjectGradesResponseBody {
+        is_released: false,
+        grades,
+    }))
+}
@@ -0,0 +1,108 @@
+use actix_web::{web, HttpResponse};
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, responses::DefaultSuccessResponse},
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    routes::semesters::subjects::get_subject_grades::SubjectGradesRequestParams,
+    services::{
+        grades::{compute_grades, get_subject_grades, lock_grades},
+        notifications::notify,
+        professors::is_teaching,
+    },
+    shared_app_data::SharedAppData,
+};
+
+/// Computes the final grades one last time and releases them to the students, every student gets
+/// a notification. Grades could only be changed through amendments afterwards.
+#[utoipa::path(
+    post,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/grades/release",
+    tag = "semesters",
+    operation_id = "post_grades_release",
+    params(AuthenticationHeaders, SubjectGradesRequestParams),
+    responses(
+        (
+            status = 200,
+            description = "grades released",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "professor does not teach the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::NotTeaching.get_error_struct())
+        ),
+        (
+            status = 409,
+            description = "grades are already released",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::GradesAlreadyReleased.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<SubjectGradesRequestParams>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    if !is_teaching(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        &claims.0.access_token.claims.uid,
+    )
+    .await?
+    {
+        return Err(HttpError::NotTeaching);
+    }
+
+    if lock_grades(&transaction, &params.semester_id, &params.subject_id).await? {
+        return Err(HttpError::GradesAlreadyReleased);
+    }
+
+    compute_grades(&transaction, &params.semester_id, &params.subject_id).await?;
+
+    transaction
+        .execute(
+            r##"
+            update opening_subjects_in_semester_subject_descriptions set
+                is_grade_released = true
+            where semester_id = $1 and subject_id = $2
+            "##,
+            &[&params.semester_id, &params.subject_id],
+        )
+        .await?;
+
+    for grade in get_subject_grades(&transaction, &params.semester_id, &params.subject_id).await? {
+        notify(
+            &transaction,
+            &grade.student_id,
+            &format!(
+                "grade of subject {} in semester {} has been released",
+                params.subject_id, params.semester_id
+            ),
+        )
+        .await?;
+    }
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok().json(DefaultSuccessResponse::default()))
+}
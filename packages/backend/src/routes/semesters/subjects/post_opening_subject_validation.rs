@@ -0,0 +1,92 @@
+use actix_web::{web, HttpResponse};
+use serde::Serialize;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::admins::AuthenticatedAdminClaims,
+    routes::semesters::subjects::put_opening_subject::{
+        validate_opening_subject, PutOpeningSubjectRequestBody, PutOpeningSubjectRequestParams,
+    },
+    services::schedule_conflicts::{
+        find_schedule_conflicts, ProposedOpeningSubject, ScheduleConflict,
+    },
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct PostOpeningSubjectValidationResponseBody {
+    /// Every conflict found, empty when the opening subject can be saved.
+    conflicts: Vec<ScheduleConflict>,
+}
+
+/// Dry runs the schedule conflict checks of
+/// [put_opening_subject](crate::routes::semesters::subjects::put_opening_subject::handler) without
+/// writing anything, so planners could see every conflict at once.
+#[utoipa::path(
+    post,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/validate",
+    tag = "semesters",
+    operation_id = "post_opening_subject_validation",
+    params(AuthenticationHeaders, PutOpeningSubjectRequestParams),
+    request_body = PutOpeningSubjectRequestBody,
+    responses(
+        (
+            status = 200,
+            description = "list of conflicts of the opening subject",
+            body = PostOpeningSubjectValidationResponseBody,
+            example = json!({ "conflicts": [] })
+        ),
+        (
+            status = 400,
+            description = "input errors, such as overlapping schedules, a room used twice, unknown professors or majors, or a capacity below the enrolled students",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InputValidationError.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "semester, subject or grading criteria not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::SemesterNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<PutOpeningSubjectRequestParams>,
+    body: web::Json<PutOpeningSubjectRequestBody>,
+    data: web::Data<SharedAppData>,
+    _claims: AuthenticatedAdminClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    validate_opening_subject(&client, &params, &body).await?;
+
+    let conflicts = find_schedule_conflicts(
+        &client,
+        &ProposedOpeningSubject {
+            semester_id: &params.semester_id,
+            subject_id: &params.subject_id,
+            subject_capacity: body.subject_capacity,
+            professor_ids: &body.professor_ids,
+            schedules: &body.schedules,
+        },
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(PostOpeningSubjectValidationResponseBody { conflicts }))
+}
@@ -0,0 +1,126 @@
+use actix_web::{web, HttpResponse};
+use postgres_types::Type;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, responses::DefaultSuccessResponse},
+    errors::HttpError,
+    extractors::students::AuthenticatedStudentClaims,
+    services::evaluations::check_evaluation_open,
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct SubjectCommentsRequestParams {
+    pub semester_id: String,
+    pub subject_id: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PostSubjectCommentRequestBody {
+    pub comment: String,
+}
+
+/// Submits the evaluation of an opening subject the signed in student studied. Every student
+/// could only comment once per subject, and only after the grades of the subject are released.
+#[utoipa::path(
+    post,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/comments",
+    tag = "semesters",
+    operation_id = "post_subject_comment",
+    params(AuthenticationHeaders, SubjectCommentsRequestParams),
+    request_body = PostSubjectCommentRequestBody,
+    responses(
+        (
+            status = 201,
+            description = "comment submitted",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 400,
+            description = "input errors or student did not study the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::StudentNotEnrolled.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "opening subject not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::OpeningSubjectNotFound.get_error_struct())
+        ),
+        (
+            status = 409,
+            description = "already commented or the grades are not released yet",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::CommentAlreadySubmitted.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<SubjectCommentsRequestParams>,
+    body: web::Json<PostSubjectCommentRequestBody>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedStudentClaims,
+) -> Result<HttpResponse, HttpError> {
+    let student_id = claims.0.access_token.claims.uid.as_str();
+
+    if body.comment.trim().is_empty() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let client = data.pool.get().await?;
+
+    check_evaluation_open(&client, &params.semester_id, &params.subject_id, student_id).await?;
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            insert into student_subject_comments (
+                semester_id,
+                subject_id,
+                student_id,
+                student_comment
+            ) values (
+                $1,
+                $2,
+                $3,
+                $4
+            ) on conflict (semester_id, subject_id, student_id) do nothing
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let inserted = client
+        .execute(
+            &statement,
+            &[
+                &params.semester_id,
+                &params.subject_id,
+                &student_id,
+                &body.comment.trim(),
+            ],
+        )
+        .await?;
+
+    if inserted == 0 {
+        return Err(HttpError::CommentAlreadySubmitted);
+    }
+
+    Ok(HttpResponse::Created().json(DefaultSuccessResponse::default()))
+}
@@ -0,0 +1,163 @@
+use actix_web::{web, HttpResponse};
+use postgres_types::Type;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, responses::DefaultSuccessResponse},
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    routes::semesters::subjects::get_assignment_scores::AssignmentScoresRequestParams,
+    services::{
+        gradebook::{
+            get_assignment_full_score, get_enrolled_student_ids, validate_scores, ScoreEntry,
+        },
+        grades::lock_grades,
+        professors::is_teaching,
+    },
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema)]
+pub struct PutAssignmentScoresRequestBody {
+    pub scores: Vec<ScoreEntry>,
+}
+
+/// Enters or updates scores of many students in an assignment at once. Nothing is written when
+/// any of the scores is above the full score of the assignment or belongs to a student who is not
+/// enrolled in the subject.
+#[utoipa::path(
+    put,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/assignments/{assignment_id}/scores",
+    tag = "semesters",
+    operation_id = "put_assignment_scores",
+    params(AuthenticationHeaders, AssignmentScoresRequestParams),
+    request_body = PutAssignmentScoresRequestBody,
+    responses(
+        (
+            status = 200,
+            description = "scores saved",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 400,
+            description = "scores above the full score or students not enrolled",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::ScoreExceedsFullScore.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "professor does not teach the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::NotTeaching.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "assignment not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::AssignmentNotFound.get_error_struct())
+        ),
+        (
+            status = 409,
+            description = "grades are already released",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::GradesAlreadyReleased.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<AssignmentScoresRequestParams>,
+    body: web::Json<PutAssignmentScoresRequestBody>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    if !is_teaching(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        &claims.0.access_token.claims.uid,
+    )
+    .await?
+    {
+        return Err(HttpError::NotTeaching);
+    }
+
+    if lock_grades(&transaction, &params.semester_id, &params.subject_id).await? {
+        return Err(HttpError::GradesAlreadyReleased);
+    }
+
+    let full_score = get_assignment_full_score(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        &params.assignment_id,
+    )
+    .await?;
+    let enrolled_student_ids =
+        get_enrolled_student_ids(&transaction, &params.semester_id, &params.subject_id).await?;
+
+    validate_scores(&body.scores, full_score, &enrolled_student_ids)?;
+
+    let statement = transaction
+        .prepare_typed_cached(
+            r##"
+            insert into student_scores (
+                semester_id,
+                subject_id,
+                student_id,
+                assignment_id,
+                student_score
+            ) values (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5
+            ) on conflict (semester_id, subject_id, student_id, assignment_id) do update set
+                student_score = $5
+            "##,
+            &[
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::NUMERIC,
+            ],
+        )
+        .await?;
+
+    for entry in body.scores.iter() {
+        transaction
+            .execute(
+                &statement,
+                &[
+                    &params.semester_id,
+                    &params.subject_id,
+                    &entry.student_id,
+                    &params.assignment_id,
+                    &entry.score,
+                ],
+            )
+            .await?;
+    }
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok().json(DefaultSuccessResponse::default()))
+}
@@ -0,0 +1,137 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, responses::DefaultSuccessResponse},
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    services::{
+        grades::{
+            get_subject_grading_criteria_grades, is_valid_alphabet, lock_grades, override_grade,
+        },
+        professors::is_teaching,
+    },
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct StudentGradeRequestParams {
+    pub semester_id: String,
+    pub subject_id: String,
+    pub student_id: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct StudentGradeChangeRequestBody {
+    /// One of the grades of the attached grading criteria or "F".
+    #[schema(example = json!("B+"))]
+    pub alphabet: String,
+    pub reason: String,
+}
+
+/// Replaces the computed grade of a student before the grades are released. Later computations
+/// keep the overridden grade.
+#[utoipa::path(
+    put,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/grades/{student_id}",
+    tag = "semesters",
+    operation_id = "put_grade_override",
+    params(AuthenticationHeaders, StudentGradeRequestParams),
+    request_body = StudentGradeChangeRequestBody,
+    responses(
+        (
+            status = 200,
+            description = "grade overridden",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 400,
+            description = "input errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InputValidationError.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "professor does not teach the subject",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::NotTeaching.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "grade is not computed yet",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::GradeNotFound.get_error_struct())
+        ),
+        (
+            status = 409,
+            description = "grades are already released",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::GradesAlreadyReleased.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<StudentGradeRequestParams>,
+    body: web::Json<StudentGradeChangeRequestBody>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    if body.reason.trim().is_empty() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    if !is_teaching(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        &claims.0.access_token.claims.uid,
+    )
+    .await?
+    {
+        return Err(HttpError::NotTeaching);
+    }
+
+    if lock_grades(&transaction, &params.semester_id, &params.subject_id).await? {
+        return Err(HttpError::GradesAlreadyReleased);
+    }
+
+    let grades =
+        get_subject_grading_criteria_grades(&transaction, &params.semester_id, &params.subject_id)
+            .await?;
+
+    if !is_valid_alphabet(&body.alphabet, &grades) {
+        return Err(HttpError::InputValidationError);
+    }
+
+    override_grade(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        &params.student_id,
+        &body.alphabet,
+        body.reason.trim(),
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok().json(DefaultSuccessResponse::default()))
+}
@@ -0,0 +1,491 @@
+use std::collections::HashSet;
+
+use actix_web::{web, HttpResponse};
+use deadpool_postgres::GenericClient;
+use postgres_types::Type;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, responses::DefaultSuccessResponse},
+    errors::HttpError,
+    extractors::admins::AuthenticatedAdminClaims,
+    services::{
+        enrollments::{count_enrollments, fill_seats_from_waitlist},
+        registration_periods::get_registration_periods,
+        schedule_conflicts::{
+            find_schedule_conflicts, validate_schedules, ProposedOpeningSubject, ProposedSchedule,
+        },
+    },
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct PutOpeningSubjectRequestParams {
+    pub semester_id: String,
+    pub subject_id: String,
+}
+
+/// Which major in which academic year could enroll in the subject.
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct OpeningSubjectEligibleMajor {
+    pub major_id: String,
+    pub academic_year_id: String,
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct PutOpeningSubjectRequestBody {
+    pub grading_criteria_id: String,
+    #[schema(minimum = 1, example = json!(60))]
+    pub subject_capacity: i32,
+    /// `user_id` of every professor teaching the subject.
+    pub professor_ids: Vec<String>,
+    pub schedules: Vec<ProposedSchedule>,
+    pub eligible_majors: Vec<OpeningSubjectEligibleMajor>,
+}
+
+/// Checks that the request body is valid, both of the semester and the subject exist, every id in
+/// the body is listed once and exists, and the capacity still fits the enrolled students.
+pub async fn validate_opening_subject(
+    client: &impl GenericClient,
+    params: &PutOpeningSubjectRequestParams,
+    body: &PutOpeningSubjectRequestBody,
+) -> Result<(), HttpError> {
+    if !body.subject_capacity.is_positive() || body.schedules.is_empty() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    validate_schedules(&body.schedules)?;
+
+    let semester = client
+        .query_opt(
+            "select semester_id from semesters where semester_id = $1",
+            &[&params.semester_id],
+        )
+        .await?;
+
+    if semester.is_none() {
+        return Err(HttpError::SemesterNotFound);
+    }
+
+    let subject = client
+        .query_opt(
+            "select subject_id from subjects where subject_id = $1",
+            &[&params.subject_id],
+        )
+        .await?;
+
+    if subject.is_none() {
+        return Err(HttpError::SubjectNotFound);
+    }
+
+    let professor_ids = body.professor_ids.iter().collect::<HashSet<_>>();
+
+    if professor_ids.len() != body.professor_ids.len() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let professors = client
+        .query(
+            "select professor_id from professors where professor_id = any($1)",
+            &[&body.professor_ids],
+        )
+        .await?;
+
+    if professors.len() != body.professor_ids.len() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let grading_criteria = client
+        .query_opt(
+            "select grading_criteria_id from grading_criterias where grading_criteria_id = $1",
+            &[&body.grading_criteria_id],
+        )
+        .await?;
+
+    if grading_criteria.is_none() {
+        return Err(HttpError::GradingCriteriaNotFound);
+    }
+
+    let eligible_majors = body
+        .eligible_majors
+        .iter()
+        .map(|m| (&m.major_id, &m.academic_year_id))
+        .collect::<HashSet<_>>();
+
+    if eligible_majors.len() != body.eligible_majors.len() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let major_ids = Vec::from_iter(eligible_majors.iter().map(|m| m.0).collect::<HashSet<_>>());
+    let academic_year_ids =
+        Vec::from_iter(eligible_majors.iter().map(|m| m.1).collect::<HashSet<_>>());
+
+    let majors = client
+        .query(
+            "select major_id from majors where major_id = any($1)",
+            &[&major_ids],
+        )
+        .await?;
+    let academic_years = client
+        .query(
+            "select academic_year_id from academic_years where academic_year_id = any($1)",
+            &[&academic_year_ids],
+        )
+        .await?;
+
+    if majors.len() != major_ids.len() || academic_years.len() != academic_year_ids.len() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    // the capacity could not drop below the students who are already enrolled.
+    let enrollment_count =
+        count_enrollments(client, &params.semester_id, &params.subject_id).await?;
+
+    if (body.subject_capacity as i64) < enrollment_count {
+        return Err(HttpError::InputValidationError);
+    }
+
+    Ok(())
+}
+
+/// Creates or replaces an opening subject in a semester. The schedules are checked against every
+/// other opening subject in the semester for room double bookings, professor double bookings and
+/// room capacity before anything gets written. When the capacity grows while enrolling is still
+/// opened, waitlisted students are enrolled into the new seats.
+#[utoipa::path(
+    put,
+    path = "/semesters/{semester_id}/subjects/{subject_id}",
+    tag = "semesters",
+    operation_id = "put_opening_subject",
+    params(AuthenticationHeaders, PutOpeningSubjectRequestParams),
+    request_body = PutOpeningSubjectRequestBody,
+    responses(
+        (
+            status = 200,
+            description = "opening subject saved",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 400,
+            description = "input errors, such as overlapping schedules, a room used twice, unknown professors or majors, or a capacity below the enrolled students",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InputValidationError.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "semester, subject or grading criteria not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::SemesterNotFound.get_error_struct())
+        ),
+        (
+            status = 409,
+            description = "schedule conflicts, use the validation endpoint to see the details",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::ScheduleConflict.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<PutOpeningSubjectRequestParams>,
+    body: web::Json<PutOpeningSubjectRequestBody>,
+    data: web::Data<SharedAppData>,
+    _claims: AuthenticatedAdminClaims,
+) -> Result<HttpResponse, HttpError> {
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    // only one schedule write can happen in a semester at a time, otherwise two writes could both
+    // pass the conflict checks and book the same room.
+    transaction
+        .execute(
+            "select pg_advisory_xact_lock(hashtext($1))",
+            &[&params.semester_id],
+        )
+        .await?;
+
+    validate_opening_subject(&transaction, &params, &body).await?;
+
+    let conflicts = find_schedule_conflicts(
+        &transaction,
+        &ProposedOpeningSubject {
+            semester_id: &params.semester_id,
+            subject_id: &params.subject_id,
+            subject_capacity: body.subject_capacity,
+            professor_ids: &body.professor_ids,
+            schedules: &body.schedules,
+        },
+    )
+    .await?;
+
+    if !conflicts.is_empty() {
+        return Err(HttpError::ScheduleConflict);
+    }
+
+    let upsert_description_statement = transaction
+        .prepare_typed_cached(
+            r##"
+            insert into opening_subjects_in_semester_subject_descriptions (
+                semester_id,
+                subject_id,
+                grading_criteria_id,
+                subject_capacity
+            ) values (
+                $1,
+                $2,
+                $3,
+                $4
+            ) on conflict (semester_id, subject_id) do update set
+                grading_criteria_id = $3,
+                subject_capacity = $4
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT, Type::INT4],
+        )
+        .await?;
+
+    let insert_professor_statement = transaction
+        .prepare_typed_cached(
+            r##"
+            insert into opening_subjects_in_semester_professors (
+                semester_id,
+                subject_id,
+                professor_id
+            ) values (
+                $1,
+                $2,
+                $3
+            )
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let insert_schedule_statement = transaction
+        .prepare_typed_cached(
+            r##"
+            insert into opening_subjects_in_semester_schedules (
+                semester_id,
+                subject_id,
+                room_id,
+                day_of_week,
+                start_time_of_day,
+                end_time_of_day
+            ) values (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6
+            )
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let insert_eligible_major_statement = transaction
+        .prepare_typed_cached(
+            r##"
+            insert into opening_subjects_in_semester_eligible_majors (
+                semester_id,
+                subject_id,
+                major_id,
+                academic_year_id
+            ) values (
+                $1,
+                $2,
+                $3,
+                $4
+            )
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    transaction
+        .execute(
+            &upsert_description_statement,
+            &[
+                &params.semester_id,
+                &params.subject_id,
+                &body.grading_criteria_id,
+                &body.subject_capacity,
+            ],
+        )
+        .await?;
+
+    for table in [
+        "opening_subjects_in_semester_professors",
+        "opening_subjects_in_semester_schedules",
+        "opening_subjects_in_semester_eligible_majors",
+    ] {
+        transaction
+            .execute(
+                format!(
+                    "delete from {} where semester_id = $1 and subject_id = $2",
+                    table
+                )
+                .as_str(),
+                &[&params.semester_id, &params.subject_id],
+            )
+            .await?;
+    }
+
+    for professor_id in body.professor_ids.iter() {
+        transaction
+            .execute(
+                &insert_professor_statement,
+                &[&params.semester_id, &params.subject_id, professor_id],
+            )
+            .await?;
+    }
+
+    for schedule in body.schedules.iter() {
+        transaction
+            .execute(
+                &insert_schedule_statement,
+                &[
+                    &params.semester_id,
+                    &params.subject_id,
+                    &schedule.room_id,
+                    &schedule.day_of_week,
+                    &schedule.start_time_of_day,
+                    &schedule.end_time_of_day,
+                ],
+            )
+            .await?;
+    }
+
+    for eligible_major in body.eligible_majors.iter() {
+        transaction
+            .execute(
+                &insert_eligible_major_statement,
+                &[
+                    &params.semester_id,
+                    &params.subject_id,
+                    &eligible_major.major_id,
+                    &eligible_major.academic_year_id,
+                ],
+            )
+            .await?;
+    }
+
+    let period = get_registration_periods(&transaction, &params.semester_id)
+        .await?
+        .and_then(|p| p.get_period_at(time::OffsetDateTime::now_utc()));
+
+    transaction.commit().await?;
+
+    // the opening subject is already committed, a failed promotion leaves the students in the
+    // waitlist.
+    if period.is_some_and(|p| p.is_enrollment_open()) {
+        if let Err(e) =
+            fill_seats_from_waitlist(&mut client, &params.semester_id, &params.subject_id).await
+        {
+            tracing::error!("cannot fill seats from the waitlist: {}", e);
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(DefaultSuccessResponse::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::time;
+
+    use crate::{
+        database::DayOfWeek,
+        errors::HttpError,
+        fixtures::{fresh_schema, Fixtures},
+        services::schedule_conflicts::ProposedSchedule,
+        startup::load_postgres_config,
+    };
+
+    use super::{
+        validate_opening_subject, OpeningSubjectEligibleMajor, PutOpeningSubjectRequestBody,
+        PutOpeningSubjectRequestParams,
+    };
+
+    fn body() -> PutOpeningSubjectRequestBody {
+        PutOpeningSubjectRequestBody {
+            grading_criteria_id: "grading-criteria-standard".to_string(),
+            subject_capacity: 60,
+            professor_ids: vec!["professor-malee".to_string()],
+            schedules: vec![ProposedSchedule {
+                room_id: "room-sc1-201".to_string(),
+                day_of_week: DayOfWeek::Tuesday,
+                start_time_of_day: time!(13:00),
+                end_time_of_day: time!(16:00),
+            }],
+            eligible_majors: vec![OpeningSubjectEligibleMajor {
+                major_id: "major-mathematics".to_string(),
+                academic_year_id: "academic-year-2022".to_string(),
+            }],
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_validate_opening_subject() {
+        let pool = fresh_schema(
+            &load_postgres_config(),
+            "test_validate_opening_subject",
+            &Fixtures::parse(include_str!("../../../../fixtures/demo.toml")).unwrap(),
+        )
+        .await
+        .unwrap();
+        let client = pool.get().await.unwrap();
+
+        let params = PutOpeningSubjectRequestParams {
+            semester_id: "semester-2022-1".to_string(),
+            subject_id: "subject-calculus".to_string(),
+        };
+
+        assert!(validate_opening_subject(&client, &params, &body())
+            .await
+            .is_ok());
+
+        let mut unknown_grading_criteria = body();
+        unknown_grading_criteria.grading_criteria_id = "grading-criteria-lenient".to_string();
+        assert!(matches!(
+            validate_opening_subject(&client, &params, &unknown_grading_criteria).await,
+            Err(HttpError::GradingCriteriaNotFound)
+        ));
+
+        let mut unknown_major = body();
+        unknown_major.eligible_majors[0].major_id = "major-physics".to_string();
+        assert!(matches!(
+            validate_opening_subject(&client, &params, &unknown_major).await,
+            Err(HttpError::InputValidationError)
+        ));
+
+        let mut unknown_academic_year = body();
+        unknown_academic_year.eligible_majors[0].academic_year_id =
+            "academic-year-1999".to_string();
+        assert!(matches!(
+            validate_opening_subject(&client, &params, &unknown_academic_year).await,
+            Err(HttpError::InputValidationError)
+        ));
+
+        // three students are enrolled in calculus.
+        let mut below_enrollments = body();
+        below_enrollments.subject_capacity = 2;
+        assert!(matches!(
+            validate_opening_subject(&client, &params, &below_enrollments).await,
+            Err(HttpError::InputValidationError)
+        ));
+    }
+}
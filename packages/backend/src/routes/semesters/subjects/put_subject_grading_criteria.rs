@@ -0,0 +1,135 @@
+use actix_web::{web, HttpResponse};
+use postgres_types::Type;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{requests::AuthenticationHeaders, responses::DefaultSuccessResponse},
+    errors::HttpError,
+    extractors::professors::AuthenticatedProfessorClaims,
+    services::{
+        grades::lock_grades, grading_criterias::get_grading_criteria_owner, professors::is_teaching,
+    },
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct PutSubjectGradingCriteriaRequestParams {
+    pub semester_id: String,
+    pub subject_id: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PutSubjectGradingCriteriaRequestBody {
+    pub grading_criteria_id: String,
+}
+
+/// Attaches one of the signed in professor's grading criterias to an opening subject the
+/// professor teaches. Criterias of other professors have to be cloned first.
+#[utoipa::path(
+    put,
+    path = "/semesters/{semester_id}/subjects/{subject_id}/grading-criteria",
+    tag = "semesters",
+    operation_id = "put_subject_grading_criteria",
+    params(AuthenticationHeaders, PutSubjectGradingCriteriaRequestParams),
+    request_body = PutSubjectGradingCriteriaRequestBody,
+    responses(
+        (
+            status = 200,
+            description = "grading criteria attached",
+            body = DefaultSuccessResponse,
+            example = json!(DefaultSuccessResponse::default())
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "professor does not teach the subject or does not own the grading criteria",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::NotTeaching.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "grading criteria or opening subject not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::GradingCriteriaNotFound.get_error_struct())
+        ),
+        (
+            status = 409,
+            description = "grades are already released",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::GradesAlreadyReleased.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<PutSubjectGradingCriteriaRequestParams>,
+    body: web::Json<PutSubjectGradingCriteriaRequestBody>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedProfessorClaims,
+) -> Result<HttpResponse, HttpError> {
+    let professor_id = claims.0.access_token.claims.uid.as_str();
+
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    if !is_teaching(
+        &transaction,
+        &params.semester_id,
+        &params.subject_id,
+        professor_id,
+    )
+    .await?
+    {
+        return Err(HttpError::NotTeaching);
+    }
+
+    if get_grading_criteria_owner(&transaction, &body.grading_criteria_id).await? != professor_id {
+        return Err(HttpError::Forbidden);
+    }
+
+    if lock_grades(&transaction, &params.semester_id, &params.subject_id).await? {
+        return Err(HttpError::GradesAlreadyReleased);
+    }
+
+    let statement = transaction
+        .prepare_typed_cached(
+            r##"
+            update opening_subjects_in_semester_subject_descriptions set
+                grading_criteria_id = $3
+            where semester_id = $1 and subject_id = $2
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let updated = transaction
+        .execute(
+            &statement,
+            &[
+                &params.semester_id,
+                &params.subject_id,
+                &body.grading_criteria_id,
+            ],
+        )
+        .await?;
+
+    if updated == 0 {
+        return Err(HttpError::OpeningSubjectNotFound);
+    }
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok().json(DefaultSuccessResponse::default()))
+}
@@ -0,0 +1,78 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::users::AuthenticatedUserClaims,
+    services::{degree_audits::get_degree_audit, students::check_student_access},
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct GetDegreeAuditRequestParams {
+    pub student_id: String,
+}
+
+/// Audit a student against the credit specifications of the student's major, with the credits
+/// earned, the credits in progress and the remaining subjects of every specification. Students
+/// could only audit themselves and professors could audit their advisees.
+#[utoipa::path(
+    get,
+    path = "/students/{student_id}/degree-audit",
+    tag = "students",
+    operation_id = "get_degree_audit",
+    params(AuthenticationHeaders, GetDegreeAuditRequestParams),
+    responses(
+        (
+            status = 200,
+            description = "successfully audit the student",
+            body = DegreeAudit,
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "not allowed to audit the student",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Forbidden.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "student not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::StudentNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<GetDegreeAuditRequestParams>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedUserClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    check_student_access(
+        &client,
+        &claims.0.access_token.claims.uid,
+        &claims.0.access_token.claims.rle,
+        &params.student_id,
+    )
+    .await?;
+
+    let audit = get_degree_audit(&client, &params.student_id).await?;
+
+    Ok(HttpResponse::Ok().json(audit))
+}
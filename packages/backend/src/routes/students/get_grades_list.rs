@@ -0,0 +1,114 @@
+use actix_web::{web, HttpResponse};
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders, errors::HttpError,
+    extractors::students::AuthenticatedStudentClaims, shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, IntoParams, ToSchema, TS)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct GetGradesListRequestQueries {
+    /// Only get grades in this semester.
+    #[ts(optional)]
+    pub semester_id: Option<String>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetGradesListResponseBody {
+    grades: Vec<GetGradesListResponseBodyInner>,
+}
+
+#[derive(Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct GetGradesListResponseBodyInner {
+    semester_id: String,
+    subject_id: String,
+    subject_name: String,
+    #[ts(type = "number")]
+    subject_credit: i32,
+    alphabet: String,
+}
+
+/// Get released grades of the signed in student, grades that are not released yet are hidden.
+#[utoipa::path(
+    get,
+    path = "/students/grades",
+    tag = "students",
+    operation_id = "get_grades_list",
+    params(AuthenticationHeaders, GetGradesListRequestQueries),
+    responses(
+        (
+            status = 200,
+            description = "successfully get list of grades",
+            body = GetGradesListResponseBody,
+            example = json!({ "grades": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    query: web::Query<GetGradesListRequestQueries>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedStudentClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_grades.semester_id,
+                student_grades.subject_id,
+                subjects.subject_name,
+                subjects.subject_credit,
+                coalesce(
+                    student_grades.student_grade_overridden_alphabet,
+                    student_grades.student_grade_computed_alphabet
+                ) as alphabet
+            from student_grades
+            inner join opening_subjects_in_semester_subject_descriptions on
+                student_grades.semester_id = opening_subjects_in_semester_subject_descriptions.semester_id and
+                student_grades.subject_id = opening_subjects_in_semester_subject_descriptions.subject_id
+            inner join subjects on student_grades.subject_id = subjects.subject_id
+            where
+                student_grades.student_id = $1 and
+                opening_subjects_in_semester_subject_descriptions.is_grade_released and
+                ($2::text is null or student_grades.semester_id = $2)
+            order by
+                student_grades.semester_id asc,
+                student_grades.subject_id asc
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let grades = client
+        .query(
+            &statement,
+            &[&claims.0.access_token.claims.uid, &query.semester_id],
+        )
+        .await?
+        .iter()
+        .map(|g| GetGradesListResponseBodyInner::try_from(g))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(HttpResponse::Ok().json(GetGradesListResponseBody { grades }))
+}
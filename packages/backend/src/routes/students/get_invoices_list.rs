@@ -0,0 +1,100 @@
+use actix_web::{web, HttpResponse};
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    constants::requests::AuthenticationHeaders, errors::HttpError,
+    extractors::students::AuthenticatedStudentClaims, shared_app_data::SharedAppData,
+};
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetInvoicesListResponseBody {
+    invoices: Vec<GetInvoicesListResponseBodyInner>,
+}
+
+#[derive(Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct GetInvoicesListResponseBodyInner {
+    id: String,
+    semester_id: String,
+    #[schema(value_type = String, example = json!("7500.00"))]
+    #[ts(type = "string")]
+    price: Decimal,
+    is_paid: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    #[ts(type = "string")]
+    created_timestamp: time::OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    #[ts(optional, type = "string")]
+    paid_timestamp: Option<time::OffsetDateTime>,
+}
+
+/// Get every invoice of the signed in student, latest first. Unpaid invoices are kept up to date
+/// with the student's enrollments.
+#[utoipa::path(
+    get,
+    path = "/students/invoices",
+    tag = "students",
+    operation_id = "get_invoices_list",
+    params(AuthenticationHeaders),
+    responses(
+        (
+            status = 200,
+            description = "successfully get list of invoices",
+            body = GetInvoicesListResponseBody,
+            example = json!({ "invoices": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedStudentClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_transaction_id as id,
+                semester_id,
+                student_transaction_price as price,
+                student_transaction_is_transaction_successful as is_paid,
+                student_transaction_created_timestamp as created_timestamp,
+                student_transaction_finished_timestamp as paid_timestamp
+            from student_transactions
+            where student_id = $1
+            order by student_transaction_created_timestamp desc
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    let invoices = client
+        .query(&statement, &[&claims.0.access_token.claims.uid])
+        .await?;
+
+    let invoices = invoices
+        .iter()
+        .map(|i| GetInvoicesListResponseBodyInner::try_from(i))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(HttpResponse::Ok().json(GetInvoicesListResponseBody { invoices }))
+}
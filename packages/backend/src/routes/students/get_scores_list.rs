@@ -0,0 +1,124 @@
+use actix_web::{web, HttpResponse};
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders, errors::HttpError,
+    extractors::students::AuthenticatedStudentClaims, shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, IntoParams, ToSchema, TS)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct GetScoresListRequestQueries {
+    /// Only get scores in this semester.
+    #[ts(optional)]
+    pub semester_id: Option<String>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetScoresListResponseBody {
+    scores: Vec<GetScoresListResponseBodyInner>,
+}
+
+#[derive(Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct GetScoresListResponseBodyInner {
+    semester_id: String,
+    subject_id: String,
+    subject_name: String,
+    assignment_id: String,
+    assignment_name: String,
+    #[schema(value_type = String, example = json!("10.00"))]
+    #[ts(type = "string")]
+    full_score: Decimal,
+    /// Not exists when the score is not entered yet.
+    #[schema(value_type = Option<String>, example = json!("8.50"))]
+    #[ts(optional, type = "string")]
+    score: Option<Decimal>,
+}
+
+/// Get scores of the signed in student in every assignment of the subjects the student is enrolled
+/// in.
+#[utoipa::path(
+    get,
+    path = "/students/scores",
+    tag = "students",
+    operation_id = "get_scores_list",
+    params(AuthenticationHeaders, GetScoresListRequestQueries),
+    responses(
+        (
+            status = 200,
+            description = "successfully get list of scores",
+            body = GetScoresListResponseBody,
+            example = json!({ "scores": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    query: web::Query<GetScoresListRequestQueries>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedStudentClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_assignments.semester_id,
+                student_assignments.subject_id,
+                subjects.subject_name,
+                student_assignments.student_assignment_id as assignment_id,
+                student_assignments.student_assignment_name as assignment_name,
+                student_assignments.student_assignment_full_score as full_score,
+                student_scores.student_score as score
+            from student_enrollments
+            inner join student_assignments on
+                student_enrollments.semester_id = student_assignments.semester_id and
+                student_enrollments.subject_id = student_assignments.subject_id
+            inner join subjects on student_assignments.subject_id = subjects.subject_id
+            left join student_scores on
+                student_assignments.student_assignment_id = student_scores.assignment_id and
+                student_enrollments.student_id = student_scores.student_id
+            where
+                student_enrollments.student_id = $1 and
+                ($2::text is null or student_enrollments.semester_id = $2)
+            order by
+                student_assignments.semester_id asc,
+                student_assignments.subject_id asc,
+                student_assignments.student_assignment_name asc
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let scores = client
+        .query(
+            &statement,
+            &[&claims.0.access_token.claims.uid, &query.semester_id],
+        )
+        .await?
+        .iter()
+        .map(|s| GetScoresListResponseBodyInner::try_from(s))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(HttpResponse::Ok().json(GetScoresListResponseBody { scores }))
+}
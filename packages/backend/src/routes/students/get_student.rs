@@ -0,0 +1,79 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::users::AuthenticatedUserClaims,
+    routes::students::get_student_profile::StudentNameLanguageRequestQueries,
+    services::students::{check_student_access, get_student_profile},
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct GetStudentRequestParams {
+    pub student_id: String,
+}
+
+/// Get the profile of a student. Professors could only get profiles of their advisees.
+#[utoipa::path(
+    get,
+    path = "/students/{student_id}/profile",
+    tag = "students",
+    operation_id = "get_student",
+    params(AuthenticationHeaders, GetStudentRequestParams, StudentNameLanguageRequestQueries),
+    responses(
+        (
+            status = 200,
+            description = "successfully get the profile",
+            body = StudentProfile,
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "not allowed to see the student",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Forbidden.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "student not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::StudentNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<GetStudentRequestParams>,
+    query: web::Query<StudentNameLanguageRequestQueries>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedUserClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    check_student_access(
+        &client,
+        &claims.0.access_token.claims.uid,
+        &claims.0.access_token.claims.rle,
+        &params.student_id,
+    )
+    .await?;
+
+    let profile =
+        get_student_profile(&client, &params.student_id, query.language.as_deref()).await?;
+
+    Ok(HttpResponse::Ok().json(profile))
+}
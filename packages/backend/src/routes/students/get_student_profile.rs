@@ -0,0 +1,75 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders, errors::HttpError,
+    extractors::students::AuthenticatedStudentClaims, services::students::get_student_profile,
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, IntoParams, ToSchema, TS)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct StudentNameLanguageRequestQueries {
+    /// ISO 639-1 code of the language names should be shown in, falls back to english and then to
+    /// any available language.
+    #[param(example = json!("th"))]
+    #[serde(
+        default,
+        deserialize_with = "crate::constants::requests::empty_string_as_none"
+    )]
+    #[ts(optional)]
+    pub language: Option<String>,
+}
+
+/// Get the profile of the signed in student.
+#[utoipa::path(
+    get,
+    path = "/students/profile",
+    tag = "students",
+    operation_id = "get_student_profile",
+    params(AuthenticationHeaders, StudentNameLanguageRequestQueries),
+    responses(
+        (
+            status = 200,
+            description = "successfully get the profile",
+            body = StudentProfile,
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "student not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::StudentNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    query: web::Query<StudentNameLanguageRequestQueries>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedStudentClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    let profile = get_student_profile(
+        &client,
+        &claims.0.access_token.claims.uid,
+        query.language.as_deref(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(profile))
+}
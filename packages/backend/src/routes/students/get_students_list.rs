@@ -0,0 +1,213 @@
+use actix_web::{web, HttpResponse};
+use postgres_types::Type;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{
+        requests::{AuthenticationHeaders, SqlRange},
+        DEFAULT_PAGE, DEFAULT_PAGE_SIZE,
+    },
+    database::Role,
+    errors::HttpError,
+    extractors::users::AuthenticatedUserClaims,
+    services::{
+        names::{pick_name, PersonName},
+        students::get_student_names,
+    },
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, IntoParams, ToSchema, TS)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct GetStudentsListRequestQueries {
+    /// Either the beginning of a `student_representative_id` or a full text search on names of
+    /// students in every language.
+    #[serde(
+        default,
+        deserialize_with = "crate::constants::requests::empty_string_as_none"
+    )]
+    #[ts(optional)]
+    pub search: Option<String>,
+    /// ISO 639-1 code of the language names should be shown in.
+    #[serde(
+        default,
+        deserialize_with = "crate::constants::requests::empty_string_as_none"
+    )]
+    #[ts(optional)]
+    pub language: Option<String>,
+    /// page of the queried data. If less than `1`, will default to `1`
+    #[param(minimum = 1, default = json!(DEFAULT_PAGE))]
+    #[serde(
+        default,
+        deserialize_with = "crate::constants::requests::deserialize_page"
+    )]
+    #[ts(optional)]
+    pub page: Option<i32>,
+    /// size of page for each query. will use default page size if it is out of bounds.
+    #[param(minimum = 1, maximum = 100, default = json!(DEFAULT_PAGE_SIZE))]
+    #[serde(
+        default,
+        deserialize_with = "crate::constants::requests::deserialize_page_size"
+    )]
+    #[ts(optional)]
+    pub page_size: Option<i32>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetStudentsListResponseBody {
+    students: Vec<GetStudentsListResponseBodyInner>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetStudentsListResponseBodyInner {
+    student_id: String,
+    student_representative_id: String,
+    major_id: String,
+    #[ts(optional)]
+    name: Option<PersonName>,
+}
+
+/// Look students up by `student_representative_id` or name. Admins could look up every student,
+/// professors could only look up their advisees.
+#[utoipa::path(
+    get,
+    path = "/students",
+    tag = "students",
+    operation_id = "get_students_list",
+    params(AuthenticationHeaders, GetStudentsListRequestQueries),
+    responses(
+        (
+            status = 200,
+            description = "successfully get list of students",
+            body = GetStudentsListResponseBody,
+            example = json!({ "students": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "students could not look up other students",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Forbidden.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    query: web::Query<GetStudentsListRequestQueries>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedUserClaims,
+) -> Result<HttpResponse, HttpError> {
+    let token_claims = &claims.0.access_token.claims;
+
+    let professor_id = match token_claims.rle {
+        Role::Admin => None,
+        Role::Professor => Some(token_claims.uid.clone()),
+        Role::Student => return Err(HttpError::Forbidden),
+    };
+
+    let SqlRange { limit, offset } = SqlRange::from_page(
+        query.page.unwrap_or(DEFAULT_PAGE),
+        query.page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+    )?;
+
+    let client = data.pool.get().await?;
+
+    let rows = match query.search {
+        Some(ref search) => {
+            let statement = client
+                .prepare_typed_cached(
+                    r##"
+                    select
+                        students.student_id,
+                        students.student_representative_id,
+                        students.major_id
+                    from students
+                    where
+                        ($2::text is null or students.professor_id = $2) and (
+                            starts_with(students.student_representative_id, $1) or
+                            exists (
+                                select 1
+                                from student_names
+                                where
+                                    student_names.student_id = students.student_id and (
+                                        student_names.student_first_name &@~ $1 or
+                                        student_names.student_middle_name &@~ $1 or
+                                        student_names.student_last_name &@~ $1
+                                    )
+                            )
+                        )
+                    order by students.student_representative_id asc
+                    limit $3
+                    offset $4
+                    "##,
+                    &[Type::TEXT, Type::TEXT, Type::INT4, Type::INT4],
+                )
+                .await?;
+
+            client
+                .query(&statement, &[search, &professor_id, &limit, &offset])
+                .await?
+        }
+        None => {
+            let statement = client
+                .prepare_typed_cached(
+                    r##"
+                    select
+                        student_id,
+                        student_representative_id,
+                        major_id
+                    from students
+                    where $1::text is null or professor_id = $1
+                    order by student_representative_id asc
+                    limit $2
+                    offset $3
+                    "##,
+                    &[Type::TEXT, Type::INT4, Type::INT4],
+                )
+                .await?;
+
+            client
+                .query(&statement, &[&professor_id, &limit, &offset])
+                .await?
+        }
+    };
+
+    let student_ids = rows
+        .iter()
+        .map(|r| r.try_get::<&str, String>("student_id"))
+        .collect::<Result<Vec<_>, _>>()?;
+    let names = get_student_names(&client, &student_ids).await?;
+
+    let students = rows
+        .iter()
+        .map(|r| {
+            let student_id = r.try_get::<&str, String>("student_id")?;
+
+            Ok(GetStudentsListResponseBodyInner {
+                name: names
+                    .get(&student_id)
+                    .and_then(|n| pick_name(n, query.language.as_deref())),
+                student_id,
+                student_representative_id: r.try_get("student_representative_id")?,
+                major_id: r.try_get("major_id")?,
+            })
+        })
+        .collect::<Result<Vec<_>, HttpError>>()?;
+
+    Ok(HttpResponse::Ok().json(GetStudentsListResponseBody { students }))
+}
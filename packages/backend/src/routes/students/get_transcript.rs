@@ -0,0 +1,77 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::users::AuthenticatedUserClaims,
+    services::{students::check_student_access, transcripts::get_transcript},
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct TranscriptRequestParams {
+    pub student_id: String,
+}
+
+/// Get the transcript of a student with the gpa of every semester and the gpax. Students could
+/// only get their own transcripts and professors could get transcripts of their advisees.
+#[utoipa::path(
+    get,
+    path = "/students/{student_id}/transcript",
+    tag = "students",
+    operation_id = "get_transcript",
+    params(AuthenticationHeaders, TranscriptRequestParams),
+    responses(
+        (
+            status = 200,
+            description = "successfully get the transcript",
+            body = Transcript,
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "not allowed to see the transcript",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Forbidden.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "student not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::StudentNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<TranscriptRequestParams>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedUserClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    check_student_access(
+        &client,
+        &claims.0.access_token.claims.uid,
+        &claims.0.access_token.claims.rle,
+        &params.student_id,
+    )
+    .await?;
+
+    let transcript = get_transcript(&client, &params.student_id).await?;
+
+    Ok(HttpResponse::Ok().json(transcript))
+}
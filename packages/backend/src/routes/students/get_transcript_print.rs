@@ -0,0 +1,76 @@
+use actix_web::{web, HttpResponse};
+
+use crate::{
+    constants::requests::AuthenticationHeaders,
+    errors::HttpError,
+    extractors::users::AuthenticatedUserClaims,
+    routes::students::get_transcript::TranscriptRequestParams,
+    services::{
+        students::check_student_access,
+        transcripts::{get_transcript, render_transcript_html},
+    },
+    shared_app_data::SharedAppData,
+};
+
+/// Get the transcript of a student as a printable HTML document for official documents, the same
+/// rules as [get_transcript](crate::routes::students::get_transcript::handler) apply.
+#[utoipa::path(
+    get,
+    path = "/students/{student_id}/transcript/print",
+    tag = "students",
+    operation_id = "get_transcript_print",
+    params(AuthenticationHeaders, TranscriptRequestParams),
+    responses(
+        (
+            status = 200,
+            description = "printable transcript",
+            content_type = "text/html",
+            body = String,
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 403,
+            description = "not allowed to see the transcript",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Forbidden.get_error_struct())
+        ),
+        (
+            status = 404,
+            description = "student not found",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::StudentNotFound.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    params: web::Path<TranscriptRequestParams>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedUserClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    check_student_access(
+        &client,
+        &claims.0.access_token.claims.uid,
+        &claims.0.access_token.claims.rle,
+        &params.student_id,
+    )
+    .await?;
+
+    let transcript = get_transcript(&client, &params.student_id).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(render_transcript_html(&transcript)))
+}
@@ -0,0 +1,108 @@
+use actix_web::{web, HttpResponse};
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use serde::Serialize;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{
+    constants::requests::AuthenticationHeaders, errors::HttpError,
+    extractors::students::AuthenticatedStudentClaims, shared_app_data::SharedAppData,
+};
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetWaitlistsListResponseBody {
+    waitlists: Vec<GetWaitlistsListResponseBodyInner>,
+}
+
+#[derive(Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct GetWaitlistsListResponseBodyInner {
+    semester_id: String,
+    subject_id: String,
+    subject_name: String,
+    /// 1-indexed position in the waitlist of the subject.
+    #[ts(type = "number")]
+    waitlist_position: i64,
+    #[serde(with = "time::serde::rfc3339")]
+    #[ts(type = "string")]
+    created_timestamp: time::OffsetDateTime,
+}
+
+/// Get every waitlist that the signed in student is waiting in, with the position in each of them.
+#[utoipa::path(
+    get,
+    path = "/students/waitlists",
+    tag = "students",
+    operation_id = "get_waitlists_list",
+    params(AuthenticationHeaders),
+    responses(
+        (
+            status = 200,
+            description = "successfully get list of waitlists",
+            body = GetWaitlistsListResponseBody,
+            example = json!({ "waitlists": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedStudentClaims,
+) -> Result<HttpResponse, HttpError> {
+    let client = data.pool.get().await?;
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                waitlist.semester_id,
+                waitlist.subject_id,
+                subjects.subject_name,
+                waitlist.waitlist_position,
+                waitlist.created_timestamp
+            from (
+                select
+                    semester_id,
+                    subject_id,
+                    student_id,
+                    student_enrollment_waitlist_created_timestamp as created_timestamp,
+                    row_number() over (
+                        partition by semester_id, subject_id
+                        order by
+                            student_enrollment_waitlist_created_timestamp asc,
+                            student_id asc
+                    ) as waitlist_position
+                from student_enrollment_waitlists
+            ) as waitlist
+            inner join subjects on waitlist.subject_id = subjects.subject_id
+            where waitlist.student_id = $1
+            order by waitlist.created_timestamp asc
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    let waitlists = client
+        .query(&statement, &[&claims.0.access_token.claims.uid])
+        .await?;
+
+    let waitlists = waitlists
+        .iter()
+        .map(|w| GetWaitlistsListResponseBodyInner::try_from(w))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(HttpResponse::Ok().json(GetWaitlistsListResponseBody { waitlists }))
+}
@@ -1 +1,12 @@
+pub mod get_degree_audit;
+pub mod get_grades_list;
+pub mod get_invoices_list;
+pub mod get_scores_list;
+pub mod get_student;
+pub mod get_student_profile;
+pub mod get_students_list;
+pub mod get_transcript;
+pub mod get_transcript_print;
+pub mod get_waitlists_list;
+pub mod post_signup_import;
 pub mod signup;
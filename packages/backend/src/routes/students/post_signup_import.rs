@@ -0,0 +1,257 @@
+use actix_multipart::Multipart;
+use actix_web::{web, HttpResponse};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{
+        create_argon2_context, requests::AuthenticationHeaders, ARGON2_PEPPER_STRING,
+        MAX_SIGNUP_IMPORT_FILE_SIZE,
+    },
+    errors::HttpError,
+    extractors::admins::AuthenticatedAdminClaims,
+    services::{
+        signup_imports::{
+            drop_conflicting_emails, parse_rows, read_csv, read_xlsx, ImportFormat, ImportRowError,
+        },
+        signups::{
+            find_taken_emails, generate_representative_ids, generate_student_email,
+            get_signup_context, signup_students, SignupTarget,
+        },
+    },
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, IntoParams, ToSchema, TS)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct PostSignupImportRequestQueries {
+    /// Only validate the file and show the ids that would be generated, nothing is signed up.
+    #[ts(optional)]
+    pub dry_run: Option<bool>,
+}
+
+/// Multipart form of the import, the columns of the file are the fields of
+/// `StudentSignupRequestBodyInner` and the first row is the header.
+#[derive(ToSchema)]
+#[allow(dead_code)]
+pub struct PostSignupImportRequestBody {
+    /// A `.csv` file, or a `.xlsx` file whose first worksheet is read.
+    #[schema(value_type = String, format = Binary)]
+    file: Vec<u8>,
+    major_id: String,
+    #[schema(example = json!("31"))]
+    major_representative_id: String,
+    professor_id: String,
+    first_academic_year_id: String,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct PostSignupImportResponseBody {
+    dry_run: bool,
+    /// Students that are signed up, or would be signed up in a dry run.
+    students: Vec<PostSignupImportResponseBodyInner>,
+    /// Rows that are skipped.
+    errors: Vec<ImportRowError>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct PostSignupImportResponseBodyInner {
+    #[ts(type = "number")]
+    row: usize,
+    student_representative_id: String,
+}
+
+#[derive(Default)]
+struct ImportForm {
+    file: Option<(ImportFormat, Vec<u8>)>,
+    major_id: Option<String>,
+    major_representative_id: Option<String>,
+    professor_id: Option<String>,
+    first_academic_year_id: Option<String>,
+}
+
+fn field_text(content: Vec<u8>) -> Result<String, HttpError> {
+    String::from_utf8(content)
+        .map(|t| t.trim().to_string())
+        .map_err(|_| HttpError::InputValidationError)
+}
+
+async fn read_form(mut payload: Multipart) -> Result<ImportForm, HttpError> {
+    let mut form = ImportForm::default();
+
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|_| HttpError::InputValidationError)?;
+        let name = field.name().unwrap_or_default().to_string();
+        let file_name = field
+            .content_disposition()
+            .and_then(|c| c.get_filename())
+            .map(|f| f.to_string());
+
+        let mut content = vec![];
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|_| HttpError::InputValidationError)?;
+
+            if content.len() + chunk.len() > MAX_SIGNUP_IMPORT_FILE_SIZE {
+                return Err(HttpError::ImportFileTooLarge);
+            }
+            content.extend_from_slice(&chunk);
+        }
+
+        match name.as_str() {
+            "file" => {
+                let format = file_name
+                    .as_deref()
+                    .and_then(ImportFormat::from_file_name)
+                    .ok_or(HttpError::UnsupportedImportFile)?;
+                form.file = Some((format, content));
+            }
+            "major_id" => form.major_id = Some(field_text(content)?),
+            "major_representative_id" => form.major_representative_id = Some(field_text(content)?),
+            "professor_id" => form.professor_id = Some(field_text(content)?),
+            "first_academic_year_id" => form.first_academic_year_id = Some(field_text(content)?),
+            _ => {}
+        }
+    }
+
+    Ok(form)
+}
+
+/// Bulk signup students from a csv or xlsx file. Every row is validated on its own, valid rows are
+/// signed up together and invalid rows are reported with their row numbers instead of failing the
+/// whole file. Rows whose generated email is taken by an existing user or by an earlier row are
+/// reported the same way. With `dry_run`, nothing is signed up and the response shows the
+/// `student_representative_id` each valid row would get, which could still change if another
+/// signup of the same major and year happens first.
+#[utoipa::path(
+    post,
+    path = "/students/signup/import",
+    tag = "students",
+    operation_id = "post_signup_import",
+    params(AuthenticationHeaders, PostSignupImportRequestQueries),
+    request_body(content = PostSignupImportRequestBody, content_type = "multipart/form-data"),
+    responses(
+        (
+            status = 200,
+            description = "rows that are signed up and rows that are skipped",
+            body = PostSignupImportResponseBody,
+        ),
+        (
+            status = 400,
+            description = "input errors, such as missing form fields or a file that could not be read",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InputValidationError.get_error_struct())
+        ),
+        (
+            status = 401,
+            description = "unauthorized error",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 413,
+            description = "file is too large",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::ImportFileTooLarge.get_error_struct())
+        ),
+        (
+            status = 415,
+            description = "file is not a csv or xlsx file",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::UnsupportedImportFile.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    payload: Multipart,
+    query: web::Query<PostSignupImportRequestQueries>,
+    data: web::Data<SharedAppData>,
+    _claims: AuthenticatedAdminClaims,
+) -> Result<HttpResponse, HttpError> {
+    let dry_run = query.dry_run.unwrap_or(false);
+    let form = read_form(payload).await?;
+
+    let (format, file) = form.file.ok_or(HttpError::InputValidationError)?;
+    let (major_id, major_representative_id, professor_id, first_academic_year_id) = match (
+        form.major_id,
+        form.major_representative_id,
+        form.professor_id,
+        form.first_academic_year_id,
+    ) {
+        (Some(a), Some(b), Some(c), Some(d)) => (a, b, c, d),
+        _ => return Err(HttpError::InputValidationError),
+    };
+
+    let (headers, records) = match format {
+        ImportFormat::Csv => read_csv(&file)?,
+        ImportFormat::Xlsx => read_xlsx(&file)?,
+    };
+
+    let (rows, mut errors) = parse_rows(&headers, &records, time::OffsetDateTime::now_utc().date());
+
+    let target = SignupTarget {
+        major_id: &major_id,
+        major_representative_id: &major_representative_id,
+        professor_id: &professor_id,
+        first_academic_year_id: &first_academic_year_id,
+    };
+
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    let context = get_signup_context(&transaction, &target).await?;
+
+    // emails are unique, rows whose email is taken are reported instead of failing the import.
+    let emails = rows
+        .iter()
+        .map(|r| generate_student_email(&r.student, context.first_academic_year_be))
+        .collect::<Vec<_>>();
+    let taken_emails = find_taken_emails(&transaction, &emails).await?;
+    let rows = drop_conflicting_emails(rows, &emails, &taken_emails, &mut errors);
+
+    let representative_ids = if dry_run {
+        generate_representative_ids(
+            context.first_academic_year_be,
+            target.major_representative_id,
+            context.latest_student_number + 1,
+            rows.len(),
+        )?
+    } else {
+        let argon2_context = create_argon2_context(&ARGON2_PEPPER_STRING)?;
+        let students = rows.iter().map(|r| &r.student).collect::<Vec<_>>();
+
+        let representative_ids =
+            signup_students(&transaction, &argon2_context, &target, &context, &students).await?;
+
+        transaction.commit().await?;
+
+        representative_ids
+    };
+
+    let students = rows
+        .iter()
+        .zip(representative_ids)
+        .map(
+            |(row, student_representative_id)| PostSignupImportResponseBodyInner {
+                row: row.row,
+                student_representative_id,
+            },
+        )
+        .collect();
+
+    Ok(HttpResponse::Ok().json(PostSignupImportResponseBody {
+        dry_run,
+        students,
+        errors,
+    }))
+}
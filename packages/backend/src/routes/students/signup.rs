@@ -1,55 +1,36 @@
 use actix_web::{web, HttpResponse};
-use argon2::{password_hash::SaltString, PasswordHasher};
-use rand_core::OsRng;
-use rust_decimal::Decimal;
 use serde::Deserialize;
-use tokio_postgres::types::Type;
 use utoipa::ToSchema;
 
 use crate::{
     constants::{
         create_argon2_context, requests::AuthenticationHeaders, responses::DefaultSuccessResponse,
-        AD_BE_YEAR_DIFFERENCE, ARGON2_PEPPER_STRING, ID_LENGTH,
+        ARGON2_PEPPER_STRING,
     },
-    database::Role,
     errors::HttpError,
     extractors::admins::AuthenticatedAdminClaims,
+    services::signups::{
+        get_signup_context, signup_students, validate_student, SignupTarget,
+        StudentSignupRequestBodyInner,
+    },
     shared_app_data::SharedAppData,
 };
 
 #[derive(Deserialize, ToSchema)]
 pub struct StudentSignupRequestBody {
-    students: Vec<StudentSignupRequestBodyInner>,
-    major_id: String,
+    pub students: Vec<StudentSignupRequestBodyInner>,
+    pub major_id: String,
     #[schema(example = json!("31"))]
-    major_representative_id: String,
-    professor_id: String,
+    pub major_representative_id: String,
+    pub professor_id: String,
     /// academic year id of student that will be recognized.
-    first_academic_year_id: String,
-}
-
-#[derive(Deserialize, ToSchema)]
-pub struct StudentSignupRequestBodyInner {
-    #[schema(example = json!("8365079019452"))]
-    student_nid: String,
-    student_english_first_name: String,
-    student_english_middle_name: String,
-    student_english_last_name: String,
-    #[schema(value_type = String, format = Date)]
-    #[serde(with = "time::Date")]
-    student_birthdate: time::Date,
-    student_previous_school_name: String,
-    #[serde(with = "rust_decimal::serde::float")]
-    #[schema(value_type = f32, example = json!(3.99))]
-    student_previous_school_gpa: Decimal,
-}
-
-#[derive(Deserialize, ger_from_row::FromRow)]
-struct LatestStudentIndex {
-    student_representative_id: String,
+    pub first_academic_year_id: String,
 }
 
-/// Bulk signup students either given from a csv file or some admission website.
+/// Bulk signup students either given from some admission website or read from a file by
+/// [post_signup_import](crate::routes::students::post_signup_import::handler). Nothing is signed up
+/// when any student is invalid or
+/// could not be inserted.
 #[utoipa::path(
     post,
     path = "/students/signup",
@@ -93,222 +74,34 @@ pub async fn handler(
         return Err(HttpError::NoData);
     }
 
-    let mut client = data.pool.get().await?;
-
-    let get_numeric_first_academic_year_statement = client
-        .prepare_typed(
-            r##"
-            select
-                academic_year_id,
-                academic_year_anno_domini_year
-            from academic_years
-            where academic_year_id = $1
-            "##,
-            &[Type::TEXT],
-        )
-        .await?;
-
-    let first_academic_year = client
-        .query_one(
-            &get_numeric_first_academic_year_statement,
-            &[&body.first_academic_year_id],
-        )
-        .await?;
-    let first_academic_year = first_academic_year
-        .try_get::<usize, i32>(1usize)
-        .map_err(|_| HttpError::InternalServerError {
-            cause: "row \"first_academic_year\" cannot be accessed".to_string(),
-        })?;
-
-    let first_academic_year_bhuddist_era_year = first_academic_year + AD_BE_YEAR_DIFFERENCE as i32;
+    let today = time::OffsetDateTime::now_utc().date();
 
-    let insert_user_statement = client
-        .prepare_typed_cached(
-            r##"
-            insert into users (
-                user_id,
-                user_username,
-                user_email,
-                user_password,
-                user_role,
-                user_birthdate
-            ) values (
-                $1,
-                $2,
-                $3,
-                $4,
-                $5,
-                $6
-            )"##,
-            &[
-                Type::TEXT,
-                Type::TEXT,
-                Type::TEXT,
-                Type::TEXT,
-                Type::TEXT,
-                Type::DATE,
-            ],
-        )
-        .await?;
-
-    let insert_student_statement = client
-        .prepare_typed_cached(
-            r##"
-            insert into students (
-                student_id,
-                student_representative_id,
-                student_nid,
-                student_previous_school_name,
-                student_previous_school_gpa,
-                major_id,
-                professor_id,
-                first_academic_year_id
-            ) values (
-                $1,
-                $2,
-                $3,
-                $4,
-                $5,
-                $6,
-                $7,
-                $8
-            )"##,
-            &[
-                Type::TEXT,
-                Type::TEXT,
-                Type::TEXT,
-                Type::TEXT,
-                Type::NUMERIC,
-                Type::TEXT,
-                Type::TEXT,
-                Type::TEXT,
-            ],
-        )
-        .await?;
+    if body
+        .students
+        .iter()
+        .any(|s| !validate_student(s, today).is_empty())
+    {
+        return Err(HttpError::InputValidationError);
+    }
 
-    let insert_student_name_statement = client
-        .prepare_typed_cached(
-            r##"
-            insert into student_names (
-                student_name_id,
-                student_id,
-                student_name_language,
-                student_firstname,
-                student_middlename,
-                student_lastname
-            ) values (
-                $1,
-                $2,
-                $3,
-                $4,
-                $5,
-                $6
-            )"##,
-            &[
-                Type::TEXT,
-                Type::TEXT,
-                Type::TEXT,
-                Type::TEXT,
-                Type::TEXT,
-                Type::TEXT,
-            ],
-        )
-        .await?;
+    let target = SignupTarget {
+        major_id: &body.major_id,
+        major_representative_id: &body.major_representative_id,
+        professor_id: &body.professor_id,
+        first_academic_year_id: &body.first_academic_year_id,
+    };
 
     let argon2_context = create_argon2_context(&ARGON2_PEPPER_STRING)?;
 
-    let latest_student_index = client
-        .query_one(
-            r##"
-            select
-                student_representative_id
-            from students
-            where major_id = $1 and first_academic_year_id = $2"##,
-            &[&body.major_id, &body.first_academic_year_id],
-        )
-        .await?;
-    let latest_student_index = LatestStudentIndex::try_from(latest_student_index)?;
-    let latest_student_number = latest_student_index.student_representative_id[4..]
-        .parse::<usize>()
-        .unwrap_or(0usize);
-
-    for (i, student) in body.students.iter().enumerate() {
-        let transaction = client.transaction().await?;
-
-        let new_student_id = randoid::randoid!(ID_LENGTH);
-        let new_student_email = format!(
-            "{}{}{}@gmail.com",
-            &student.student_english_first_name,
-            &student
-                .student_english_last_name
-                .as_str()
-                .chars()
-                .nth(0)
-                .unwrap_or('s')
-                .to_string(),
-            &first_academic_year_bhuddist_era_year,
-        );
-        let new_student_index = latest_student_number + i;
-
-        if new_student_index == 10000 {
-            tracing::error!("new student_index overflow to {}", new_student_index);
-            return Err(HttpError::InternalServerError {
-                cause: "new student_index integer overflow to 10000".to_string(),
-            });
-        }
+    let mut client = data.pool.get().await?;
+    let transaction = client.transaction().await?;
 
-        let new_student_representative_id = format!(
-            "{}{}{:0>4}",
-            first_academic_year_bhuddist_era_year, body.major_representative_id, new_student_index
-        );
-        let salt = SaltString::generate(&mut OsRng);
-        let new_student_account_password =
-            argon2_context.hash_password(&new_student_representative_id.as_bytes(), &salt)?;
+    let context = get_signup_context(&transaction, &target).await?;
+    let students = body.students.iter().collect::<Vec<_>>();
 
-        transaction
-            .execute(
-                &insert_user_statement,
-                &[
-                    &new_student_id,
-                    &randoid::randoid!(10),
-                    &new_student_email,
-                    &new_student_account_password.to_string(),
-                    &Role::Student,
-                    &student.student_birthdate,
-                ],
-            )
-            .await?;
-        transaction
-            .execute(
-                &insert_student_statement,
-                &[
-                    &new_student_id,
-                    &new_student_representative_id,
-                    &student.student_nid,
-                    &student.student_previous_school_name,
-                    &student.student_previous_school_gpa,
-                    &body.major_id,
-                    &body.professor_id,
-                    &body.first_academic_year_id,
-                ],
-            )
-            .await?;
-        transaction
-            .execute(
-                &insert_student_name_statement,
-                &[
-                    &randoid::randoid!(ID_LENGTH),
-                    &new_student_id,
-                    &"EN",
-                    &student.student_english_first_name.trim(),
-                    &student.student_english_middle_name.trim(),
-                    &student.student_english_last_name.trim(),
-                ],
-            )
-            .await?;
+    signup_students(&transaction, &argon2_context, &target, &context, &students).await?;
 
-        transaction.commit().await?;
-    }
+    transaction.commit().await?;
 
     Ok(HttpResponse::Ok().json(DefaultSuccessResponse::default()))
 }
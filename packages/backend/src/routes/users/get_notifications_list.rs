@@ -0,0 +1,119 @@
+use actix_web::{web, HttpResponse};
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    constants::{
+        requests::{AuthenticationHeaders, SqlRange},
+        DEFAULT_PAGE, DEFAULT_PAGE_SIZE,
+    },
+    errors::HttpError,
+    extractors::users::AuthenticatedUserClaims,
+    shared_app_data::SharedAppData,
+};
+
+#[derive(Deserialize, IntoParams, ToSchema, TS)]
+#[ts(export)]
+#[into_params(parameter_in = Query)]
+pub struct GetNotificationsListRequestQueries {
+    #[param(minimum = 1, default = json!(DEFAULT_PAGE))]
+    #[serde(deserialize_with = "crate::constants::requests::deserialize_page")]
+    #[ts(optional)]
+    pub page: Option<i32>,
+    #[param(minimum = 1, maximum = 100, default = json!(DEFAULT_PAGE_SIZE))]
+    #[serde(deserialize_with = "crate::constants::requests::deserialize_page_size")]
+    #[ts(optional)]
+    pub page_size: Option<i32>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct GetNotificationsListResponseBody {
+    notifications: Vec<GetNotificationsListResponseBodyInner>,
+}
+
+#[derive(Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct GetNotificationsListResponseBodyInner {
+    id: String,
+    content: String,
+    is_read: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    #[ts(type = "string")]
+    created_timestamp: time::OffsetDateTime,
+}
+
+/// Get notifications of the signed in user, latest first.
+#[utoipa::path(
+    get,
+    path = "/users/notifications",
+    tag = "users",
+    operation_id = "get_notifications_list",
+    params(AuthenticationHeaders, GetNotificationsListRequestQueries),
+    responses(
+        (
+            status = 200,
+            description = "successfully get list of notifications",
+            body = GetNotificationsListResponseBody,
+            example = json!({ "notifications": [] })
+        ),
+        (
+            status = 401,
+            description = "unauthorized",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::Unauthorized.get_error_struct())
+        ),
+        (
+            status = 500,
+            description = "internal server errors",
+            body = FormattedErrorResponse,
+            example = json!(HttpError::InternalServerError { cause: "internal".to_string() }.get_error_struct())
+        )
+    )
+)]
+pub async fn handler(
+    query: web::Query<GetNotificationsListRequestQueries>,
+    data: web::Data<SharedAppData>,
+    claims: AuthenticatedUserClaims,
+) -> Result<HttpResponse, HttpError> {
+    let page = query.page.unwrap_or(DEFAULT_PAGE);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let SqlRange { limit, offset } = SqlRange::from_page(page, page_size)?;
+
+    let client = data.pool.get().await?;
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                user_notification_id as id,
+                user_notification_content as content,
+                user_notification_is_read as is_read,
+                user_notification_created_timestamp as created_timestamp
+            from user_notifications
+            where user_id = $1
+            order by user_notification_created_timestamp desc
+            limit $2
+            offset $3
+            "##,
+            &[Type::TEXT, Type::INT4, Type::INT4],
+        )
+        .await?;
+
+    let notifications = client
+        .query(
+            &statement,
+            &[&claims.0.access_token.claims.uid, &limit, &offset],
+        )
+        .await?;
+
+    let notifications = notifications
+        .iter()
+        .map(|n| GetNotificationsListResponseBodyInner::try_from(n))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(HttpResponse::Ok().json(GetNotificationsListResponseBody { notifications }))
+}
@@ -1,2 +1,3 @@
+pub mod get_notifications_list;
 pub mod get_user_profile_image;
 pub mod get_users_list;
@@ -0,0 +1,327 @@
+use deadpool_postgres::GenericClient;
+use postgres_types::Type;
+use rust_decimal::Decimal;
+
+use crate::{constants::ID_LENGTH, errors::HttpError};
+
+use super::{enrollments::lock_student, notifications::notify};
+
+/// Tuition rate of a curriculum.
+pub struct TuitionRate {
+    pub per_credit: Decimal,
+    pub flat_fee: Decimal,
+}
+
+/// Calculate tuition of a semester from the enrolled credits, students who have nothing enrolled
+/// are not charged the flat fee.
+pub fn calculate_tuition(rate: &TuitionRate, total_credit: i64) -> Decimal {
+    if total_credit <= 0 {
+        return Decimal::ZERO;
+    }
+
+    rate.per_credit * Decimal::from(total_credit) + rate.flat_fee
+}
+
+/// Get the tuition rate of the curriculum the student is studying in, returns `None` when the
+/// curriculum has no rate configured.
+pub async fn get_tuition_rate(
+    client: &impl GenericClient,
+    student_id: &str,
+) -> Result<Option<TuitionRate>, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                curriculum_tuition_rates.curriculum_tuition_rate_per_credit,
+                curriculum_tuition_rates.curriculum_tuition_rate_flat_fee
+            from students
+            inner join majors on students.major_id = majors.major_id
+            inner join curriculum_tuition_rates on majors.curriculum_id = curriculum_tuition_rates.curriculum_id
+            where students.student_id = $1
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    let row = client.query_opt(&statement, &[&student_id]).await?;
+
+    match row {
+        Some(r) => Ok(Some(TuitionRate {
+            per_credit: r.try_get::<&str, Decimal>("curriculum_tuition_rate_per_credit")?,
+            flat_fee: r.try_get::<&str, Decimal>("curriculum_tuition_rate_flat_fee")?,
+        })),
+        None => Ok(None),
+    }
+}
+
+/// Sum credits of every subject the student is enrolled in during a semester. Withdrawn subjects
+/// are still charged.
+pub async fn get_enrolled_credit(
+    client: &impl GenericClient,
+    semester_id: &str,
+    student_id: &str,
+) -> Result<i64, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                coalesce(sum(subjects.subject_credit), 0)::int8 as total_credit
+            from student_enrollments
+            inner join subjects on student_enrollments.subject_id = subjects.subject_id
+            where
+                student_enrollments.semester_id = $1 and
+                student_enrollments.student_id = $2
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_one(&statement, &[&semester_id, &student_id])
+        .await?;
+
+    Ok(row.try_get::<&str, i64>("total_credit")?)
+}
+
+/// Recalculate what the student owes in a semester and bring the unpaid invoice up to date. A new
+/// invoice is issued when there is nothing unpaid yet, and the unpaid invoice is removed once
+/// the paid invoices already cover the tuition.
+///
+/// This has to be called in the same transaction that changes the enrollments, after
+/// [lock_student] has been called.
+pub async fn update_invoice(
+    client: &impl GenericClient,
+    semester_id: &str,
+    student_id: &str,
+) -> Result<(), HttpError> {
+    let rate = match get_tuition_rate(client, student_id).await? {
+        Some(r) => r,
+        None => {
+            tracing::warn!(
+                "curriculum of student {} has no tuition rate, skipped billing",
+                student_id
+            );
+            return Ok(());
+        }
+    };
+
+    let total_credit = get_enrolled_credit(client, semester_id, student_id).await?;
+    let tuition = calculate_tuition(&rate, total_credit);
+
+    let paid_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                coalesce(sum(student_transaction_price), 0) as paid_amount
+            from student_transactions
+            where
+                semester_id = $1 and
+                student_id = $2 and
+                student_transaction_is_transaction_successful
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let unpaid_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_transaction_id
+            from student_transactions
+            where
+                semester_id = $1 and
+                student_id = $2 and
+                not student_transaction_is_transaction_successful
+            for update
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let paid_amount = client
+        .query_one(&paid_statement, &[&semester_id, &student_id])
+        .await?
+        .try_get::<&str, Decimal>("paid_amount")?;
+    let outstanding = tuition - paid_amount;
+
+    let unpaid_transaction_id = client
+        .query_opt(&unpaid_statement, &[&semester_id, &student_id])
+        .await?
+        .map(|r| r.try_get::<&str, String>("student_transaction_id"))
+        .transpose()?;
+
+    match (unpaid_transaction_id, outstanding > Decimal::ZERO) {
+        (Some(transaction_id), true) => {
+            client
+                .execute(
+                    r##"
+                    update student_transactions set
+                        student_transaction_price = $2
+                    where student_transaction_id = $1
+                    "##,
+                    &[&transaction_id, &outstanding],
+                )
+                .await?;
+        }
+        (Some(transaction_id), false) => {
+            client
+                .execute(
+                    "delete from student_transactions where student_transaction_id = $1",
+                    &[&transaction_id],
+                )
+                .await?;
+        }
+        (None, true) => {
+            client
+                .execute(
+                    r##"
+                    insert into student_transactions (
+                        semester_id,
+                        student_id,
+                        student_transaction_id,
+                        student_transaction_price
+                    ) values (
+                        $1,
+                        $2,
+                        $3,
+                        $4
+                    )
+                    "##,
+                    &[
+                        &semester_id,
+                        &student_id,
+                        &randoid::randoid!(ID_LENGTH),
+                        &outstanding,
+                    ],
+                )
+                .await?;
+        }
+        (None, false) => {}
+    }
+
+    Ok(())
+}
+
+/// Check whether the student still has unpaid invoices in semesters other than the given one.
+pub async fn has_unpaid_invoices_in_other_semesters(
+    client: &impl GenericClient,
+    semester_id: &str,
+    student_id: &str,
+) -> Result<bool, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                exists (
+                    select 1
+                    from student_transactions
+                    where
+                        semester_id <> $1 and
+                        student_id = $2 and
+                        not student_transaction_is_transaction_successful
+                ) as has_unpaid_invoices
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_one(&statement, &[&semester_id, &student_id])
+        .await?;
+
+    Ok(row.try_get::<&str, bool>("has_unpaid_invoices")?)
+}
+
+/// Mark an invoice as paid with the amount the payment gateway actually charged. Paying less than
+/// the invoice issues another invoice for the rest. Paying an invoice that is already paid does
+/// nothing, so payment gateways could safely retry their webhooks.
+pub async fn pay_invoice(
+    client: &impl GenericClient,
+    transaction_id: &str,
+    amount: Decimal,
+) -> Result<(), HttpError> {
+    let owner = client
+        .query_opt(
+            "select student_id from student_transactions where student_transaction_id = $1",
+            &[&transaction_id],
+        )
+        .await?
+        .ok_or(HttpError::TransactionNotFound)?;
+
+    // same lock order as enrollments, which lock the student before touching invoices.
+    lock_student(client, owner.try_get::<&str, &str>("student_id")?).await?;
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                semester_id,
+                student_id,
+                student_transaction_is_transaction_successful
+            from student_transactions
+            where student_transaction_id = $1
+            for update
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    let transaction = client
+        .query_opt(&statement, &[&transaction_id])
+        .await?
+        .ok_or(HttpError::TransactionNotFound)?;
+
+    if transaction.try_get::<&str, bool>("student_transaction_is_transaction_successful")? {
+        return Ok(());
+    }
+
+    let semester_id = transaction.try_get::<&str, String>("semester_id")?;
+    let student_id = transaction.try_get::<&str, String>("student_id")?;
+
+    client
+        .execute(
+            r##"
+            update student_transactions set
+                student_transaction_is_transaction_successful = true,
+                student_transaction_price = $2,
+                student_transaction_finished_timestamp = now()
+            where student_transaction_id = $1
+            "##,
+            &[&transaction_id, &amount],
+        )
+        .await?;
+
+    update_invoice(client, &semester_id, &student_id).await?;
+
+    notify(
+        client,
+        &student_id,
+        &format!(
+            "payment of {} for semester {} has been received",
+            amount, semester_id
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use super::{calculate_tuition, TuitionRate};
+
+    #[test]
+    fn test_calculate_tuition() {
+        let rate = TuitionRate {
+            per_credit: Decimal::new(150000, 2),
+            flat_fee: Decimal::new(300000, 2),
+        };
+
+        assert_eq!(calculate_tuition(&rate, 0), Decimal::ZERO);
+        assert_eq!(calculate_tuition(&rate, 3), Decimal::new(750000, 2));
+        assert_eq!(calculate_tuition(&rate, 21), Decimal::new(3450000, 2));
+    }
+}
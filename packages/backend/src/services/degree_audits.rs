@@ -0,0 +1,324 @@
+use std::collections::HashSet;
+
+use deadpool_postgres::GenericClient;
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use serde::Serialize;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::errors::HttpError;
+
+/// A subject required by a credit specification of a major.
+#[derive(Serialize, ToSchema, TS, FromRow, Clone)]
+#[ts(export)]
+pub struct MajorSubject {
+    pub subject_id: String,
+    pub subject_name: String,
+    #[ts(type = "number")]
+    pub credit: i32,
+}
+
+/// Progress of a student in one credit specification of the major.
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct DegreeAuditGroup {
+    pub major_credit_specification_id: String,
+    pub name: String,
+    #[ts(type = "number")]
+    pub minimum_credit: i32,
+    /// Credits of subjects the student has passed.
+    #[ts(type = "number")]
+    pub earned_credit: i32,
+    /// Credits of subjects the student is enrolled in whose grades are not released yet.
+    #[ts(type = "number")]
+    pub in_progress_credit: i32,
+    /// Subjects the student has neither passed nor is studying.
+    pub remaining_subjects: Vec<MajorSubject>,
+    pub is_satisfied: bool,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct DegreeAudit {
+    pub student_id: String,
+    pub major_id: String,
+    pub groups: Vec<DegreeAuditGroup>,
+    /// Whether every credit specification of the major is satisfied, majors without any credit
+    /// specification never make anyone eligible.
+    pub is_eligible_to_graduate: bool,
+}
+
+#[derive(FromRow)]
+struct SpecificationRow {
+    major_credit_specification_id: String,
+    major_credit_specification_name: String,
+    major_credit_specification_minimum_credit: i32,
+    subject_id: Option<String>,
+    subject_name: Option<String>,
+    subject_credit: Option<i32>,
+}
+
+/// Audit one credit specification from the subjects the student has passed and is studying. Every
+/// subject counts towards each specification it is in.
+pub fn audit_group(
+    major_credit_specification_id: String,
+    name: String,
+    minimum_credit: i32,
+    subjects: &[MajorSubject],
+    passed_subject_ids: &HashSet<String>,
+    in_progress_subject_ids: &HashSet<String>,
+) -> DegreeAuditGroup {
+    let mut earned_credit = 0;
+    let mut in_progress_credit = 0;
+    let mut remaining_subjects = vec![];
+
+    for subject in subjects {
+        if passed_subject_ids.contains(&subject.subject_id) {
+            earned_credit += subject.credit;
+        } else if in_progress_subject_ids.contains(&subject.subject_id) {
+            in_progress_credit += subject.credit;
+        } else {
+            remaining_subjects.push(subject.clone());
+        }
+    }
+
+    DegreeAuditGroup {
+        major_credit_specification_id,
+        name,
+        minimum_credit,
+        earned_credit,
+        in_progress_credit,
+        remaining_subjects,
+        is_satisfied: earned_credit >= minimum_credit,
+    }
+}
+
+/// Get `subject_id` of every subject the student has passed, which are subjects with a released
+/// grade that gives more than zero grade points.
+pub async fn get_passed_subject_ids(
+    client: &impl GenericClient,
+    student_id: &str,
+) -> Result<HashSet<String>, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select distinct
+                student_grades.subject_id
+            from student_grades
+            inner join opening_subjects_in_semester_subject_descriptions on
+                student_grades.semester_id = opening_subjects_in_semester_subject_descriptions.semester_id and
+                student_grades.subject_id = opening_subjects_in_semester_subject_descriptions.subject_id
+            inner join grade_point_mappings on
+                coalesce(
+                    student_grades.student_grade_overridden_alphabet,
+                    student_grades.student_grade_computed_alphabet
+                ) = grade_point_mappings.grade_point_mapping_alphabet
+            where
+                student_grades.student_id = $1 and
+                opening_subjects_in_semester_subject_descriptions.is_grade_released and
+                grade_point_mappings.grade_point_mapping_point > 0
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    client
+        .query(&statement, &[&student_id])
+        .await?
+        .iter()
+        .map(|r| r.try_get::<&str, String>("subject_id").map_err(Into::into))
+        .collect()
+}
+
+/// Get `subject_id` of every subject the student is enrolled in whose grades are not released yet.
+pub async fn get_in_progress_subject_ids(
+    client: &impl GenericClient,
+    student_id: &str,
+) -> Result<HashSet<String>, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select distinct
+                student_enrollments.subject_id
+            from student_enrollments
+            inner join opening_subjects_in_semester_subject_descriptions on
+                student_enrollments.semester_id = opening_subjects_in_semester_subject_descriptions.semester_id and
+                student_enrollments.subject_id = opening_subjects_in_semester_subject_descriptions.subject_id
+            where
+                student_enrollments.student_id = $1 and
+                not student_enrollments.student_enrollment_is_withdrawn and
+                not opening_subjects_in_semester_subject_descriptions.is_grade_released
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    client
+        .query(&statement, &[&student_id])
+        .await?
+        .iter()
+        .map(|r| r.try_get::<&str, String>("subject_id").map_err(Into::into))
+        .collect()
+}
+
+/// Audit a student against every credit specification of the student's major.
+pub async fn get_degree_audit(
+    client: &impl GenericClient,
+    student_id: &str,
+) -> Result<DegreeAudit, HttpError> {
+    let major_id = client
+        .query_opt(
+            "select major_id from students where student_id = $1",
+            &[&student_id],
+        )
+        .await?
+        .ok_or(HttpError::StudentNotFound)?
+        .try_get::<&str, String>("major_id")?;
+
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                major_credit_specifications.major_credit_specification_id,
+                major_credit_specifications.major_credit_specification_name,
+                major_credit_specifications.major_credit_specification_minimum_credit,
+                subjects.subject_id,
+                subjects.subject_name,
+                subjects.subject_credit
+            from major_credit_specifications
+            left join major_subjects on
+                major_credit_specifications.major_credit_specification_id = major_subjects.major_credit_specification_id
+            left join subjects on major_subjects.subject_id = subjects.subject_id
+            where major_credit_specifications.major_id = $1
+            order by
+                major_credit_specifications.major_credit_specification_created_timestamp asc,
+                major_credit_specifications.major_credit_specification_id asc,
+                subjects.subject_id asc
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    let rows = client
+        .query(&statement, &[&major_id])
+        .await?
+        .iter()
+        .map(|r| SpecificationRow::try_from(r))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let passed_subject_ids = get_passed_subject_ids(client, student_id).await?;
+    let in_progress_subject_ids = get_in_progress_subject_ids(client, student_id).await?;
+
+    // rows of the same specification are next to each other, group them in order.
+    let mut specifications: Vec<(String, String, i32, Vec<MajorSubject>)> = vec![];
+
+    for row in rows {
+        if specifications
+            .last()
+            .map_or(true, |s| s.0 != row.major_credit_specification_id)
+        {
+            specifications.push((
+                row.major_credit_specification_id,
+                row.major_credit_specification_name,
+                row.major_credit_specification_minimum_credit,
+                vec![],
+            ));
+        }
+
+        if let (Some(subject_id), Some(subject_name), Some(credit), Some(specification)) = (
+            row.subject_id,
+            row.subject_name,
+            row.subject_credit,
+            specifications.last_mut(),
+        ) {
+            specification.3.push(MajorSubject {
+                subject_id,
+                subject_name,
+                credit,
+            });
+        }
+    }
+
+    let groups = specifications
+        .into_iter()
+        .map(|(id, name, minimum_credit, subjects)| {
+            audit_group(
+                id,
+                name,
+                minimum_credit,
+                &subjects,
+                &passed_subject_ids,
+                &in_progress_subject_ids,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let is_eligible_to_graduate = !groups.is_empty() && groups.iter().all(|g| g.is_satisfied);
+
+    Ok(DegreeAudit {
+        student_id: student_id.to_string(),
+        major_id,
+        groups,
+        is_eligible_to_graduate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{audit_group, MajorSubject};
+
+    fn subject(subject_id: &str, credit: i32) -> MajorSubject {
+        MajorSubject {
+            subject_id: subject_id.to_string(),
+            subject_name: subject_id.to_string(),
+            credit,
+        }
+    }
+
+    #[test]
+    fn test_audit_group() {
+        let subjects = [
+            subject("261101", 3),
+            subject("261102", 3),
+            subject("261103", 3),
+            subject("261104", 1),
+        ];
+        let passed = HashSet::from(["261101".to_string(), "261104".to_string()]);
+        let in_progress = HashSet::from(["261102".to_string()]);
+
+        let group = audit_group(
+            "core".to_string(),
+            "Core".to_string(),
+            6,
+            &subjects,
+            &passed,
+            &in_progress,
+        );
+
+        assert_eq!(group.earned_credit, 4);
+        assert_eq!(group.in_progress_credit, 3);
+        assert_eq!(
+            group
+                .remaining_subjects
+                .iter()
+                .map(|s| s.subject_id.as_str())
+                .collect::<Vec<_>>(),
+            ["261103"]
+        );
+        assert!(!group.is_satisfied);
+
+        let group = audit_group(
+            "core".to_string(),
+            "Core".to_string(),
+            4,
+            &subjects,
+            &passed,
+            &in_progress,
+        );
+
+        assert!(group.is_satisfied);
+    }
+}
@@ -0,0 +1,691 @@
+use deadpool_postgres::{Client, GenericClient};
+use postgres_types::Type;
+
+use crate::{database::DayOfWeek, errors::HttpError};
+
+use super::{
+    billing::update_invoice, notifications::notify, schedule_conflicts::time_ranges_overlap,
+};
+
+/// One weekly class of a subject.
+pub struct ClassTime {
+    pub subject_id: String,
+    pub day_of_week: DayOfWeek,
+    pub start_time_of_day: time::Time,
+    pub end_time_of_day: time::Time,
+}
+
+/// Returns the subject id of the first enrolled class that happens at the same time as one of the
+/// classes of the subject that is about to be enrolled.
+pub fn find_clashing_subject<'a>(
+    subject_classes: &[ClassTime],
+    enrolled_classes: &'a [ClassTime],
+) -> Option<&'a str> {
+    for class in subject_classes {
+        for enrolled in enrolled_classes {
+            if class.day_of_week == enrolled.day_of_week
+                && time_ranges_overlap(
+                    (class.start_time_of_day, class.end_time_of_day),
+                    (enrolled.start_time_of_day, enrolled.end_time_of_day),
+                )
+            {
+                return Some(enrolled.subject_id.as_str());
+            }
+        }
+    }
+
+    None
+}
+
+/// Lock an opening subject for the rest of the transaction and return its capacity.
+///
+/// Every enrollment into the same opening subject has to lock its row first, so that concurrent
+/// enrollments are processed one after another and the capacity could never be exceeded even in
+/// the registration day rush.
+pub async fn lock_opening_subject(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+) -> Result<i32, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                subject_capacity
+            from opening_subjects_in_semester_subject_descriptions
+            where semester_id = $1 and subject_id = $2
+            for update
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&statement, &[&semester_id, &subject_id])
+        .await?;
+
+    match row {
+        Some(r) => Ok(r.try_get::<&str, i32>("subject_capacity")?),
+        None => Err(HttpError::OpeningSubjectNotFound),
+    }
+}
+
+/// Lock a student for the rest of the transaction, so two enrollments of the same student could not
+/// pass the time clash checks at the same time.
+pub async fn lock_student(client: &impl GenericClient, student_id: &str) -> Result<(), HttpError> {
+    client
+        .execute("select pg_advisory_xact_lock(hashtext($1))", &[&student_id])
+        .await?;
+
+    Ok(())
+}
+
+/// Count how many students are enrolled in an opening subject, withdrawn students are not counted.
+pub async fn count_enrollments(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+) -> Result<i64, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                count(*) as enrollment_count
+            from student_enrollments
+            where
+                semester_id = $1 and
+                subject_id = $2 and
+                not student_enrollment_is_withdrawn
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_one(&statement, &[&semester_id, &subject_id])
+        .await?;
+
+    Ok(row.try_get::<&str, i64>("enrollment_count")?)
+}
+
+/// Check whether the student is already enrolled in an opening subject, including withdrawn
+/// enrollments.
+pub async fn is_enrolled(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    student_id: &str,
+) -> Result<bool, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_id
+            from student_enrollments
+            where semester_id = $1 and subject_id = $2 and student_id = $3
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&statement, &[&semester_id, &subject_id, &student_id])
+        .await?;
+
+    Ok(row.is_some())
+}
+
+/// A student is eligible for an opening subject when the student's major and first academic year
+/// is listed in `opening_subjects_in_semester_eligible_majors`, or the student is listed in
+/// `opening_subjects_in_semester_additional_eligible_students`.
+pub async fn is_eligible(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    student_id: &str,
+) -> Result<bool, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                exists (
+                    select 1
+                    from opening_subjects_in_semester_eligible_majors
+                    inner join students on
+                        opening_subjects_in_semester_eligible_majors.major_id = students.major_id and
+                        opening_subjects_in_semester_eligible_majors.academic_year_id = students.first_academic_year_id
+                    where
+                        opening_subjects_in_semester_eligible_majors.semester_id = $1 and
+                        opening_subjects_in_semester_eligible_majors.subject_id = $2 and
+                        students.student_id = $3
+                ) or exists (
+                    select 1
+                    from opening_subjects_in_semester_additional_eligible_students
+                    where
+                        semester_id = $1 and
+                        subject_id = $2 and
+                        additional_student_id = $3
+                ) as is_eligible
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_one(&statement, &[&semester_id, &subject_id, &student_id])
+        .await?;
+
+    Ok(row.try_get::<&str, bool>("is_eligible")?)
+}
+
+/// Find the subject that clashes with the classes of the subject that the student is about to
+/// enroll in, by looking at the rest of the student's enrollments in the semester.
+pub async fn find_time_clash(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    student_id: &str,
+) -> Result<Option<String>, HttpError> {
+    let subject_classes_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                subject_id,
+                day_of_week,
+                start_time_of_day,
+                end_time_of_day
+            from opening_subjects_in_semester_schedules
+            where semester_id = $1 and subject_id = $2
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let enrolled_classes_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                opening_subjects_in_semester_schedules.subject_id,
+                opening_subjects_in_semester_schedules.day_of_week,
+                opening_subjects_in_semester_schedules.start_time_of_day,
+                opening_subjects_in_semester_schedules.end_time_of_day
+            from student_enrollments
+            inner join opening_subjects_in_semester_schedules on
+                student_enrollments.semester_id = opening_subjects_in_semester_schedules.semester_id and
+                student_enrollments.subject_id = opening_subjects_in_semester_schedules.subject_id
+            where
+                student_enrollments.semester_id = $1 and
+                student_enrollments.subject_id <> $2 and
+                student_enrollments.student_id = $3 and
+                not student_enrollments.student_enrollment_is_withdrawn
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let subject_classes = client
+        .query(&subject_classes_statement, &[&semester_id, &subject_id])
+        .await?
+        .iter()
+        .map(ClassTime::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let enrolled_classes = client
+        .query(
+            &enrolled_classes_statement,
+            &[&semester_id, &subject_id, &student_id],
+        )
+        .await?
+        .iter()
+        .map(ClassTime::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(find_clashing_subject(&subject_classes, &enrolled_classes).map(|s| s.to_string()))
+}
+
+/// Insert an enrollment of the student into an opening subject without any checks.
+pub async fn enroll(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    student_id: &str,
+) -> Result<(), HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            insert into student_enrollments (
+                semester_id,
+                subject_id,
+                student_id
+            ) values (
+                $1,
+                $2,
+                $3
+            )
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    client
+        .execute(&statement, &[&semester_id, &subject_id, &student_id])
+        .await?;
+
+    Ok(())
+}
+
+/// Delete an enrollment of the student, returns `false` when the student is not enrolled.
+pub async fn drop_enrollment(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    student_id: &str,
+) -> Result<bool, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            delete from student_enrollments
+            where semester_id = $1 and subject_id = $2 and student_id = $3
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let deleted = client
+        .execute(&statement, &[&semester_id, &subject_id, &student_id])
+        .await?;
+
+    Ok(deleted > 0)
+}
+
+/// Mark an enrollment of the student as withdrawn so it shows up as "W" on the transcript, returns
+/// `false` when the student is not enrolled or already withdrawn.
+pub async fn withdraw_enrollment(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    student_id: &str,
+) -> Result<bool, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            update student_enrollments set
+                student_enrollment_is_withdrawn = true,
+                student_enrollment_withdrawn_timestamp = now()
+            where
+                semester_id = $1 and
+                subject_id = $2 and
+                student_id = $3 and
+                not student_enrollment_is_withdrawn
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let updated = client
+        .execute(&statement, &[&semester_id, &subject_id, &student_id])
+        .await?;
+
+    Ok(updated > 0)
+}
+
+/// Get 1-indexed position of the student in the waitlist of an opening subject, returns `None` when
+/// the student is not in the waitlist.
+pub async fn get_waitlist_position(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    student_id: &str,
+) -> Result<Option<i64>, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                waitlist.waitlist_position
+            from (
+                select
+                    student_id,
+                    row_number() over (
+                        order by
+                            student_enrollment_waitlist_created_timestamp asc,
+                            student_id asc
+                    ) as waitlist_position
+                from student_enrollment_waitlists
+                where semester_id = $1 and subject_id = $2
+            ) as waitlist
+            where waitlist.student_id = $3
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&statement, &[&semester_id, &subject_id, &student_id])
+        .await?;
+
+    match row {
+        Some(r) => Ok(Some(r.try_get::<&str, i64>("waitlist_position")?)),
+        None => Ok(None),
+    }
+}
+
+/// Put the student at the end of the waitlist of an opening subject and returns the position.
+pub async fn add_to_waitlist(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    student_id: &str,
+) -> Result<i64, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            insert into student_enrollment_waitlists (
+                semester_id,
+                subject_id,
+                student_id
+            ) values (
+                $1,
+                $2,
+                $3
+            )
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    client
+        .execute(&statement, &[&semester_id, &subject_id, &student_id])
+        .await?;
+
+    get_waitlist_position(client, semester_id, subject_id, student_id)
+        .await?
+        .ok_or(HttpError::InternalServerError {
+            cause: "cannot find waitlist position after inserting".to_string(),
+        })
+}
+
+/// Remove the student from the waitlist of an opening subject, returns `false` when the student
+/// was not in the waitlist.
+pub async fn remove_from_waitlist(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    student_id: &str,
+) -> Result<bool, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            delete from student_enrollment_waitlists
+            where semester_id = $1 and subject_id = $2 and student_id = $3
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let deleted = client
+        .execute(&statement, &[&semester_id, &subject_id, &student_id])
+        .await?;
+
+    Ok(deleted > 0)
+}
+
+/// Promote waitlisted students of an opening subject, in waitlist order, until the subject is full
+/// or the waitlist runs out. Students who are no longer eligible or whose enrollments now clash
+/// with the subject are skipped and stay in the waitlist, students who are already enrolled are
+/// removed from it. Every promoted student gets a notification and an updated invoice.
+///
+/// This has to be called after the transaction that frees the seats is committed. Each student is
+/// promoted in a transaction of its own that takes [lock_student] before [lock_opening_subject],
+/// the same order as a direct enrollment, so a student who is enrolling somewhere else at the
+/// moment is waited for instead of losing the seat to the next student in the waitlist.
+pub async fn fill_seats_from_waitlist(
+    client: &mut Client,
+    semester_id: &str,
+    subject_id: &str,
+) -> Result<Vec<String>, HttpError> {
+    let next_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_enrollment_waitlists.student_id,
+                subjects.subject_name
+            from student_enrollment_waitlists
+            inner join subjects on student_enrollment_waitlists.subject_id = subjects.subject_id
+            where
+                student_enrollment_waitlists.semester_id = $1 and
+                student_enrollment_waitlists.subject_id = $2 and
+                student_enrollment_waitlists.student_id <> all($3)
+            order by
+                student_enrollment_waitlists.student_enrollment_waitlist_created_timestamp asc,
+                student_enrollment_waitlists.student_id asc
+            limit 1
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT_ARRAY],
+        )
+        .await?;
+
+    let mut skipped_student_ids = Vec::<String>::new();
+    let mut promoted_student_ids = Vec::new();
+
+    loop {
+        let waitlisted = match client
+            .query_opt(
+                &next_statement,
+                &[&semester_id, &subject_id, &skipped_student_ids],
+            )
+            .await?
+        {
+            Some(w) => w,
+            None => break,
+        };
+
+        let student_id = waitlisted.try_get::<&str, String>("student_id")?;
+        let transaction = client.transaction().await?;
+
+        lock_student(&transaction, &student_id).await?;
+        let subject_capacity = lock_opening_subject(&transaction, semester_id, subject_id).await?;
+
+        if count_enrollments(&transaction, semester_id, subject_id).await?
+            >= subject_capacity as i64
+        {
+            break;
+        }
+
+        // students who got a seat directly are only cleared from the waitlist.
+        if is_enrolled(&transaction, semester_id, subject_id, &student_id).await? {
+            remove_from_waitlist(&transaction, semester_id, subject_id, &student_id).await?;
+            transaction.commit().await?;
+            continue;
+        }
+
+        if !is_eligible(&transaction, semester_id, subject_id, &student_id).await?
+            || find_time_clash(&transaction, semester_id, subject_id, &student_id)
+                .await?
+                .is_some()
+        {
+            skipped_student_ids.push(student_id);
+            continue;
+        }
+
+        // the student could have left the waitlist while waiting for the lock.
+        if !remove_from_waitlist(&transaction, semester_id, subject_id, &student_id).await? {
+            continue;
+        }
+
+        enroll(&transaction, semester_id, subject_id, &student_id).await?;
+        update_invoice(&transaction, semester_id, &student_id).await?;
+        notify(
+            &transaction,
+            &student_id,
+            &format!(
+                "you have been enrolled in {} ({}) from the waitlist",
+                waitlisted.try_get::<&str, String>("subject_name")?,
+                subject_id
+            ),
+        )
+        .await?;
+
+        transaction.commit().await?;
+        promoted_student_ids.push(student_id);
+    }
+
+    Ok(promoted_student_ids)
+}
+
+impl TryFrom<&tokio_postgres::Row> for ClassTime {
+    type Error = HttpError;
+
+    fn try_from(row: &tokio_postgres::Row) -> Result<Self, Self::Error> {
+        Ok(Self {
+            subject_id: row.try_get::<&str, String>("subject_id")?,
+            day_of_week: row.try_get::<&str, DayOfWeek>("day_of_week")?,
+            start_time_of_day: row.try_get::<&str, time::Time>("start_time_of_day")?,
+            end_time_of_day: row.try_get::<&str, time::Time>("end_time_of_day")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::time;
+
+    use crate::{
+        database::DayOfWeek,
+        fixtures::{fresh_schema, Fixtures},
+        startup::load_postgres_config,
+    };
+
+    use super::{
+        add_to_waitlist, drop_enrollment, fill_seats_from_waitlist, find_clashing_subject,
+        get_waitlist_position, is_enrolled, ClassTime,
+    };
+
+    fn class(
+        subject_id: &str,
+        day_of_week: DayOfWeek,
+        start_time_of_day: time::Time,
+        end_time_of_day: time::Time,
+    ) -> ClassTime {
+        ClassTime {
+            subject_id: subject_id.to_string(),
+            day_of_week,
+            start_time_of_day,
+            end_time_of_day,
+        }
+    }
+
+    #[test]
+    fn test_find_clashing_subject() {
+        let subject = [
+            class("273101", DayOfWeek::Monday, time!(09:00), time!(12:00)),
+            class("273101", DayOfWeek::Thursday, time!(13:00), time!(15:00)),
+        ];
+
+        // nothing enrolled yet
+        assert_eq!(find_clashing_subject(&subject, &[]), None);
+
+        // same time on another day
+        let enrolled = [class(
+            "273102",
+            DayOfWeek::Tuesday,
+            time!(09:00),
+            time!(12:00),
+        )];
+        assert_eq!(find_clashing_subject(&subject, &enrolled), None);
+
+        // back to back on the same day
+        let enrolled = [class(
+            "273102",
+            DayOfWeek::Thursday,
+            time!(15:00),
+            time!(16:00),
+        )];
+        assert_eq!(find_clashing_subject(&subject, &enrolled), None);
+
+        // overlapping on the second class of the week
+        let enrolled = [
+            class("273102", DayOfWeek::Tuesday, time!(09:00), time!(12:00)),
+            class("273103", DayOfWeek::Thursday, time!(14:00), time!(16:00)),
+        ];
+        assert_eq!(find_clashing_subject(&subject, &enrolled), Some("273103"));
+    }
+
+    #[actix_web::test]
+    async fn test_fill_seats_from_waitlist() {
+        let pool = fresh_schema(
+            &load_postgres_config(),
+            "test_fill_seats_from_waitlist",
+            &Fixtures::parse(include_str!("../../fixtures/demo.toml")).unwrap(),
+        )
+        .await
+        .unwrap();
+        let mut client = pool.get().await.unwrap();
+
+        let semester_id = "semester-2022-1";
+        let subject_id = "subject-calculus";
+
+        // calculus is full with its three students, and opens to somchai's year as well.
+        client
+            .batch_execute(
+                r##"
+                update opening_subjects_in_semester_subject_descriptions
+                set subject_capacity = 3
+                where semester_id = 'semester-2022-1' and subject_id = 'subject-calculus';
+
+                insert into opening_subjects_in_semester_eligible_majors (
+                    semester_id,
+                    subject_id,
+                    major_id,
+                    academic_year_id
+                ) values (
+                    'semester-2022-1',
+                    'subject-calculus',
+                    'major-computer-science',
+                    'academic-year-2021'
+                );
+                "##,
+            )
+            .await
+            .unwrap();
+
+        // napat is already enrolled, so only the waitlist entry goes away.
+        for student_id in ["student-napat", "student-somchai"] {
+            add_to_waitlist(&client, semester_id, subject_id, student_id)
+                .await
+                .unwrap();
+        }
+
+        assert!(
+            fill_seats_from_waitlist(&mut client, semester_id, subject_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        assert!(
+            drop_enrollment(&client, semester_id, subject_id, "student-anan")
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            fill_seats_from_waitlist(&mut client, semester_id, subject_id)
+                .await
+                .unwrap(),
+            vec!["student-somchai".to_string()]
+        );
+
+        assert!(
+            is_enrolled(&client, semester_id, subject_id, "student-somchai")
+                .await
+                .unwrap()
+        );
+        for student_id in ["student-napat", "student-somchai"] {
+            assert_eq!(
+                get_waitlist_position(&client, semester_id, subject_id, student_id)
+                    .await
+                    .unwrap(),
+                None
+            );
+        }
+    }
+}
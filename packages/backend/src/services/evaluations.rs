@@ -0,0 +1,101 @@
+use deadpool_postgres::GenericClient;
+use postgres_types::Type;
+
+use crate::{constants::MINIMUM_COMMENT_RESPONSE_COUNT, errors::HttpError};
+
+/// Whether comments of a subject could be shown to its professors without making any student
+/// identifiable.
+pub fn can_show_comments(response_count: i64) -> bool {
+    response_count >= MINIMUM_COMMENT_RESPONSE_COUNT
+}
+
+/// Check whether the student could comment on an opening subject. Only students who got a grade
+/// in the subject could comment, and only after the grades are released.
+pub async fn check_evaluation_open(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    student_id: &str,
+) -> Result<(), HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                opening_subjects_in_semester_subject_descriptions.is_grade_released,
+                exists (
+                    select 1
+                    from student_grades
+                    where
+                        student_grades.semester_id = $1 and
+                        student_grades.subject_id = $2 and
+                        student_grades.student_id = $3
+                ) as has_grade
+            from opening_subjects_in_semester_subject_descriptions
+            where
+                opening_subjects_in_semester_subject_descriptions.semester_id = $1 and
+                opening_subjects_in_semester_subject_descriptions.subject_id = $2
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&statement, &[&semester_id, &subject_id, &student_id])
+        .await?
+        .ok_or(HttpError::OpeningSubjectNotFound)?;
+
+    if !row.try_get::<&str, bool>("is_grade_released")? {
+        return Err(HttpError::GradesNotReleased);
+    }
+
+    if !row.try_get::<&str, bool>("has_grade")? {
+        return Err(HttpError::StudentNotEnrolled);
+    }
+
+    Ok(())
+}
+
+/// Get comments of an opening subject without anything that could identify the students, ordered
+/// by their content instead of when they were submitted.
+pub async fn get_anonymous_comments(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+) -> Result<Vec<String>, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_comment
+            from student_subject_comments
+            where semester_id = $1 and subject_id = $2
+            order by student_comment asc
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    client
+        .query(&statement, &[&semester_id, &subject_id])
+        .await?
+        .iter()
+        .map(|r| {
+            r.try_get::<&str, String>("student_comment")
+                .map_err(Into::into)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constants::MINIMUM_COMMENT_RESPONSE_COUNT;
+
+    use super::can_show_comments;
+
+    #[test]
+    fn test_can_show_comments() {
+        assert!(!can_show_comments(0));
+        assert!(!can_show_comments(MINIMUM_COMMENT_RESPONSE_COUNT - 1));
+        assert!(can_show_comments(MINIMUM_COMMENT_RESPONSE_COUNT));
+    }
+}
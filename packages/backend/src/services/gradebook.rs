@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+
+use deadpool_postgres::GenericClient;
+use postgres_types::Type;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::errors::HttpError;
+
+/// Score of one student in an assignment.
+#[derive(Deserialize, ToSchema)]
+pub struct ScoreEntry {
+    pub student_id: String,
+    #[schema(value_type = String, example = json!("8.50"))]
+    pub score: Decimal,
+}
+
+/// Validate scores that are about to be written into an assignment. Every score has to be between
+/// zero and the full score of the assignment, every student has to be enrolled in the subject and
+/// no student could be repeated.
+pub fn validate_scores(
+    scores: &[ScoreEntry],
+    full_score: Decimal,
+    enrolled_student_ids: &HashSet<String>,
+) -> Result<(), HttpError> {
+    let mut student_ids = HashSet::new();
+
+    for entry in scores {
+        if !student_ids.insert(entry.student_id.as_str()) {
+            return Err(HttpError::InputValidationError);
+        }
+
+        if entry.score.is_sign_negative() || entry.score > full_score {
+            return Err(HttpError::ScoreExceedsFullScore);
+        }
+
+        if !enrolled_student_ids.contains(&entry.student_id) {
+            return Err(HttpError::StudentNotEnrolled);
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the full score of an assignment of an opening subject.
+pub async fn get_assignment_full_score(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    assignment_id: &str,
+) -> Result<Decimal, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_assignment_full_score
+            from student_assignments
+            where
+                semester_id = $1 and
+                subject_id = $2 and
+                student_assignment_id = $3
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&statement, &[&semester_id, &subject_id, &assignment_id])
+        .await?;
+
+    match row {
+        Some(r) => Ok(r.try_get::<&str, Decimal>("student_assignment_full_score")?),
+        None => Err(HttpError::AssignmentNotFound),
+    }
+}
+
+/// Get `student_id` of every student enrolled in an opening subject, withdrawn students are not
+/// included.
+pub async fn get_enrolled_student_ids(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+) -> Result<HashSet<String>, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_id
+            from student_enrollments
+            where
+                semester_id = $1 and
+                subject_id = $2 and
+                not student_enrollment_is_withdrawn
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    client
+        .query(&statement, &[&semester_id, &subject_id])
+        .await?
+        .iter()
+        .map(|r| r.try_get::<&str, String>("student_id").map_err(Into::into))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rust_decimal::Decimal;
+
+    use crate::errors::HttpError;
+
+    use super::{validate_scores, ScoreEntry};
+
+    fn entry(student_id: &str, score: i64) -> ScoreEntry {
+        ScoreEntry {
+            student_id: student_id.to_string(),
+            score: Decimal::from(score),
+        }
+    }
+
+    #[test]
+    fn test_validate_scores() {
+        let full_score = Decimal::from(10);
+        let enrolled = HashSet::from(["640610001".to_string(), "640610002".to_string()]);
+
+        assert!(validate_scores(
+            &[entry("640610001", 10), entry("640610002", 0)],
+            full_score,
+            &enrolled
+        )
+        .is_ok());
+
+        assert!(matches!(
+            validate_scores(&[entry("640610001", 11)], full_score, &enrolled),
+            Err(HttpError::ScoreExceedsFullScore)
+        ));
+        assert!(matches!(
+            validate_scores(&[entry("640610001", -1)], full_score, &enrolled),
+            Err(HttpError::ScoreExceedsFullScore)
+        ));
+        assert!(matches!(
+            validate_scores(&[entry("640610003", 5)], full_score, &enrolled),
+            Err(HttpError::StudentNotEnrolled)
+        ));
+        assert!(matches!(
+            validate_scores(
+                &[entry("640610001", 5), entry("640610001", 6)],
+                full_score,
+                &enrolled
+            ),
+            Err(HttpError::InputValidationError)
+        ));
+    }
+}
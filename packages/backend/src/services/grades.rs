@@ -0,0 +1,399 @@
+use deadpool_postgres::GenericClient;
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{constants::FALLBACK_GRADE_ALPHABET, errors::HttpError};
+
+use super::grading_criterias::{get_grading_criteria_grades, GradingCriteriaGrade};
+
+/// Grade of a student in the snapshot of an opening subject.
+#[derive(Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct SubjectGrade {
+    pub student_id: String,
+    pub student_representative_id: String,
+    /// Percentage of the total score of every assignment.
+    #[schema(value_type = String, example = json!("78.50"))]
+    #[ts(type = "string")]
+    pub total_score: Decimal,
+    pub computed_alphabet: String,
+    #[ts(optional)]
+    pub overridden_alphabet: Option<String>,
+    #[ts(optional)]
+    pub override_reason: Option<String>,
+    /// The grade students see, the overridden grade when it exists.
+    pub alphabet: String,
+}
+
+/// Calculate the percentage of the full score rounded to 2 decimal places. Subjects without any
+/// assignment have a total score of zero.
+pub fn calculate_total_score(score: Decimal, full_score: Decimal) -> Decimal {
+    if full_score <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+
+    (score * Decimal::ONE_HUNDRED / full_score).round_dp(2)
+}
+
+/// Map a total score to the first grade, from the best grade to the worst grade, whose minimum
+/// score is reached. Total scores below every grade get [FALLBACK_GRADE_ALPHABET].
+pub fn map_to_alphabet(total_score: Decimal, grades: &[GradingCriteriaGrade]) -> String {
+    let mut grades = grades.iter().collect::<Vec<_>>();
+    grades.sort_by(|a, b| b.minimum_score.cmp(&a.minimum_score));
+
+    grades
+        .into_iter()
+        .find(|g| total_score >= g.minimum_score)
+        .map(|g| g.alphabet.clone())
+        .unwrap_or_else(|| FALLBACK_GRADE_ALPHABET.to_string())
+}
+
+/// Whether a grade could be given manually with the grading criteria.
+pub fn is_valid_alphabet(alphabet: &str, grades: &[GradingCriteriaGrade]) -> bool {
+    alphabet == FALLBACK_GRADE_ALPHABET || grades.iter().any(|g| g.alphabet == alphabet)
+}
+
+/// Lock the grades of an opening subject for the rest of the transaction and return whether they
+/// are already released.
+pub async fn lock_grades(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+) -> Result<bool, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                is_grade_released
+            from opening_subjects_in_semester_subject_descriptions
+            where semester_id = $1 and subject_id = $2
+            for update
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&statement, &[&semester_id, &subject_id])
+        .await?;
+
+    match row {
+        Some(r) => Ok(r.try_get::<&str, bool>("is_grade_released")?),
+        None => Err(HttpError::OpeningSubjectNotFound),
+    }
+}
+
+/// Get grades of the grading criteria attached to an opening subject.
+pub async fn get_subject_grading_criteria_grades(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+) -> Result<Vec<GradingCriteriaGrade>, HttpError> {
+    let row = client
+        .query_opt(
+            r##"
+            select
+                grading_criteria_id
+            from opening_subjects_in_semester_subject_descriptions
+            where semester_id = $1 and subject_id = $2
+            "##,
+            &[&semester_id, &subject_id],
+        )
+        .await?
+        .ok_or(HttpError::OpeningSubjectNotFound)?;
+
+    get_grading_criteria_grades(client, row.try_get::<&str, &str>("grading_criteria_id")?).await
+}
+
+/// Compute grades of every enrolled student in an opening subject and write them into the
+/// snapshot. Grades overridden by the professor are kept, and grades of students who are no
+/// longer enrolled are removed. Withdrawn students do not get grades.
+///
+/// This has to be called after [lock_grades] in the same transaction, and only before the grades
+/// are released.
+pub async fn compute_grades(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+) -> Result<(), HttpError> {
+    let grades = get_subject_grading_criteria_grades(client, semester_id, subject_id).await?;
+
+    let full_score_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                coalesce(sum(student_assignment_full_score), 0) as full_score
+            from student_assignments
+            where semester_id = $1 and subject_id = $2
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let scores_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_enrollments.student_id,
+                coalesce(sum(student_scores.student_score), 0) as score
+            from student_enrollments
+            left join student_scores on
+                student_enrollments.semester_id = student_scores.semester_id and
+                student_enrollments.subject_id = student_scores.subject_id and
+                student_enrollments.student_id = student_scores.student_id
+            where
+                student_enrollments.semester_id = $1 and
+                student_enrollments.subject_id = $2 and
+                not student_enrollments.student_enrollment_is_withdrawn
+            group by student_enrollments.student_id
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let upsert_statement = client
+        .prepare_typed_cached(
+            r##"
+            insert into student_grades (
+                semester_id,
+                subject_id,
+                student_id,
+                student_grade_total_score,
+                student_grade_computed_alphabet
+            ) values (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5
+            ) on conflict (semester_id, subject_id, student_id) do update set
+                student_grade_total_score = $4,
+                student_grade_computed_alphabet = $5,
+                student_grade_updated_timestamp = now()
+            "##,
+            &[
+                Type::TEXT,
+                Type::TEXT,
+                Type::TEXT,
+                Type::NUMERIC,
+                Type::TEXT,
+            ],
+        )
+        .await?;
+
+    let full_score = client
+        .query_one(&full_score_statement, &[&semester_id, &subject_id])
+        .await?
+        .try_get::<&str, Decimal>("full_score")?;
+
+    let scores = client
+        .query(&scores_statement, &[&semester_id, &subject_id])
+        .await?;
+
+    let mut student_ids = Vec::with_capacity(scores.len());
+
+    for row in scores {
+        let student_id = row.try_get::<&str, String>("student_id")?;
+        let total_score = calculate_total_score(row.try_get::<&str, Decimal>("score")?, full_score);
+
+        client
+            .execute(
+                &upsert_statement,
+                &[
+                    &semester_id,
+                    &subject_id,
+                    &student_id,
+                    &total_score,
+                    &map_to_alphabet(total_score, &grades),
+                ],
+            )
+            .await?;
+
+        student_ids.push(student_id);
+    }
+
+    client
+        .execute(
+            r##"
+            delete from student_grades
+            where
+                semester_id = $1 and
+                subject_id = $2 and
+                student_id <> all($3)
+            "##,
+            &[&semester_id, &subject_id, &student_ids],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Get the snapshot of grades of an opening subject.
+pub async fn get_subject_grades(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+) -> Result<Vec<SubjectGrade>, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_grades.student_id,
+                students.student_representative_id,
+                student_grades.student_grade_total_score as total_score,
+                student_grades.student_grade_computed_alphabet as computed_alphabet,
+                student_grades.student_grade_overridden_alphabet as overridden_alphabet,
+                student_grades.student_grade_override_reason as override_reason,
+                coalesce(
+                    student_grades.student_grade_overridden_alphabet,
+                    student_grades.student_grade_computed_alphabet
+                ) as alphabet
+            from student_grades
+            inner join students on student_grades.student_id = students.student_id
+            where
+                student_grades.semester_id = $1 and
+                student_grades.subject_id = $2
+            order by students.student_representative_id asc
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let grades = client
+        .query(&statement, &[&semester_id, &subject_id])
+        .await?
+        .iter()
+        .map(|g| SubjectGrade::try_from(g))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(grades)
+}
+
+/// Get the grade a student currently sees in an opening subject.
+pub async fn get_grade_alphabet(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    student_id: &str,
+) -> Result<String, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                coalesce(
+                    student_grade_overridden_alphabet,
+                    student_grade_computed_alphabet
+                ) as alphabet
+            from student_grades
+            where semester_id = $1 and subject_id = $2 and student_id = $3
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&statement, &[&semester_id, &subject_id, &student_id])
+        .await?;
+
+    match row {
+        Some(r) => Ok(r.try_get::<&str, String>("alphabet")?),
+        None => Err(HttpError::GradeNotFound),
+    }
+}
+
+/// Replace the grade of a student with the one given by the professor.
+pub async fn override_grade(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    student_id: &str,
+    alphabet: &str,
+    reason: &str,
+) -> Result<(), HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            update student_grades set
+                student_grade_overridden_alphabet = $4,
+                student_grade_override_reason = $5,
+                student_grade_updated_timestamp = now()
+            where semester_id = $1 and subject_id = $2 and student_id = $3
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let updated = client
+        .execute(
+            &statement,
+            &[&semester_id, &subject_id, &student_id, &alphabet, &reason],
+        )
+        .await?;
+
+    if updated == 0 {
+        return Err(HttpError::GradeNotFound);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use crate::services::grading_criterias::GradingCriteriaGrade;
+
+    use super::{calculate_total_score, is_valid_alphabet, map_to_alphabet};
+
+    fn grades() -> Vec<GradingCriteriaGrade> {
+        [("A", 80), ("B+", 75), ("B", 70), ("C", 60), ("D", 50)]
+            .iter()
+            .map(|(alphabet, minimum_score)| GradingCriteriaGrade {
+                alphabet: alphabet.to_string(),
+                minimum_score: Decimal::from(*minimum_score),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_calculate_total_score() {
+        assert_eq!(
+            calculate_total_score(Decimal::from(45), Decimal::from(60)),
+            Decimal::from(75)
+        );
+        assert_eq!(
+            calculate_total_score(Decimal::from(2), Decimal::from(3)),
+            Decimal::new(6667, 2)
+        );
+        assert_eq!(
+            calculate_total_score(Decimal::ZERO, Decimal::ZERO),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn test_map_to_alphabet() {
+        let grades = grades();
+
+        assert_eq!(map_to_alphabet(Decimal::from(100), &grades), "A");
+        assert_eq!(map_to_alphabet(Decimal::from(80), &grades), "A");
+        assert_eq!(map_to_alphabet(Decimal::new(7999, 2), &grades), "B+");
+        assert_eq!(map_to_alphabet(Decimal::from(50), &grades), "D");
+        // nothing reached
+        assert_eq!(map_to_alphabet(Decimal::new(4999, 2), &grades), "F");
+        assert_eq!(map_to_alphabet(Decimal::from(90), &[]), "F");
+    }
+
+    #[test]
+    fn test_is_valid_alphabet() {
+        let grades = grades();
+
+        assert!(is_valid_alphabet("B+", &grades));
+        assert!(is_valid_alphabet("F", &grades));
+        assert!(!is_valid_alphabet("C+", &grades));
+    }
+}
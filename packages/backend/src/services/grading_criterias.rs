@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+
+use deadpool_postgres::GenericClient;
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{constants::ID_LENGTH, errors::HttpError};
+
+/// One letter grade of a grading criteria, students get the first grade whose minimum score they
+/// reach.
+#[derive(Deserialize, Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct GradingCriteriaGrade {
+    #[schema(example = json!("B+"))]
+    pub alphabet: String,
+    #[schema(value_type = String, example = json!("75.00"))]
+    #[ts(type = "string")]
+    pub minimum_score: Decimal,
+}
+
+/// Validate grades of a grading criteria. Grades have to be ordered from the best grade to the
+/// worst grade, the minimum scores have to strictly decrease and no letter could be repeated.
+pub fn validate_grades(grades: &[GradingCriteriaGrade]) -> Result<(), HttpError> {
+    if grades.is_empty() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let mut alphabets = HashSet::new();
+
+    for grade in grades {
+        let alphabet = grade.alphabet.trim();
+
+        if alphabet.is_empty()
+            || grade.minimum_score.is_sign_negative()
+            || !alphabets.insert(alphabet.to_uppercase())
+        {
+            return Err(HttpError::InputValidationError);
+        }
+    }
+
+    if grades
+        .windows(2)
+        .any(|pair| pair[0].minimum_score <= pair[1].minimum_score)
+    {
+        return Err(HttpError::InputValidationError);
+    }
+
+    Ok(())
+}
+
+/// Insert a grading criteria owned by the user, returns the new `grading_criteria_id`. The grades
+/// have to be validated with [validate_grades] first.
+pub async fn insert_grading_criteria(
+    client: &impl GenericClient,
+    user_id: &str,
+    name: &str,
+    grades: &[GradingCriteriaGrade],
+) -> Result<String, HttpError> {
+    let grading_criteria_id = randoid::randoid!(ID_LENGTH);
+
+    let criteria_statement = client
+        .prepare_typed_cached(
+            r##"
+            insert into grading_criterias (
+                grading_criteria_id,
+                user_id,
+                grading_criteria_name,
+                grading_criteria_created_timestamp
+            ) values (
+                $1,
+                $2,
+                $3,
+                now()
+            )
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let grade_statement = client
+        .prepare_typed_cached(
+            r##"
+            insert into grading_criteria_grades (
+                grading_criteria_grade_id,
+                grading_criteria_id,
+                grading_criteria_grade_alphabet,
+                grading_criteria_grade_minimum_score
+            ) values (
+                $1,
+                $2,
+                $3,
+                $4
+            )
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT, Type::NUMERIC],
+        )
+        .await?;
+
+    client
+        .execute(
+            &criteria_statement,
+            &[&grading_criteria_id, &user_id, &name.trim()],
+        )
+        .await?;
+
+    for grade in grades {
+        client
+            .execute(
+                &grade_statement,
+                &[
+                    &randoid::randoid!(ID_LENGTH),
+                    &grading_criteria_id,
+                    &grade.alphabet.trim(),
+                    &grade.minimum_score,
+                ],
+            )
+            .await?;
+    }
+
+    Ok(grading_criteria_id)
+}
+
+/// Get the `user_id` who owns the grading criteria.
+pub async fn get_grading_criteria_owner(
+    client: &impl GenericClient,
+    grading_criteria_id: &str,
+) -> Result<String, HttpError> {
+    let row = client
+        .query_opt(
+            "select user_id from grading_criterias where grading_criteria_id = $1",
+            &[&grading_criteria_id],
+        )
+        .await?;
+
+    match row {
+        Some(r) => Ok(r.try_get::<&str, String>("user_id")?),
+        None => Err(HttpError::GradingCriteriaNotFound),
+    }
+}
+
+/// Get grades of a grading criteria from the best grade to the worst grade.
+pub async fn get_grading_criteria_grades(
+    client: &impl GenericClient,
+    grading_criteria_id: &str,
+) -> Result<Vec<GradingCriteriaGrade>, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                grading_criteria_grade_alphabet as alphabet,
+                grading_criteria_grade_minimum_score as minimum_score
+            from grading_criteria_grades
+            where grading_criteria_id = $1
+            order by grading_criteria_grade_minimum_score desc
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    let grades = client
+        .query(&statement, &[&grading_criteria_id])
+        .await?
+        .iter()
+        .map(|g| GradingCriteriaGrade::try_from(g))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(grades)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use super::{validate_grades, GradingCriteriaGrade};
+
+    fn grades(grades: &[(&str, i64)]) -> Vec<GradingCriteriaGrade> {
+        grades
+            .iter()
+            .map(|(alphabet, minimum_score)| GradingCriteriaGrade {
+                alphabet: alphabet.to_string(),
+                minimum_score: Decimal::from(*minimum_score),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_grades() {
+        assert!(validate_grades(&grades(&[
+            ("A", 80),
+            ("B+", 75),
+            ("B", 70),
+            ("C+", 65),
+            ("C", 60),
+            ("D+", 55),
+            ("D", 50),
+            ("F", 0)
+        ]))
+        .is_ok());
+
+        // empty
+        assert!(validate_grades(&[]).is_err());
+
+        // not decreasing
+        assert!(validate_grades(&grades(&[("A", 80), ("B", 80)])).is_err());
+        assert!(validate_grades(&grades(&[("B", 70), ("A", 80)])).is_err());
+
+        // duplicate letters, regardless of casing and whitespaces
+        assert!(validate_grades(&grades(&[("A", 80), ("a ", 70)])).is_err());
+
+        // blank letter and negative score
+        assert!(validate_grades(&grades(&[("A", 80), (" ", 70)])).is_err());
+        assert!(validate_grades(&grades(&[("A", 80), ("F", -1)])).is_err());
+    }
+}
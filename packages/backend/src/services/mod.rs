@@ -0,0 +1,36 @@
+/// Tuition calculation and invoices of students in each semester.
+pub mod billing;
+/// Progress of students towards the credit specifications of their majors.
+pub mod degree_audits;
+/// Checks used when a student enrolls in an opening subject, such as eligibility, capacity and
+/// time clashes with the rest of the student's enrollments.
+pub mod enrollments;
+/// Evaluations students give to the subjects they studied.
+pub mod evaluations;
+/// Assignments and scores of students in opening subjects.
+pub mod gradebook;
+/// Final grades of students computed from their scores and the grading criteria.
+pub mod grades;
+/// Grading criterias owned by professors and the grades in them.
+pub mod grading_criterias;
+/// Names of people in several languages.
+pub mod names;
+/// Notifications sent to users by the system.
+pub mod notifications;
+/// Signed payment events sent by the payment gateway.
+pub mod payments;
+/// Checks about the opening subjects professors teach.
+pub mod professors;
+/// Registration windows of semesters and checks whether students could enroll in them right now.
+pub mod registration_periods;
+/// Checks whether an opening subject's schedules clash with other opening subjects in the same
+/// semester, either by room or by professor, and whether the rooms can hold the subject capacity.
+pub mod schedule_conflicts;
+/// Reading students to sign up from csv and xlsx files.
+pub mod signup_imports;
+/// Signup of new students and validation of their information.
+pub mod signups;
+/// Profiles of students and checks about who could see their records.
+pub mod students;
+/// Transcripts of students and the gpa calculated from the grade points of letter grades.
+pub mod transcripts;
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{constants::DEFAULT_NAME_LANGUAGE, errors::HttpError};
+
+/// Name of a person in one language.
+#[derive(Deserialize, Serialize, ToSchema, TS, Clone)]
+#[ts(export)]
+pub struct PersonName {
+    /// ISO 639-1 language code.
+    #[schema(example = json!("en"))]
+    pub language: String,
+    pub first_name: String,
+    /// Empty when the person has no middle name.
+    #[serde(default)]
+    pub middle_name: String,
+    pub last_name: String,
+}
+
+/// Check whether a language code looks like an ISO 639-1 code, two lowercase ascii letters.
+pub fn is_language_code(language: &str) -> bool {
+    language.len() == 2 && language.chars().all(|c| c.is_ascii_lowercase())
+}
+
+/// Validate names of a person, there has to be at least one name and every language could only
+/// have one name. Names are trimmed and language codes are lowercased.
+pub fn normalize_names(names: &[PersonName]) -> Result<Vec<PersonName>, HttpError> {
+    if names.is_empty() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let mut languages = HashSet::new();
+    let mut normalized = Vec::with_capacity(names.len());
+
+    for name in names {
+        let name = PersonName {
+            language: name.language.trim().to_lowercase(),
+            first_name: name.first_name.trim().to_string(),
+            middle_name: name.middle_name.trim().to_string(),
+            last_name: name.last_name.trim().to_string(),
+        };
+
+        if !is_language_code(&name.language)
+            || name.first_name.is_empty()
+            || name.last_name.is_empty()
+            || !languages.insert(name.language.clone())
+        {
+            return Err(HttpError::InputValidationError);
+        }
+
+        normalized.push(name);
+    }
+
+    Ok(normalized)
+}
+
+/// Pick the name to show from names of a person in several languages. The name in the requested
+/// language is preferred, then the name in [DEFAULT_NAME_LANGUAGE], then whichever name comes
+/// first. Language codes are compared case-insensitively.
+pub fn pick_name(names: &[PersonName], language: Option<&str>) -> Option<PersonName> {
+    let find = |language: &str| {
+        names
+            .iter()
+            .find(|n| n.language.eq_ignore_ascii_case(language.trim()))
+    };
+
+    language
+        .and_then(find)
+        .or_else(|| find(DEFAULT_NAME_LANGUAGE))
+        .or_else(|| names.first())
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_language_code, normalize_names, pick_name, PersonName};
+
+    fn name(language: &str, first_name: &str, last_name: &str) -> PersonName {
+        PersonName {
+            language: language.to_string(),
+            first_name: first_name.to_string(),
+            middle_name: " ".to_string(),
+            last_name: last_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_is_language_code() {
+        assert!(is_language_code("en"));
+        assert!(is_language_code("th"));
+        assert!(!is_language_code("EN"));
+        assert!(!is_language_code("eng"));
+        assert!(!is_language_code(""));
+    }
+
+    #[test]
+    fn test_normalize_names() {
+        let names = normalize_names(&[
+            name(" EN ", " Somchai ", "Jaidee"),
+            name("th", "สมชาย", "ใจดี"),
+        ])
+        .unwrap();
+
+        assert_eq!(names[0].language, "en");
+        assert_eq!(names[0].first_name, "Somchai");
+        assert_eq!(names[0].middle_name, "");
+        assert_eq!(names[1].language, "th");
+
+        assert!(normalize_names(&[]).is_err());
+        // one name per language
+        assert!(normalize_names(&[name("en", "a", "b"), name("EN", "c", "d")]).is_err());
+        // blank names
+        assert!(normalize_names(&[name("en", " ", "b")]).is_err());
+        assert!(normalize_names(&[name("english", "a", "b")]).is_err());
+    }
+
+    #[test]
+    fn test_pick_name() {
+        let names = [name("th", "สมชาย", "ใจดี"), name("EN", "Somchai", "Jaidee")];
+
+        assert_eq!(pick_name(&names, Some("th")).unwrap().first_name, "สมชาย");
+        // falls back to english, regardless of casing
+        assert_eq!(pick_name(&names, Some("ja")).unwrap().first_name, "Somchai");
+        assert_eq!(pick_name(&names, None).unwrap().first_name, "Somchai");
+        // falls back to the first name
+        assert_eq!(
+            pick_name(&names[..1], Some("ja")).unwrap().first_name,
+            "สมชาย"
+        );
+        assert!(pick_name(&[], Some("en")).is_none());
+    }
+}
@@ -0,0 +1,38 @@
+use deadpool_postgres::GenericClient;
+use postgres_types::Type;
+
+use crate::{constants::ID_LENGTH, errors::HttpError};
+
+/// Send a notification to a user. The notification is stored in the same transaction as the
+/// action that caused it, so users never get notified about something that was rolled back.
+pub async fn notify(
+    client: &impl GenericClient,
+    user_id: &str,
+    content: &str,
+) -> Result<(), HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            insert into user_notifications (
+                user_notification_id,
+                user_id,
+                user_notification_content
+            ) values (
+                $1,
+                $2,
+                $3
+            )
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    client
+        .execute(
+            &statement,
+            &[&randoid::randoid!(ID_LENGTH), &user_id, &content],
+        )
+        .await?;
+
+    Ok(())
+}
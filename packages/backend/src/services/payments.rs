@@ -0,0 +1,108 @@
+use deadpool_postgres::GenericClient;
+use hmac::{Hmac, Mac};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use utoipa::ToSchema;
+
+use crate::errors::HttpError;
+
+use super::billing::pay_invoice;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Result of a payment reported by the payment gateway.
+#[derive(Deserialize, Serialize, ToSchema, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum PaymentStatus {
+    Succeeded,
+    Failed,
+}
+
+/// Event sent by the payment gateway to the webhook once a payment of an invoice finishes.
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct PaymentEvent {
+    /// `student_transaction_id` of the invoice.
+    pub transaction_id: String,
+    /// How much the gateway actually charged.
+    #[schema(value_type = String, example = json!("7500.00"))]
+    pub amount: Decimal,
+    pub status: PaymentStatus,
+}
+
+/// Sign a webhook payload with HMAC-SHA256, returns the signature as a lowercase hex string.
+pub fn sign_payload(secret: &[u8], payload: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("hmac accepts keys of any length");
+    mac.update(payload);
+
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Verify the hex signature of a webhook payload in constant time. Nothing is verified with an
+/// empty secret, since anyone could sign with it.
+pub fn verify_signature(secret: &[u8], payload: &[u8], signature: &str) -> bool {
+    if secret.is_empty() {
+        return false;
+    }
+
+    let signature = match hex::decode(signature) {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+
+    let mut mac = HmacSha256::new_from_slice(secret).expect("hmac accepts keys of any length");
+    mac.update(payload);
+
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// Verify and apply a signed payment event. Failed payments leave the invoice unpaid.
+pub async fn process_payment_event(
+    client: &impl GenericClient,
+    secret: &[u8],
+    payload: &[u8],
+    signature: &str,
+) -> Result<(), HttpError> {
+    if !verify_signature(secret, payload, signature) {
+        return Err(HttpError::InvalidWebhookSignature);
+    }
+
+    let event = serde_json::from_slice::<PaymentEvent>(payload)
+        .map_err(|_| HttpError::InputValidationError)?;
+
+    match event.status {
+        PaymentStatus::Succeeded => pay_invoice(client, &event.transaction_id, event.amount).await,
+        PaymentStatus::Failed => {
+            tracing::info!("payment of transaction {} failed", event.transaction_id);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sign_payload, verify_signature};
+
+    #[test]
+    fn test_verify_signature() {
+        let secret = b"webhook secret";
+        let payload = br#"{"transaction_id":"abc","amount":"7500.00","status":"succeeded"}"#;
+        let signature = sign_payload(secret, payload);
+
+        assert!(verify_signature(secret, payload, &signature));
+        assert!(verify_signature(secret, payload, &signature.to_uppercase()));
+
+        // tampered payload
+        assert!(!verify_signature(
+            secret,
+            br#"{"transaction_id":"abc","amount":"1.00","status":"succeeded"}"#,
+            &signature
+        ));
+        // wrong secret
+        assert!(!verify_signature(b"another secret", payload, &signature));
+        // not a hex string
+        assert!(!verify_signature(secret, payload, "not hex"));
+        // empty secret
+        assert!(!verify_signature(b"", payload, &sign_payload(b"", payload)));
+    }
+}
@@ -0,0 +1,30 @@
+use deadpool_postgres::GenericClient;
+use postgres_types::Type;
+
+use crate::errors::HttpError;
+
+/// Check whether the professor teaches an opening subject.
+pub async fn is_teaching(
+    client: &impl GenericClient,
+    semester_id: &str,
+    subject_id: &str,
+    professor_id: &str,
+) -> Result<bool, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                professor_id
+            from opening_subjects_in_semester_professors
+            where semester_id = $1 and subject_id = $2 and professor_id = $3
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&statement, &[&semester_id, &subject_id, &professor_id])
+        .await?;
+
+    Ok(row.is_some())
+}
@@ -0,0 +1,297 @@
+use deadpool_postgres::GenericClient;
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::errors::HttpError;
+
+/// Registration windows of a semester, every window starts inclusively and ends exclusively.
+#[derive(Deserialize, Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct RegistrationPeriods {
+    /// Early registration is only opened to students who are at least in this year of study.
+    #[schema(minimum = 1, example = json!(4))]
+    pub early_registration_minimum_study_year: i32,
+    #[serde(with = "time::serde::rfc3339")]
+    #[ts(type = "string")]
+    pub early_registration_start_timestamp: time::OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    #[ts(type = "string")]
+    pub early_registration_end_timestamp: time::OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    #[ts(type = "string")]
+    pub general_registration_start_timestamp: time::OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    #[ts(type = "string")]
+    pub general_registration_end_timestamp: time::OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    #[ts(type = "string")]
+    pub add_drop_start_timestamp: time::OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    #[ts(type = "string")]
+    pub add_drop_end_timestamp: time::OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    #[ts(type = "string")]
+    pub withdrawal_start_timestamp: time::OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    #[ts(type = "string")]
+    pub withdrawal_end_timestamp: time::OffsetDateTime,
+}
+
+/// Which registration window a semester is currently in.
+#[derive(Serialize, ToSchema, TS, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum RegistrationPeriod {
+    /// Only senior students could enroll and drop.
+    EarlyRegistration,
+    /// Every student could enroll and drop.
+    GeneralRegistration,
+    /// Every student could still enroll and drop.
+    AddDrop,
+    /// Students could not enroll anymore, dropping a subject withdraws from it and gets a "W".
+    Withdrawal,
+}
+
+impl RegistrationPeriod {
+    /// Whether students could enroll in or drop subjects without getting a "W".
+    pub fn is_enrollment_open(&self) -> bool {
+        !matches!(self, RegistrationPeriod::Withdrawal)
+    }
+}
+
+impl RegistrationPeriods {
+    /// Windows in the order they happen.
+    fn windows(
+        &self,
+    ) -> [(
+        RegistrationPeriod,
+        time::OffsetDateTime,
+        time::OffsetDateTime,
+    ); 4] {
+        [
+            (
+                RegistrationPeriod::EarlyRegistration,
+                self.early_registration_start_timestamp,
+                self.early_registration_end_timestamp,
+            ),
+            (
+                RegistrationPeriod::GeneralRegistration,
+                self.general_registration_start_timestamp,
+                self.general_registration_end_timestamp,
+            ),
+            (
+                RegistrationPeriod::AddDrop,
+                self.add_drop_start_timestamp,
+                self.add_drop_end_timestamp,
+            ),
+            (
+                RegistrationPeriod::Withdrawal,
+                self.withdrawal_start_timestamp,
+                self.withdrawal_end_timestamp,
+            ),
+        ]
+    }
+
+    /// Validate that every window ends after it starts and the windows do not overlap each other.
+    pub fn validate(&self) -> Result<(), HttpError> {
+        if self.early_registration_minimum_study_year < 1 {
+            return Err(HttpError::InputValidationError);
+        }
+
+        let windows = self.windows();
+
+        if windows.iter().any(|(_, start, end)| start >= end) {
+            return Err(HttpError::InputValidationError);
+        }
+
+        if windows.windows(2).any(|pair| pair[0].2 > pair[1].1) {
+            return Err(HttpError::InputValidationError);
+        }
+
+        Ok(())
+    }
+
+    /// Get the window `now` is in, returns `None` when the registration is closed.
+    pub fn get_period_at(&self, now: time::OffsetDateTime) -> Option<RegistrationPeriod> {
+        self.windows()
+            .into_iter()
+            .find(|(_, start, end)| *start <= now && now < *end)
+            .map(|(period, _, _)| period)
+    }
+}
+
+/// Get the registration windows of a semester, returns `None` when they are not configured yet.
+pub async fn get_registration_periods(
+    client: &impl GenericClient,
+    semester_id: &str,
+) -> Result<Option<RegistrationPeriods>, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                early_registration_minimum_study_year,
+                early_registration_start_timestamp,
+                early_registration_end_timestamp,
+                general_registration_start_timestamp,
+                general_registration_end_timestamp,
+                add_drop_start_timestamp,
+                add_drop_end_timestamp,
+                withdrawal_start_timestamp,
+                withdrawal_end_timestamp
+            from semester_registration_periods
+            where semester_id = $1
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    let row = client.query_opt(&statement, &[&semester_id]).await?;
+
+    match row {
+        Some(r) => Ok(Some(RegistrationPeriods::try_from(r)?)),
+        None => Ok(None),
+    }
+}
+
+/// Get the registration window a semester is currently in, semesters without configured
+/// registration windows are always closed.
+pub async fn get_current_registration_period(
+    client: &impl GenericClient,
+    semester_id: &str,
+) -> Result<RegistrationPeriod, HttpError> {
+    get_registration_periods(client, semester_id)
+        .await?
+        .and_then(|p| p.get_period_at(time::OffsetDateTime::now_utc()))
+        .ok_or(HttpError::RegistrationClosed)
+}
+
+/// Check whether the student could enroll in subjects of a semester right now. During early
+/// registration only students in the minimum study year or later could enroll.
+pub async fn check_enrollment_open(
+    client: &impl GenericClient,
+    semester_id: &str,
+    student_id: &str,
+) -> Result<(), HttpError> {
+    let periods = get_registration_periods(client, semester_id)
+        .await?
+        .ok_or(HttpError::RegistrationClosed)?;
+
+    match periods.get_period_at(time::OffsetDateTime::now_utc()) {
+        Some(RegistrationPeriod::EarlyRegistration) => {
+            let study_year = get_study_year(client, semester_id, student_id).await?;
+
+            if study_year < periods.early_registration_minimum_study_year {
+                return Err(HttpError::EarlyRegistrationNotAllowed);
+            }
+
+            Ok(())
+        }
+        Some(RegistrationPeriod::GeneralRegistration) | Some(RegistrationPeriod::AddDrop) => Ok(()),
+        Some(RegistrationPeriod::Withdrawal) | None => Err(HttpError::RegistrationClosed),
+    }
+}
+
+/// Get which year of study the student is in during a semester, students are in their first year
+/// during their first academic year.
+pub async fn get_study_year(
+    client: &impl GenericClient,
+    semester_id: &str,
+    student_id: &str,
+) -> Result<i32, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                (
+                    semester_academic_years.academic_year_anno_domini_year::int -
+                    first_academic_years.academic_year_anno_domini_year::int + 1
+                ) as study_year
+            from students
+            inner join academic_years as first_academic_years on
+                students.first_academic_year_id = first_academic_years.academic_year_id
+            cross join semesters
+            inner join academic_years as semester_academic_years on
+                semesters.academic_year_id = semester_academic_years.academic_year_id
+            where semesters.semester_id = $1 and students.student_id = $2
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&statement, &[&semester_id, &student_id])
+        .await?;
+
+    match row {
+        Some(r) => Ok(r.try_get::<&str, i32>("study_year")?),
+        None => Err(HttpError::SemesterNotFound),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::{RegistrationPeriod, RegistrationPeriods};
+
+    fn periods() -> RegistrationPeriods {
+        RegistrationPeriods {
+            early_registration_minimum_study_year: 4,
+            early_registration_start_timestamp: datetime!(2023-06-01 09:00 +7),
+            early_registration_end_timestamp: datetime!(2023-06-03 09:00 +7),
+            general_registration_start_timestamp: datetime!(2023-06-05 09:00 +7),
+            general_registration_end_timestamp: datetime!(2023-06-10 00:00 +7),
+            add_drop_start_timestamp: datetime!(2023-06-19 00:00 +7),
+            add_drop_end_timestamp: datetime!(2023-07-03 00:00 +7),
+            withdrawal_start_timestamp: datetime!(2023-07-03 00:00 +7),
+            withdrawal_end_timestamp: datetime!(2023-09-01 00:00 +7),
+        }
+    }
+
+    #[test]
+    fn test_get_period_at() {
+        let periods = periods();
+
+        assert_eq!(periods.get_period_at(datetime!(2023-05-31 00:00 +7)), None);
+        assert_eq!(
+            periods.get_period_at(datetime!(2023-06-01 09:00 +7)),
+            Some(RegistrationPeriod::EarlyRegistration)
+        );
+        // between early and general registration
+        assert_eq!(periods.get_period_at(datetime!(2023-06-04 00:00 +7)), None);
+        assert_eq!(
+            periods.get_period_at(datetime!(2023-06-09 23:59 +7)),
+            Some(RegistrationPeriod::GeneralRegistration)
+        );
+        assert_eq!(
+            periods.get_period_at(datetime!(2023-06-20 00:00 +7)),
+            Some(RegistrationPeriod::AddDrop)
+        );
+        // add/drop ends exactly when withdrawal starts
+        assert_eq!(
+            periods.get_period_at(datetime!(2023-07-03 00:00 +7)),
+            Some(RegistrationPeriod::Withdrawal)
+        );
+        assert_eq!(periods.get_period_at(datetime!(2023-09-01 00:00 +7)), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(periods().validate().is_ok());
+
+        let mut empty_window = periods();
+        empty_window.add_drop_end_timestamp = empty_window.add_drop_start_timestamp;
+        assert!(empty_window.validate().is_err());
+
+        let mut overlapping = periods();
+        overlapping.general_registration_start_timestamp = datetime!(2023-06-02 09:00 +7);
+        assert!(overlapping.validate().is_err());
+
+        let mut no_minimum_year = periods();
+        no_minimum_year.early_registration_minimum_study_year = 0;
+        assert!(no_minimum_year.validate().is_err());
+    }
+}
@@ -0,0 +1,366 @@
+use deadpool_postgres::GenericClient;
+use postgres_types::Type;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{database::DayOfWeek, errors::HttpError};
+
+/// One schedule slot of an opening subject that is about to be written.
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct ProposedSchedule {
+    pub room_id: String,
+    #[schema(value_type = String, example = json!("monday"))]
+    pub day_of_week: DayOfWeek,
+    #[schema(value_type = String, example = json!("09:00"))]
+    #[serde(with = "crate::constants::requests::time_of_day")]
+    pub start_time_of_day: time::Time,
+    #[schema(value_type = String, example = json!("12:00"))]
+    #[serde(with = "crate::constants::requests::time_of_day")]
+    pub end_time_of_day: time::Time,
+}
+
+/// An opening subject that is about to be written into a semester.
+pub struct ProposedOpeningSubject<'a> {
+    pub semester_id: &'a str,
+    pub subject_id: &'a str,
+    pub subject_capacity: i32,
+    pub professor_ids: &'a [String],
+    pub schedules: &'a [ProposedSchedule],
+}
+
+/// Kind of problem found while checking an opening subject.
+#[derive(Serialize, ToSchema, TS, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ScheduleConflictKind {
+    /// The room is already booked by another subject at an overlapping time.
+    RoomDoubleBooking,
+    /// One of the professors already teaches another subject at an overlapping time.
+    ProfessorDoubleBooking,
+    /// The room has less seats than the subject capacity.
+    RoomCapacity,
+    /// The room does not exist.
+    UnknownRoom,
+}
+
+/// A single conflict found by [find_schedule_conflicts].
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct ScheduleConflict {
+    pub kind: ScheduleConflictKind,
+    pub room_id: String,
+    #[ts(optional)]
+    pub professor_id: Option<String>,
+    /// The subject that is already booked at the same time.
+    #[ts(optional)]
+    pub conflicting_subject_id: Option<String>,
+    /// Human readable explanation of the conflict.
+    pub message: String,
+}
+
+/// Returns `true` when the two half open time ranges `[start, end)` overlap each other. Classes
+/// that end at exactly the same time as another class starts are not overlapping.
+pub fn time_ranges_overlap(
+    first: (time::Time, time::Time),
+    second: (time::Time, time::Time),
+) -> bool {
+    first.0 < second.1 && second.0 < first.1
+}
+
+/// Validate that every proposed schedule ends after it starts, and that the proposed schedules
+/// neither overlap each other nor use the same room twice, a subject is only stored once per room.
+pub fn validate_schedules(schedules: &[ProposedSchedule]) -> Result<(), HttpError> {
+    if schedules
+        .iter()
+        .any(|s| s.start_time_of_day >= s.end_time_of_day)
+    {
+        return Err(HttpError::InputValidationError);
+    }
+
+    for (i, schedule) in schedules.iter().enumerate() {
+        for other in schedules[i + 1..].iter() {
+            let overlaps = schedule.day_of_week == other.day_of_week
+                && time_ranges_overlap(
+                    (schedule.start_time_of_day, schedule.end_time_of_day),
+                    (other.start_time_of_day, other.end_time_of_day),
+                );
+
+            if schedule.room_id == other.room_id || overlaps {
+                return Err(HttpError::InputValidationError);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Find every room double booking, professor double booking and room capacity problem of the
+/// given opening subject against the other opening subjects in the same semester. Rows of the
+/// opening subject itself are ignored so the function can be used to check updates.
+pub async fn find_schedule_conflicts(
+    client: &impl GenericClient,
+    proposal: &ProposedOpeningSubject<'_>,
+) -> Result<Vec<ScheduleConflict>, HttpError> {
+    let mut conflicts = Vec::new();
+
+    let room_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                room_id,
+                room_name,
+                room_capacity
+            from rooms
+            where room_id = $1
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    let room_schedules_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                subject_id,
+                start_time_of_day,
+                end_time_of_day
+            from opening_subjects_in_semester_schedules
+            where
+                semester_id = $1 and
+                room_id = $2 and
+                subject_id <> $3 and
+                day_of_week = $4
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let professor_schedules_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                opening_subjects_in_semester_professors.professor_id,
+                opening_subjects_in_semester_schedules.subject_id,
+                opening_subjects_in_semester_schedules.start_time_of_day,
+                opening_subjects_in_semester_schedules.end_time_of_day
+            from opening_subjects_in_semester_professors
+            inner join opening_subjects_in_semester_schedules on
+                opening_subjects_in_semester_professors.semester_id = opening_subjects_in_semester_schedules.semester_id and
+                opening_subjects_in_semester_professors.subject_id = opening_subjects_in_semester_schedules.subject_id
+            where
+                opening_subjects_in_semester_professors.semester_id = $1 and
+                opening_subjects_in_semester_professors.subject_id <> $2 and
+                opening_subjects_in_semester_professors.professor_id = any($3) and
+                opening_subjects_in_semester_schedules.day_of_week = $4
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::TEXT_ARRAY],
+        )
+        .await?;
+
+    for schedule in proposal.schedules {
+        let proposed_range = (schedule.start_time_of_day, schedule.end_time_of_day);
+
+        let room = client
+            .query_opt(&room_statement, &[&schedule.room_id])
+            .await?;
+
+        match room {
+            None => {
+                conflicts.push(ScheduleConflict {
+                    kind: ScheduleConflictKind::UnknownRoom,
+                    room_id: schedule.room_id.clone(),
+                    professor_id: None,
+                    conflicting_subject_id: None,
+                    message: format!("room {} does not exist", schedule.room_id),
+                });
+
+                continue;
+            }
+            Some(room) => {
+                let room_capacity = room.try_get::<&str, i32>("room_capacity")?;
+
+                if room_capacity < proposal.subject_capacity {
+                    conflicts.push(ScheduleConflict {
+                        kind: ScheduleConflictKind::RoomCapacity,
+                        room_id: schedule.room_id.clone(),
+                        professor_id: None,
+                        conflicting_subject_id: None,
+                        message: format!(
+                            "room {} only has {} seats but the subject accepts {} students",
+                            room.try_get::<&str, String>("room_name")?,
+                            room_capacity,
+                            proposal.subject_capacity
+                        ),
+                    });
+                }
+            }
+        }
+
+        let booked_rooms = client
+            .query(
+                &room_schedules_statement,
+                &[
+                    &proposal.semester_id,
+                    &schedule.room_id,
+                    &proposal.subject_id,
+                    &schedule.day_of_week,
+                ],
+            )
+            .await?;
+
+        for booked in booked_rooms {
+            let booked_range = (
+                booked.try_get::<&str, time::Time>("start_time_of_day")?,
+                booked.try_get::<&str, time::Time>("end_time_of_day")?,
+            );
+
+            if time_ranges_overlap(proposed_range, booked_range) {
+                let conflicting_subject_id = booked.try_get::<&str, String>("subject_id")?;
+
+                conflicts.push(ScheduleConflict {
+                    kind: ScheduleConflictKind::RoomDoubleBooking,
+                    room_id: schedule.room_id.clone(),
+                    professor_id: None,
+                    message: format!(
+                        "room {} is already booked by subject {} from {} to {}",
+                        schedule.room_id, conflicting_subject_id, booked_range.0, booked_range.1
+                    ),
+                    conflicting_subject_id: Some(conflicting_subject_id),
+                });
+            }
+        }
+
+        if proposal.professor_ids.is_empty() {
+            continue;
+        }
+
+        let booked_professors = client
+            .query(
+                &professor_schedules_statement,
+                &[
+                    &proposal.semester_id,
+                    &proposal.subject_id,
+                    &proposal.professor_ids,
+                    &schedule.day_of_week,
+                ],
+            )
+            .await?;
+
+        for booked in booked_professors {
+            let booked_range = (
+                booked.try_get::<&str, time::Time>("start_time_of_day")?,
+                booked.try_get::<&str, time::Time>("end_time_of_day")?,
+            );
+
+            if time_ranges_overlap(proposed_range, booked_range) {
+                let professor_id = booked.try_get::<&str, String>("professor_id")?;
+                let conflicting_subject_id = booked.try_get::<&str, String>("subject_id")?;
+
+                conflicts.push(ScheduleConflict {
+                    kind: ScheduleConflictKind::ProfessorDoubleBooking,
+                    room_id: schedule.room_id.clone(),
+                    message: format!(
+                        "professor {} already teaches subject {} from {} to {}",
+                        professor_id, conflicting_subject_id, booked_range.0, booked_range.1
+                    ),
+                    professor_id: Some(professor_id),
+                    conflicting_subject_id: Some(conflicting_subject_id),
+                });
+            }
+        }
+    }
+
+    Ok(conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::time;
+
+    use crate::database::DayOfWeek;
+
+    use super::{time_ranges_overlap, validate_schedules, ProposedSchedule};
+
+    fn schedule(
+        room_id: &str,
+        day_of_week: DayOfWeek,
+        start_time_of_day: time::Time,
+        end_time_of_day: time::Time,
+    ) -> ProposedSchedule {
+        ProposedSchedule {
+            room_id: room_id.to_string(),
+            day_of_week,
+            start_time_of_day,
+            end_time_of_day,
+        }
+    }
+
+    #[test]
+    fn test_time_ranges_overlap() {
+        // same range
+        assert!(time_ranges_overlap(
+            (time!(09:00), time!(12:00)),
+            (time!(09:00), time!(12:00))
+        ));
+
+        // partially overlapping from both sides
+        assert!(time_ranges_overlap(
+            (time!(09:00), time!(12:00)),
+            (time!(11:00), time!(13:00))
+        ));
+        assert!(time_ranges_overlap(
+            (time!(11:00), time!(13:00)),
+            (time!(09:00), time!(12:00))
+        ));
+
+        // one range inside another
+        assert!(time_ranges_overlap(
+            (time!(09:00), time!(16:00)),
+            (time!(10:00), time!(11:00))
+        ));
+
+        // back to back classes
+        assert!(!time_ranges_overlap(
+            (time!(09:00), time!(12:00)),
+            (time!(12:00), time!(15:00))
+        ));
+        assert!(!time_ranges_overlap(
+            (time!(13:00), time!(15:00)),
+            (time!(09:00), time!(12:00))
+        ));
+    }
+
+    #[test]
+    fn test_validate_schedules() {
+        assert!(validate_schedules(&[
+            schedule("SC1-101", DayOfWeek::Monday, time!(09:00), time!(12:00)),
+            schedule("SC1-201", DayOfWeek::Monday, time!(12:00), time!(15:00)),
+            schedule("SC1-301", DayOfWeek::Tuesday, time!(09:00), time!(12:00)),
+        ])
+        .is_ok());
+
+        // ends before it starts
+        assert!(validate_schedules(&[schedule(
+            "SC1-101",
+            DayOfWeek::Monday,
+            time!(12:00),
+            time!(09:00)
+        )])
+        .is_err());
+
+        // overlapping classes of the same subject in different rooms
+        assert!(validate_schedules(&[
+            schedule("SC1-101", DayOfWeek::Monday, time!(09:00), time!(12:00)),
+            schedule("SC1-201", DayOfWeek::Monday, time!(11:00), time!(13:00)),
+        ])
+        .is_err());
+
+        // the same room on different days
+        assert!(validate_schedules(&[
+            schedule("SC1-101", DayOfWeek::Monday, time!(09:00), time!(12:00)),
+            schedule("SC1-101", DayOfWeek::Thursday, time!(09:00), time!(12:00)),
+        ])
+        .is_err());
+    }
+}
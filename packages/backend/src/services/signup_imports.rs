@@ -0,0 +1,276 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::Cursor,
+};
+
+use calamine::{Data, Reader, Xlsx};
+use csv::StringRecord;
+use serde::Serialize;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::errors::HttpError;
+
+use super::signups::{validate_student, StudentSignupRequestBodyInner};
+
+/// Format of an imported signup file.
+#[derive(Debug, PartialEq)]
+pub enum ImportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl ImportFormat {
+    /// Guess the format from the name of the uploaded file.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let extension = file_name.rsplit_once('.')?.1.to_lowercase();
+
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "xlsx" => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+}
+
+/// Problems found in one row of an imported file.
+#[derive(Serialize, ToSchema, TS, Debug)]
+#[ts(export)]
+pub struct ImportRowError {
+    /// Row number in the file, the header is row 1.
+    #[ts(type = "number")]
+    pub row: usize,
+    pub errors: Vec<String>,
+}
+
+/// A row of an imported file that passed validation.
+pub struct ImportRow {
+    pub row: usize,
+    pub student: StudentSignupRequestBodyInner,
+}
+
+/// Read the header and the rows of a csv file.
+pub fn read_csv(file: &[u8]) -> Result<(StringRecord, Vec<StringRecord>), HttpError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(file);
+
+    let headers = reader
+        .headers()
+        .map_err(|_| HttpError::InputValidationError)?
+        .clone();
+    let records = reader
+        .records()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| HttpError::InputValidationError)?;
+
+    Ok((headers, records))
+}
+
+/// Excel stores dates as days since 1899-12-30.
+fn excel_serial_to_date(serial: f64) -> Option<time::Date> {
+    let epoch = time::Date::from_calendar_date(1899, time::Month::December, 30).ok()?;
+
+    epoch.checked_add(time::Duration::days(serial.floor() as i64))
+}
+
+fn cell_to_string(cell: &Data) -> String {
+    match cell {
+        Data::DateTime(d) => excel_serial_to_date(d.as_f64())
+            .map(|d| d.to_string())
+            .unwrap_or_default(),
+        Data::DateTimeIso(d) => d.get(..10).unwrap_or(d).to_string(),
+        Data::Empty => String::new(),
+        _ => cell.to_string().trim().to_string(),
+    }
+}
+
+/// Read the header and the rows of the first worksheet of an xlsx file, every cell is turned into
+/// text the same way it would be written in a csv file.
+pub fn read_xlsx(file: &[u8]) -> Result<(StringRecord, Vec<StringRecord>), HttpError> {
+    let mut workbook = Xlsx::new(Cursor::new(file)).map_err(|_| HttpError::InputValidationError)?;
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or(HttpError::NoData)?
+        .map_err(|_| HttpError::InputValidationError)?;
+
+    let mut rows = range
+        .rows()
+        .map(|r| r.iter().map(cell_to_string).collect::<StringRecord>());
+    let headers = rows.next().ok_or(HttpError::NoData)?;
+
+    Ok((headers, rows.collect()))
+}
+
+/// Turn rows of an imported file into students, and validate every one of them. Rows that could
+/// not be read or are invalid are reported with their row numbers instead of failing the whole
+/// file. Blank rows are skipped.
+pub fn parse_rows(
+    headers: &StringRecord,
+    records: &[StringRecord],
+    today: time::Date,
+) -> (Vec<ImportRow>, Vec<ImportRowError>) {
+    let mut rows = vec![];
+    let mut errors = vec![];
+
+    for (i, record) in records.iter().enumerate() {
+        // the header is the first row.
+        let row = i + 2;
+
+        if record.iter().all(|c| c.trim().is_empty()) {
+            continue;
+        }
+
+        match record.deserialize::<StudentSignupRequestBodyInner>(Some(headers)) {
+            Ok(student) => {
+                let row_errors = validate_student(&student, today);
+
+                if row_errors.is_empty() {
+                    rows.push(ImportRow { row, student });
+                } else {
+                    errors.push(ImportRowError {
+                        row,
+                        errors: row_errors,
+                    });
+                }
+            }
+            Err(e) => errors.push(ImportRowError {
+                row,
+                errors: vec![e.to_string()],
+            }),
+        }
+    }
+
+    (rows, errors)
+}
+
+/// Drop rows whose generated email is already taken, either by an existing user in
+/// `taken_emails` or by an earlier row of the file. `emails` are the emails of `rows` in the same
+/// order, every dropped row is reported in `errors`, which stays sorted by row.
+pub fn drop_conflicting_emails(
+    rows: Vec<ImportRow>,
+    emails: &[String],
+    taken_emails: &HashSet<String>,
+    errors: &mut Vec<ImportRowError>,
+) -> Vec<ImportRow> {
+    let mut first_rows = HashMap::new();
+    let mut kept = Vec::with_capacity(rows.len());
+
+    for (row, email) in rows.into_iter().zip(emails) {
+        if taken_emails.contains(email) {
+            errors.push(ImportRowError {
+                row: row.row,
+                errors: vec![format!("email {} is already taken by another user", email)],
+            });
+        } else if let Some(first_row) = first_rows.get(email.as_str()) {
+            errors.push(ImportRowError {
+                row: row.row,
+                errors: vec![format!(
+                    "email {} is the same as the email of row {}",
+                    email, first_row
+                )],
+            });
+        } else {
+            first_rows.insert(email.as_str(), row.row);
+            kept.push(row);
+        }
+    }
+
+    errors.sort_by_key(|e| e.row);
+
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use time::macros::date;
+
+    use crate::services::signups::generate_student_email;
+
+    use super::{
+        drop_conflicting_emails, excel_serial_to_date, parse_rows, read_csv, ImportFormat,
+    };
+
+    #[test]
+    fn test_import_format() {
+        assert_eq!(
+            ImportFormat::from_file_name("students.CSV"),
+            Some(ImportFormat::Csv)
+        );
+        assert_eq!(
+            ImportFormat::from_file_name("2565.students.xlsx"),
+            Some(ImportFormat::Xlsx)
+        );
+        assert_eq!(ImportFormat::from_file_name("students.xls"), None);
+        assert_eq!(ImportFormat::from_file_name("students"), None);
+    }
+
+    #[test]
+    fn test_excel_serial_to_date() {
+        assert_eq!(excel_serial_to_date(38124.0), Some(date!(2004 - 05 - 17)));
+    }
+
+    #[test]
+    fn test_parse_rows() {
+        let file = "\
+student_nid,student_english_first_name,student_english_middle_name,student_english_last_name,student_birthdate,student_previous_school_name,student_previous_school_gpa
+8365079019452,Somchai,,Jaidee,2004-05-17,Montfort College,3.75
+8365079019451,Somsri,,Jaidee,2004-05-17,Montfort College,4.5
+,,,,,,
+1101700230708,Somsak,,Jaidee,17/05/2004,Montfort College,3.00
+";
+        let (headers, records) = read_csv(file.as_bytes()).unwrap();
+        let (rows, errors) = parse_rows(&headers, &records, date!(2022 - 06 - 01));
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].row, 2);
+        assert_eq!(rows[0].student.student_english_first_name, "Somchai");
+
+        assert_eq!(errors.len(), 2);
+        // bad checksum and gpa out of range are both reported
+        assert_eq!(errors[0].row, 3);
+        assert_eq!(errors[0].errors.len(), 2);
+        // the blank row 4 is skipped, the date could not be read
+        assert_eq!(errors[1].row, 5);
+        assert_eq!(errors[1].errors.len(), 1);
+    }
+
+    #[test]
+    fn test_drop_conflicting_emails() {
+        let file = "\
+student_nid,student_english_first_name,student_english_middle_name,student_english_last_name,student_birthdate,student_previous_school_name,student_previous_school_gpa
+8365079019452,Somchai,,Jaidee,2004-05-17,Montfort College,3.75
+1101700230708,Somchai,,Jaiyen,2004-05-17,Montfort College,3.00
+8365079019451,Somsri,,Jaidee,2004-05-17,Montfort College,4.5
+3100400123456,Somsak,,Jaidee,2004-05-17,Montfort College,3.20
+5200600345679,Pim,,Chaiyo,2004-05-17,Montfort College,3.50
+";
+        let (headers, records) = read_csv(file.as_bytes()).unwrap();
+        let (rows, mut errors) = parse_rows(&headers, &records, date!(2022 - 06 - 01));
+        let emails = rows
+            .iter()
+            .map(|r| generate_student_email(&r.student, 2565))
+            .collect::<Vec<_>>();
+        let taken_emails = HashSet::from(["SomsakJ2565@gmail.com".to_string()]);
+
+        let rows = drop_conflicting_emails(rows, &emails, &taken_emails, &mut errors);
+
+        // the first row keeps the email, the same email in row 3 and the taken one are reported
+        assert_eq!(rows.iter().map(|r| r.row).collect::<Vec<_>>(), vec![2, 6]);
+        assert_eq!(
+            errors.iter().map(|e| e.row).collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+        assert_eq!(
+            errors[0].errors,
+            vec!["email SomchaiJ2565@gmail.com is the same as the email of row 2"]
+        );
+        assert_eq!(
+            errors[2].errors,
+            vec!["email SomsakJ2565@gmail.com is already taken by another user"]
+        );
+    }
+}
@@ -0,0 +1,545 @@
+use std::collections::HashSet;
+
+use argon2::{password_hash::SaltString, Argon2, PasswordHasher};
+use deadpool_postgres::GenericClient;
+use ger_from_row::{FromRow, ToRow};
+use postgres_types::Type;
+use rand_core::OsRng;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    constants::{AD_BE_YEAR_DIFFERENCE, ID_LENGTH},
+    database::{insert_rows, Role},
+    errors::HttpError,
+};
+
+/// One student to sign up, either given in the json body or read from a row of an imported file.
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct StudentSignupRequestBodyInner {
+    #[schema(example = json!("8365079019452"))]
+    pub student_nid: String,
+    pub student_english_first_name: String,
+    #[serde(default)]
+    pub student_english_middle_name: String,
+    pub student_english_last_name: String,
+    #[schema(value_type = String, format = Date)]
+    #[serde(with = "time::Date")]
+    pub student_birthdate: time::Date,
+    pub student_previous_school_name: String,
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(value_type = f32, example = json!(3.99))]
+    pub student_previous_school_gpa: Decimal,
+}
+
+/// Where the students in a signup batch study.
+pub struct SignupTarget<'a> {
+    pub major_id: &'a str,
+    pub major_representative_id: &'a str,
+    pub professor_id: &'a str,
+    pub first_academic_year_id: &'a str,
+}
+
+/// What is needed to generate `student_representative_id` of a signup batch.
+pub struct SignupContext {
+    /// First academic year of the students in buddhist era.
+    pub first_academic_year_be: i32,
+    /// Running number of the last id given in the major and the year. This is only a preview,
+    /// [signup_students] takes the numbers it uses from the sequence.
+    pub latest_student_number: usize,
+}
+
+#[derive(Deserialize, FromRow)]
+struct LatestStudentIndex {
+    student_representative_id: String,
+}
+
+/// Validate a thai national id, which is 13 digits with the last digit as the checksum.
+pub fn is_valid_nid(nid: &str) -> bool {
+    if nid.len() != 13 || !nid.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let digits = nid.bytes().map(|b| (b - b'0') as u32).collect::<Vec<_>>();
+    let sum = digits[..12]
+        .iter()
+        .enumerate()
+        .map(|(i, d)| d * (13 - i as u32))
+        .sum::<u32>();
+
+    (11 - sum % 11) % 10 == digits[12]
+}
+
+/// Validate a student that is about to be signed up, returns every problem found so they could be
+/// reported together. An empty list means the student is valid.
+pub fn validate_student(student: &StudentSignupRequestBodyInner, today: time::Date) -> Vec<String> {
+    let mut errors = vec![];
+
+    if !is_valid_nid(&student.student_nid) {
+        errors.push("student_nid is not a valid national id".to_string());
+    }
+
+    if student.student_english_first_name.trim().is_empty() {
+        errors.push("student_english_first_name is empty".to_string());
+    }
+
+    if student.student_english_last_name.trim().is_empty() {
+        errors.push("student_english_last_name is empty".to_string());
+    }
+
+    if student.student_birthdate >= today {
+        errors.push("student_birthdate has to be in the past".to_string());
+    }
+
+    if student.student_previous_school_name.trim().is_empty() {
+        errors.push("student_previous_school_name is empty".to_string());
+    }
+
+    if student.student_previous_school_gpa.is_sign_negative()
+        || student.student_previous_school_gpa > Decimal::from(4)
+    {
+        errors.push("student_previous_school_gpa has to be between 0 and 4".to_string());
+    }
+
+    errors
+}
+
+/// Email of a new student account, made from the english first name, the initial of the english
+/// last name and the first academic year in buddhist era.
+pub fn generate_student_email(
+    student: &StudentSignupRequestBodyInner,
+    first_academic_year_be: i32,
+) -> String {
+    format!(
+        "{}{}{}@gmail.com",
+        student.student_english_first_name.trim(),
+        student
+            .student_english_last_name
+            .trim()
+            .chars()
+            .nth(0)
+            .unwrap_or('s'),
+        first_academic_year_be,
+    )
+}
+
+/// Which of `emails` already belong to users.
+pub async fn find_taken_emails(
+    client: &impl GenericClient,
+    emails: &[String],
+) -> Result<HashSet<String>, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            "select user_email from users where user_email = any($1)",
+            &[Type::TEXT_ARRAY],
+        )
+        .await?;
+
+    client
+        .query(&statement, &[&emails])
+        .await?
+        .iter()
+        .map(|r| Ok(r.try_get::<&str, String>("user_email")?))
+        .collect()
+}
+
+/// Create `student_representative_id` from the first academic year in buddhist era, the major and
+/// the running number of the student.
+pub fn generate_representative_id(
+    first_academic_year_be: i32,
+    major_representative_id: &str,
+    student_index: usize,
+) -> Result<String, HttpError> {
+    if student_index >= 10000 {
+        tracing::error!("new student_index overflow to {}", student_index);
+        return Err(HttpError::InternalServerError {
+            cause: "new student_index integer overflow to 10000".to_string(),
+        });
+    }
+
+    Ok(format!(
+        "{}{}{:0>4}",
+        first_academic_year_be, major_representative_id, student_index
+    ))
+}
+
+/// Create `student_representative_id` of `count` students whose running numbers start from
+/// `first_student_index`.
+pub fn generate_representative_ids(
+    first_academic_year_be: i32,
+    major_representative_id: &str,
+    first_student_index: usize,
+    count: usize,
+) -> Result<Vec<String>, HttpError> {
+    (first_student_index..first_student_index + count)
+        .map(|i| generate_representative_id(first_academic_year_be, major_representative_id, i))
+        .collect()
+}
+
+/// Get the first academic year and the latest running number of students in the major.
+pub async fn get_signup_context(
+    client: &impl GenericClient,
+    target: &SignupTarget<'_>,
+) -> Result<SignupContext, HttpError> {
+    let first_academic_year = client
+        .query_opt(
+            r##"
+            select
+                academic_year_anno_domini_year
+            from academic_years
+            where academic_year_id = $1
+            "##,
+            &[&target.first_academic_year_id],
+        )
+        .await?
+        .ok_or(HttpError::InputValidationError)?
+        .try_get::<&str, String>("academic_year_anno_domini_year")?
+        .parse::<i32>()
+        .map_err(|_| HttpError::InternalServerError {
+            cause: "row \"first_academic_year\" is not a year".to_string(),
+        })?;
+
+    let latest_student_index = client
+        .query_opt(
+            r##"
+            select
+                student_representative_id
+            from students
+            where major_id = $1 and first_academic_year_id = $2
+            order by student_representative_id desc
+            limit 1"##,
+            &[&target.major_id, &target.first_academic_year_id],
+        )
+        .await?
+        .map(LatestStudentIndex::try_from)
+        .transpose()?;
+
+    let latest_student_number = latest_student_index
+        .and_then(|i| {
+            i.student_representative_id
+                .get(i.student_representative_id.len().saturating_sub(4)..)
+                .and_then(|n| n.parse::<usize>().ok())
+        })
+        .unwrap_or(0usize);
+
+    let sequence_number = client
+        .query_opt(
+            r##"
+            select
+                student_representative_id_sequence_last_number
+            from student_representative_id_sequences
+            where major_id = $1 and first_academic_year_id = $2
+            "##,
+            &[&target.major_id, &target.first_academic_year_id],
+        )
+        .await?
+        .map(|r| r.try_get::<&str, i32>("student_representative_id_sequence_last_number"))
+        .transpose()?
+        .unwrap_or(0) as usize;
+
+    Ok(SignupContext {
+        first_academic_year_be: first_academic_year + AD_BE_YEAR_DIFFERENCE as i32,
+        // students signed up before the sequence existed are not counted in it.
+        latest_student_number: latest_student_number.max(sequence_number),
+    })
+}
+
+/// Take `count` running numbers from the sequence of the major and the year, returns the first
+/// one. Numbers start after `latest_student_number` when the sequence is behind it, such as after
+/// students are inserted without going through the sequence. The sequence row stays locked until the transaction ends, so concurrent signups wait for
+/// each other instead of taking the same numbers, and the numbers are given back when the
+/// transaction is rolled back.
+pub async fn reserve_student_numbers(
+    client: &impl GenericClient,
+    target: &SignupTarget<'_>,
+    latest_student_number: usize,
+    count: usize,
+) -> Result<usize, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            insert into student_representative_id_sequences (
+                major_id,
+                first_academic_year_id,
+                student_representative_id_sequence_last_number
+            ) values (
+                $1,
+                $2,
+                $3::int4 + $4::int4
+            ) on conflict (major_id, first_academic_year_id) do update set
+                student_representative_id_sequence_last_number = greatest(
+                    student_representative_id_sequences.student_representative_id_sequence_last_number,
+                    $3::int4
+                ) + $4::int4
+            returning student_representative_id_sequence_last_number
+            "##,
+            &[Type::TEXT, Type::TEXT, Type::INT4, Type::INT4],
+        )
+        .await?;
+
+    let last_number = client
+        .query_one(
+            &statement,
+            &[
+                &target.major_id,
+                &target.first_academic_year_id,
+                &(latest_student_number as i32),
+                &(count as i32),
+            ],
+        )
+        .await?
+        .try_get::<&str, i32>("student_representative_id_sequence_last_number")?;
+
+    Ok(last_number as usize + 1 - count)
+}
+
+#[derive(ToRow)]
+#[torow(table = "users")]
+pub(crate) struct NewUser<'a> {
+    pub user_id: &'a str,
+    pub user_username: String,
+    pub user_email: String,
+    pub user_password: String,
+    pub user_role: Role,
+    pub user_birthdate: time::Date,
+}
+
+#[derive(ToRow)]
+#[torow(table = "students")]
+pub(crate) struct NewStudent<'a> {
+    pub student_id: &'a str,
+    pub student_representative_id: String,
+    pub student_nid: &'a str,
+    pub student_previous_school_name: &'a str,
+    pub student_previous_school_gpa: Decimal,
+    pub major_id: &'a str,
+    pub professor_id: &'a str,
+    pub first_academic_year_id: &'a str,
+}
+
+#[derive(ToRow)]
+#[torow(table = "student_names")]
+pub(crate) struct NewStudentName<'a> {
+    pub student_name_id: String,
+    pub student_id: &'a str,
+    pub student_name_language: &'a str,
+    pub student_first_name: &'a str,
+    pub student_middle_name: &'a str,
+    pub student_last_name: &'a str,
+}
+
+/// Insert the users, the students and the english names of new students, returns the new
+/// `student_id` of every student in the same order. The password of each new account is its
+/// `student_representative_id`.
+pub async fn insert_students(
+    client: &impl GenericClient,
+    argon2_context: &Argon2<'_>,
+    target: &SignupTarget<'_>,
+    first_academic_year_be: i32,
+    students: &[&StudentSignupRequestBodyInner],
+    representative_ids: &[String],
+) -> Result<Vec<String>, HttpError> {
+    let new_student_ids = students
+        .iter()
+        .map(|_| randoid::randoid!(ID_LENGTH))
+        .collect::<Vec<_>>();
+
+    let mut users = Vec::with_capacity(students.len());
+    let mut new_students = Vec::with_capacity(students.len());
+    let mut names = Vec::with_capacity(students.len());
+
+    for ((student, representative_id), new_student_id) in students
+        .iter()
+        .zip(representative_ids.iter())
+        .zip(new_student_ids.iter())
+    {
+        let new_student_email = generate_student_email(student, first_academic_year_be);
+        let salt = SaltString::generate(&mut OsRng);
+        let new_student_account_password =
+            argon2_context.hash_password(representative_id.as_bytes(), &salt)?;
+
+        users.push(NewUser {
+            user_id: new_student_id,
+            user_username: randoid::randoid!(10),
+            user_email: new_student_email,
+            user_password: new_student_account_password.to_string(),
+            user_role: Role::Student,
+            user_birthdate: student.student_birthdate,
+        });
+        new_students.push(NewStudent {
+            student_id: new_student_id,
+            student_representative_id: representative_id.clone(),
+            student_nid: &student.student_nid,
+            student_previous_school_name: student.student_previous_school_name.trim(),
+            student_previous_school_gpa: student.student_previous_school_gpa,
+            major_id: target.major_id,
+            professor_id: target.professor_id,
+            first_academic_year_id: target.first_academic_year_id,
+        });
+        names.push(NewStudentName {
+            student_name_id: randoid::randoid!(ID_LENGTH),
+            student_id: new_student_id,
+            student_name_language: "en",
+            student_first_name: student.student_english_first_name.trim(),
+            student_middle_name: student.student_english_middle_name.trim(),
+            student_last_name: student.student_english_last_name.trim(),
+        });
+    }
+
+    insert_rows(client, &users).await?;
+    insert_rows(client, &new_students).await?;
+    insert_rows(client, &names).await?;
+
+    Ok(new_student_ids)
+}
+
+/// Sign up every student of a batch, returns their `student_representative_id` in the same
+/// order. This has to be called in a transaction so a batch is never partially signed up.
+pub async fn signup_students(
+    client: &impl GenericClient,
+    argon2_context: &Argon2<'_>,
+    target: &SignupTarget<'_>,
+    context: &SignupContext,
+    students: &[&StudentSignupRequestBodyInner],
+) -> Result<Vec<String>, HttpError> {
+    let first_student_number = reserve_student_numbers(
+        client,
+        target,
+        context.latest_student_number,
+        students.len(),
+    )
+    .await?;
+    let representative_ids = generate_representative_ids(
+        context.first_academic_year_be,
+        target.major_representative_id,
+        first_student_number,
+        students.len(),
+    )?;
+
+    insert_students(
+        client,
+        argon2_context,
+        target,
+        context.first_academic_year_be,
+        students,
+        &representative_ids,
+    )
+    .await?;
+
+    Ok(representative_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use time::macros::date;
+
+    use crate::{
+        fixtures::{fresh_schema, Fixtures},
+        startup::load_postgres_config,
+    };
+
+    use super::{
+        generate_representative_id, generate_representative_ids, get_signup_context, is_valid_nid,
+        reserve_student_numbers, validate_student, SignupTarget, StudentSignupRequestBodyInner,
+    };
+
+    #[test]
+    fn test_is_valid_nid() {
+        assert!(is_valid_nid("8365079019452"));
+        assert!(is_valid_nid("1101700230708"));
+        // wrong checksum
+        assert!(!is_valid_nid("1101700230705"));
+        // wrong length and not digits
+        assert!(!is_valid_nid("110170023070"));
+        assert!(!is_valid_nid("11017002307a8"));
+    }
+
+    #[test]
+    fn test_validate_student() {
+        let mut student = StudentSignupRequestBodyInner {
+            student_nid: "8365079019452".to_string(),
+            student_english_first_name: "Somchai".to_string(),
+            student_english_middle_name: "".to_string(),
+            student_english_last_name: "Jaidee".to_string(),
+            student_birthdate: date!(2004 - 05 - 17),
+            student_previous_school_name: "Montfort College".to_string(),
+            student_previous_school_gpa: Decimal::new(375, 2),
+        };
+        let today = date!(2022 - 06 - 01);
+
+        assert!(validate_student(&student, today).is_empty());
+
+        student.student_nid = "8365079019451".to_string();
+        student.student_birthdate = date!(2022 - 06 - 02);
+        student.student_previous_school_gpa = Decimal::new(401, 2);
+
+        assert_eq!(validate_student(&student, today).len(), 3);
+    }
+
+    #[test]
+    fn test_generate_representative_id() {
+        assert_eq!(
+            generate_representative_id(2565, "31", 7).unwrap(),
+            "2565310007"
+        );
+        assert!(generate_representative_id(2565, "31", 10000).is_err());
+    }
+
+    #[test]
+    fn test_generate_representative_ids() {
+        assert_eq!(
+            generate_representative_ids(2565, "31", 9998, 2).unwrap(),
+            vec!["2565319998", "2565319999"]
+        );
+        assert!(generate_representative_ids(2565, "31", 9998, 3).is_err());
+        assert!(generate_representative_ids(2565, "31", 1, 0)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_reserve_student_numbers_behind_students() {
+        let pool = fresh_schema(
+            &load_postgres_config(),
+            "test_reserve_student_numbers_behind_students",
+            &Fixtures::parse(include_str!("../../fixtures/demo.toml")).unwrap(),
+        )
+        .await
+        .unwrap();
+        let client = pool.get().await.unwrap();
+
+        let target = SignupTarget {
+            major_id: "major-computer-science",
+            major_representative_id: "31",
+            professor_id: "professor-somsak",
+            first_academic_year_id: "academic-year-2022",
+        };
+
+        // two students of the major and the year are in the fixtures, the sequence forgets them.
+        client
+            .execute(
+                "update student_representative_id_sequences set student_representative_id_sequence_last_number = 0 where major_id = $1 and first_academic_year_id = $2",
+                &[&target.major_id, &target.first_academic_year_id],
+            )
+            .await
+            .unwrap();
+
+        let context = get_signup_context(&client, &target).await.unwrap();
+        assert_eq!(context.latest_student_number, 2);
+
+        let first_number =
+            reserve_student_numbers(&client, &target, context.latest_student_number, 3)
+                .await
+                .unwrap();
+        assert_eq!(first_number, 3);
+
+        // the sequence is ahead of the students now, the next numbers follow it.
+        let first_number =
+            reserve_student_numbers(&client, &target, context.latest_student_number, 1)
+                .await
+                .unwrap();
+        assert_eq!(first_number, 6);
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use deadpool_postgres::GenericClient;
+use postgres_types::Type;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{database::Role, errors::HttpError};
+
+use super::names::{pick_name, PersonName};
+
+/// Profile of a student with the name in the requested language.
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct StudentProfile {
+    pub student_id: String,
+    pub student_representative_id: String,
+    pub student_nid: String,
+    pub student_previous_school_name: String,
+    #[schema(value_type = String, example = json!("3.75"))]
+    #[ts(type = "string")]
+    pub student_previous_school_gpa: Decimal,
+    pub major_id: String,
+    pub major_name: String,
+    /// `professor_id` of the advisor.
+    pub professor_id: String,
+    pub first_academic_year_id: String,
+    #[ts(optional)]
+    pub name: Option<PersonName>,
+}
+
+/// Check whether the user could see the records of a student, such as transcripts. Students could
+/// only see their own records, professors could see records of their advisees and admins could
+/// see every record.
+pub async fn check_student_access(
+    client: &impl GenericClient,
+    user_id: &str,
+    role: &Role,
+    student_id: &str,
+) -> Result<(), HttpError> {
+    match role {
+        Role::Admin => Ok(()),
+        Role::Student if user_id == student_id => Ok(()),
+        Role::Student => Err(HttpError::Forbidden),
+        Role::Professor => {
+            let row = client
+                .query_opt(
+                    "select professor_id from students where student_id = $1",
+                    &[&student_id],
+                )
+                .await?
+                .ok_or(HttpError::StudentNotFound)?;
+
+            if row.try_get::<&str, &str>("professor_id")? != user_id {
+                return Err(HttpError::Forbidden);
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Get names of students in every language, grouped by `student_id`.
+pub async fn get_student_names(
+    client: &impl GenericClient,
+    student_ids: &[String],
+) -> Result<HashMap<String, Vec<PersonName>>, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                student_id,
+                student_name_language,
+                student_first_name,
+                student_middle_name,
+                student_last_name
+            from student_names
+            where student_id = any($1)
+            order by student_name_language asc
+            "##,
+            &[Type::TEXT_ARRAY],
+        )
+        .await?;
+
+    let mut names = HashMap::<String, Vec<PersonName>>::new();
+
+    for row in client.query(&statement, &[&student_ids]).await? {
+        names
+            .entry(row.try_get("student_id")?)
+            .or_default()
+            .push(PersonName {
+                language: row.try_get("student_name_language")?,
+                first_name: row.try_get("student_first_name")?,
+                middle_name: row.try_get("student_middle_name")?,
+                last_name: row.try_get("student_last_name")?,
+            });
+    }
+
+    Ok(names)
+}
+
+/// Get the profile of a student, the name is picked with [pick_name].
+pub async fn get_student_profile(
+    client: &impl GenericClient,
+    student_id: &str,
+    language: Option<&str>,
+) -> Result<StudentProfile, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                students.student_id,
+                students.student_representative_id,
+                students.student_nid,
+                students.student_previous_school_name,
+                students.student_previous_school_gpa,
+                students.major_id,
+                majors.major_name,
+                students.professor_id,
+                students.first_academic_year_id
+            from students
+            inner join majors on students.major_id = majors.major_id
+            where students.student_id = $1
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    let row = client
+        .query_opt(&statement, &[&student_id])
+        .await?
+        .ok_or(HttpError::StudentNotFound)?;
+
+    let names = get_student_names(client, &[student_id.to_string()]).await?;
+
+    Ok(StudentProfile {
+        student_id: row.try_get("student_id")?,
+        student_representative_id: row.try_get("student_representative_id")?,
+        student_nid: row.try_get("student_nid")?,
+        student_previous_school_name: row.try_get("student_previous_school_name")?,
+        student_previous_school_gpa: row.try_get("student_previous_school_gpa")?,
+        major_id: row.try_get("major_id")?,
+        major_name: row.try_get("major_name")?,
+        professor_id: row.try_get("professor_id")?,
+        first_academic_year_id: row.try_get("first_academic_year_id")?,
+        name: names.get(student_id).and_then(|n| pick_name(n, language)),
+    })
+}
@@ -0,0 +1,451 @@
+use std::collections::{HashMap, HashSet};
+
+use deadpool_postgres::GenericClient;
+use ger_from_row::FromRow;
+use postgres_types::Type;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{constants::WITHDRAWN_GRADE_ALPHABET, errors::HttpError};
+
+/// Grade points of a letter grade.
+#[derive(Deserialize, Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct GradePointMapping {
+    #[schema(example = json!("B+"))]
+    pub alphabet: String,
+    #[schema(value_type = String, example = json!("3.50"))]
+    #[ts(type = "string")]
+    pub point: Decimal,
+}
+
+/// A subject the student has a released grade in, or has withdrawn from.
+#[derive(Serialize, ToSchema, TS, FromRow)]
+#[ts(export)]
+pub struct TranscriptSubject {
+    pub subject_id: String,
+    pub subject_name: String,
+    #[ts(type = "number")]
+    pub credit: i32,
+    pub alphabet: String,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct TranscriptSemester {
+    pub semester_id: String,
+    pub subjects: Vec<TranscriptSubject>,
+    /// Credits counted towards the gpa.
+    #[ts(type = "number")]
+    pub credit: i32,
+    /// Not exists when no subject in the semester counts towards the gpa.
+    #[schema(value_type = Option<String>, example = json!("3.25"))]
+    #[ts(optional, type = "string")]
+    pub gpa: Option<Decimal>,
+}
+
+#[derive(Serialize, ToSchema, TS)]
+#[ts(export)]
+pub struct Transcript {
+    pub student_id: String,
+    pub student_representative_id: String,
+    #[ts(optional)]
+    pub student_name: Option<String>,
+    pub major_name: String,
+    /// Semesters from the earliest to the latest.
+    pub semesters: Vec<TranscriptSemester>,
+    /// Credits counted towards the gpax.
+    #[ts(type = "number")]
+    pub credit: i32,
+    #[schema(value_type = Option<String>, example = json!("3.25"))]
+    #[ts(optional, type = "string")]
+    pub gpax: Option<Decimal>,
+}
+
+#[derive(FromRow)]
+struct TranscriptRow {
+    semester_id: String,
+    subject_id: String,
+    subject_name: String,
+    credit: i32,
+    alphabet: String,
+}
+
+/// Validate grade point mappings that are about to replace the current ones. Letters could not be
+/// blank or repeated, and points have to fit in `numeric(3, 2)`, from 0 to less than 10.
+pub fn validate_grade_point_mappings(mappings: &[GradePointMapping]) -> Result<(), HttpError> {
+    if mappings.is_empty() {
+        return Err(HttpError::InputValidationError);
+    }
+
+    let mut alphabets = HashSet::new();
+
+    for mapping in mappings {
+        let alphabet = mapping.alphabet.trim();
+
+        if alphabet.is_empty()
+            || alphabet == WITHDRAWN_GRADE_ALPHABET
+            || mapping.point.is_sign_negative()
+            || mapping.point.round_dp(2) >= Decimal::TEN
+            || !alphabets.insert(alphabet)
+        {
+            return Err(HttpError::InputValidationError);
+        }
+    }
+
+    Ok(())
+}
+
+/// Calculate the credits that count towards the gpa and the gpa of subjects rounded to 2 decimal
+/// places. Subjects whose letter grade has no grade points are left out.
+pub fn calculate_gpa<'a>(
+    subjects: impl IntoIterator<Item = &'a TranscriptSubject>,
+    points: &HashMap<String, Decimal>,
+) -> (i32, Option<Decimal>) {
+    let mut credit = 0;
+    let mut total_point = Decimal::ZERO;
+
+    for subject in subjects {
+        if let Some(point) = points.get(&subject.alphabet) {
+            credit += subject.credit;
+            total_point += point * Decimal::from(subject.credit);
+        }
+    }
+
+    if credit <= 0 {
+        return (credit, None);
+    }
+
+    (
+        credit,
+        Some((total_point / Decimal::from(credit)).round_dp(2)),
+    )
+}
+
+/// Get grade points of every letter grade.
+pub async fn get_grade_point_mappings(
+    client: &impl GenericClient,
+) -> Result<Vec<GradePointMapping>, HttpError> {
+    let statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                grade_point_mapping_alphabet as alphabet,
+                grade_point_mapping_point as point
+            from grade_point_mappings
+            order by grade_point_mapping_point desc
+            "##,
+            &[],
+        )
+        .await?;
+
+    let mappings = client
+        .query(&statement, &[])
+        .await?
+        .iter()
+        .map(|m| GradePointMapping::try_from(m))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(mappings)
+}
+
+/// Get the transcript of a student with every released grade and every withdrawn subject,
+/// grades that are not released yet are left out.
+pub async fn get_transcript(
+    client: &impl GenericClient,
+    student_id: &str,
+) -> Result<Transcript, HttpError> {
+    let student_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                students.student_representative_id,
+                majors.major_name,
+                (
+                    select
+                        concat_ws(
+                            ' ',
+                            student_names.student_first_name,
+                            nullif(student_names.student_middle_name, ''),
+                            student_names.student_last_name
+                        )
+                    from student_names
+                    where student_names.student_id = students.student_id
+                    order by student_names.student_name_language = 'en' desc
+                    limit 1
+                ) as student_name
+            from students
+            inner join majors on students.major_id = majors.major_id
+            where students.student_id = $1
+            "##,
+            &[Type::TEXT],
+        )
+        .await?;
+
+    let subjects_statement = client
+        .prepare_typed_cached(
+            r##"
+            select
+                graded_subjects.semester_id,
+                graded_subjects.subject_id,
+                subjects.subject_name,
+                subjects.subject_credit as credit,
+                graded_subjects.alphabet
+            from (
+                select
+                    student_grades.semester_id,
+                    student_grades.subject_id,
+                    coalesce(
+                        student_grades.student_grade_overridden_alphabet,
+                        student_grades.student_grade_computed_alphabet
+                    ) as alphabet
+                from student_grades
+                inner join opening_subjects_in_semester_subject_descriptions on
+                    student_grades.semester_id = opening_subjects_in_semester_subject_descriptions.semester_id and
+                    student_grades.subject_id = opening_subjects_in_semester_subject_descriptions.subject_id
+                where
+                    student_grades.student_id = $1 and
+                    opening_subjects_in_semester_subject_descriptions.is_grade_released
+                union all
+                select
+                    semester_id,
+                    subject_id,
+                    $2 as alphabet
+                from student_enrollments
+                where
+                    student_id = $1 and
+                    student_enrollment_is_withdrawn
+            ) as graded_subjects
+            inner join subjects on graded_subjects.subject_id = subjects.subject_id
+            inner join semesters on graded_subjects.semester_id = semesters.semester_id
+            order by
+                semesters.semester_start_timestamp asc,
+                graded_subjects.semester_id asc,
+                graded_subjects.subject_id asc
+            "##,
+            &[Type::TEXT, Type::TEXT],
+        )
+        .await?;
+
+    let student = client
+        .query_opt(&student_statement, &[&student_id])
+        .await?
+        .ok_or(HttpError::StudentNotFound)?;
+
+    let points = get_grade_point_mappings(client)
+        .await?
+        .into_iter()
+        .map(|m| (m.alphabet, m.point))
+        .collect::<HashMap<_, _>>();
+
+    let rows = client
+        .query(
+            &subjects_statement,
+            &[&student_id, &WITHDRAWN_GRADE_ALPHABET],
+        )
+        .await?
+        .iter()
+        .map(|r| TranscriptRow::try_from(r))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut semesters: Vec<TranscriptSemester> = vec![];
+
+    for row in rows {
+        let subject = TranscriptSubject {
+            subject_id: row.subject_id,
+            subject_name: row.subject_name,
+            credit: row.credit,
+            alphabet: row.alphabet,
+        };
+
+        match semesters.last_mut() {
+            Some(s) if s.semester_id == row.semester_id => s.subjects.push(subject),
+            _ => semesters.push(TranscriptSemester {
+                semester_id: row.semester_id,
+                subjects: vec![subject],
+                credit: 0,
+                gpa: None,
+            }),
+        }
+    }
+
+    for semester in semesters.iter_mut() {
+        (semester.credit, semester.gpa) = calculate_gpa(&semester.subjects, &points);
+    }
+
+    let (credit, gpax) = calculate_gpa(semesters.iter().flat_map(|s| &s.subjects), &points);
+
+    Ok(Transcript {
+        student_id: student_id.to_string(),
+        student_representative_id: student.try_get("student_representative_id")?,
+        student_name: student.try_get("student_name")?,
+        major_name: student.try_get("major_name")?,
+        semesters,
+        credit,
+        gpax,
+    })
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn format_gpa(gpa: &Option<Decimal>) -> String {
+    match gpa {
+        Some(g) => format!("{:.2}", g),
+        None => "-".to_string(),
+    }
+}
+
+/// Render a transcript into a standalone HTML document meant to be printed.
+pub fn render_transcript_html(transcript: &Transcript) -> String {
+    let mut html = String::new();
+
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!(
+        "<title>Transcript of {}</title>\n",
+        escape_html(&transcript.student_representative_id)
+    ));
+    html.push_str(concat!(
+        "<style>\n",
+        "body { font-family: serif; margin: 2em; }\n",
+        "table { width: 100%; border-collapse: collapse; margin-bottom: 1em; }\n",
+        "th, td { border: 1px solid #000; padding: 0.25em 0.5em; text-align: left; }\n",
+        "@media print { section { page-break-inside: avoid; } }\n",
+        "</style>\n",
+        "</head>\n<body>\n",
+    ));
+
+    html.push_str("<h1>Official Transcript</h1>\n<dl>\n");
+    html.push_str(&format!(
+        "<dt>Student ID</dt><dd>{}</dd>\n",
+        escape_html(&transcript.student_representative_id)
+    ));
+    if let Some(name) = &transcript.student_name {
+        html.push_str(&format!("<dt>Name</dt><dd>{}</dd>\n", escape_html(name)));
+    }
+    html.push_str(&format!(
+        "<dt>Major</dt><dd>{}</dd>\n</dl>\n",
+        escape_html(&transcript.major_name)
+    ));
+
+    for semester in &transcript.semesters {
+        html.push_str(&format!(
+            "<section>\n<h2>Semester {}</h2>\n<table>\n",
+            escape_html(&semester.semester_id)
+        ));
+        html.push_str("<tr><th>Subject</th><th>Name</th><th>Credit</th><th>Grade</th></tr>\n");
+
+        for subject in &semester.subjects {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&subject.subject_id),
+                escape_html(&subject.subject_name),
+                subject.credit,
+                escape_html(&subject.alphabet)
+            ));
+        }
+
+        html.push_str(&format!(
+            "</table>\n<p>Credit: {} GPA: {}</p>\n</section>\n",
+            semester.credit,
+            format_gpa(&semester.gpa)
+        ));
+    }
+
+    html.push_str(&format!(
+        "<p><strong>Total credit: {} GPAX: {}</strong></p>\n</body>\n</html>\n",
+        transcript.credit,
+        format_gpa(&transcript.gpax)
+    ));
+
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rust_decimal::Decimal;
+
+    use super::{
+        calculate_gpa, escape_html, validate_grade_point_mappings, GradePointMapping,
+        TranscriptSubject,
+    };
+
+    fn subject(credit: i32, alphabet: &str) -> TranscriptSubject {
+        TranscriptSubject {
+            subject_id: "261497".to_string(),
+            subject_name: "Special Topics".to_string(),
+            credit,
+            alphabet: alphabet.to_string(),
+        }
+    }
+
+    fn points() -> HashMap<String, Decimal> {
+        [("A", 400), ("B+", 350), ("B", 300), ("C", 200), ("F", 0)]
+            .iter()
+            .map(|(alphabet, point)| (alphabet.to_string(), Decimal::new(*point, 2)))
+            .collect()
+    }
+
+    #[test]
+    fn test_calculate_gpa() {
+        let points = points();
+
+        assert_eq!(
+            calculate_gpa(&[subject(3, "A"), subject(3, "B")], &points),
+            (6, Some(Decimal::new(350, 2)))
+        );
+        // withdrawn subjects do not count
+        assert_eq!(
+            calculate_gpa(&[subject(3, "A"), subject(3, "W")], &points),
+            (3, Some(Decimal::new(400, 2)))
+        );
+        // failed subjects count
+        assert_eq!(
+            calculate_gpa(
+                &[subject(3, "B+"), subject(1, "F"), subject(2, "C")],
+                &points
+            ),
+            (6, Some(Decimal::new(242, 2)))
+        );
+        assert_eq!(calculate_gpa(&[subject(3, "W")], &points), (0, None));
+        assert_eq!(calculate_gpa(&[], &points), (0, None));
+    }
+
+    #[test]
+    fn test_validate_grade_point_mappings() {
+        let mapping = |alphabet: &str, point: i64| GradePointMapping {
+            alphabet: alphabet.to_string(),
+            point: Decimal::from(point),
+        };
+
+        assert!(validate_grade_point_mappings(&[mapping("A", 4), mapping("F", 0)]).is_ok());
+        assert!(validate_grade_point_mappings(&[]).is_err());
+        assert!(validate_grade_point_mappings(&[mapping("A", 4), mapping("A ", 3)]).is_err());
+        assert!(validate_grade_point_mappings(&[mapping("A", -1)]).is_err());
+        assert!(validate_grade_point_mappings(&[mapping("A", 10)]).is_err());
+        // rounded to 10.00 by the column.
+        assert!(validate_grade_point_mappings(&[GradePointMapping {
+            alphabet: "A".to_string(),
+            point: Decimal::new(9999, 3),
+        }])
+        .is_err());
+        assert!(validate_grade_point_mappings(&[mapping("W", 0)]).is_err());
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html(r#"<script>alert("x & y")</script>"#),
+            "&lt;script&gt;alert(&quot;x &amp; y&quot;)&lt;/script&gt;"
+        );
+    }
+}
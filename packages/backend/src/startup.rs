@@ -1,10 +1,17 @@
-use deadpool_postgres::{Config, ManagerConfig, RecyclingMethod};
+use deadpool_postgres::{Config, ManagerConfig, Pool, RecyclingMethod};
 use opentelemetry::{global, runtime::Tokio, sdk::propagation::TraceContextPropagator};
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_bunyan_formatter::{BunyanFormattingLayer, JsonStorageLayer};
 use tracing_subscriber::{prelude::__tracing_subscriber_SubscriberExt, EnvFilter, Registry};
 
-use crate::constants::APP_NAME;
+use crate::{
+    constants::APP_NAME,
+    fixtures::{self, Fixtures},
+    migrations,
+};
+
+const USAGE: &str =
+    "usage: ger migrate [up | down <version>]\n       ger fixtures (load | reset) <file>";
 
 /// Load postgres config from environment variables
 pub fn load_postgres_config() -> Config {
@@ -34,6 +41,92 @@ pub fn load_postgres_config() -> Config {
     postgres_config
 }
 
+/// Apply the migrations the database does not have yet, the server does not start with a schema
+/// that differs from the migrations.
+pub async fn apply_migrations(pool: &Pool) {
+    let mut client = pool
+        .get()
+        .await
+        .expect("cannot get a postgres connection to apply the migrations");
+
+    let applied = migrations::migrate(&mut client)
+        .await
+        .unwrap_or_else(|e| panic!("cannot apply the migrations: {}", e));
+
+    for migration in applied {
+        tracing::info!("applied migration {}_{}", migration.version, migration.name);
+    }
+}
+
+/// Print the migrations a command applied or reverted.
+fn print_migrations(changed: Vec<&migrations::Migration>, action: &str) {
+    if changed.is_empty() {
+        println!("the database is up to date");
+    }
+    for migration in changed {
+        println!(
+            "{} migration {}_{}",
+            action, migration.version, migration.name
+        );
+    }
+}
+
+/// Run a command instead of the server,
+///
+/// - `ger migrate` applies the migrations, `ger migrate down <version>` reverts the ones newer
+///   than the version.
+/// - `ger fixtures load <file>` applies the migrations and loads the fixtures of the file,
+///   `ger fixtures reset <file>` reverts every migration first so the database only has the
+///   fixtures in it.
+pub async fn run_command(pool: &Pool, args: &[String]) -> Result<(), String> {
+    let args = args.iter().map(|a| a.as_str()).collect::<Vec<_>>();
+
+    let mut client = pool
+        .get()
+        .await
+        .map_err(|e| format!("cannot get a postgres connection: {}", e))?;
+
+    match args[..] {
+        ["migrate"] | ["migrate", "up"] => {
+            let applied = migrations::migrate(&mut client)
+                .await
+                .map_err(|e| e.to_string())?;
+            print_migrations(applied, "applied");
+        }
+        ["migrate", "down", version] => {
+            let version = version.parse::<i32>().map_err(|_| USAGE.to_string())?;
+            let reverted = migrations::rollback(&mut client, version)
+                .await
+                .map_err(|e| e.to_string())?;
+            print_migrations(reverted, "reverted");
+        }
+        ["fixtures", action @ ("load" | "reset"), file] => {
+            let toml = std::fs::read_to_string(file)
+                .map_err(|e| format!("cannot read {}: {}", file, e))?;
+            let fixtures = Fixtures::parse(&toml).map_err(|e| e.to_string())?;
+
+            if action == "reset" {
+                let reverted = migrations::rollback(&mut client, 0)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                print_migrations(reverted, "reverted");
+            }
+            let applied = migrations::migrate(&mut client)
+                .await
+                .map_err(|e| e.to_string())?;
+            print_migrations(applied, "applied");
+
+            fixtures::load(&mut client, &fixtures)
+                .await
+                .map_err(|e| e.to_string())?;
+            println!("loaded fixtures {}", file);
+        }
+        _ => return Err(USAGE.to_string()),
+    }
+
+    Ok(())
+}
+
 /// initialize telemetry settings
 pub fn init_telemetry() -> WorkerGuard {
     global::set_text_map_propagator(TraceContextPropagator::new());
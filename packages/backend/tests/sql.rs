@@ -0,0 +1,19 @@
+//! Statements of the handlers and services checked against the schema the migrations build,
+//! without a database. `build.rs` writes the schema, and integration tests run in the directory
+//! of the package so the source directory is relative to it.
+
+#[test]
+fn statements_match_migrations() {
+    let problems =
+        ger_sql_check::check_dir(concat!(env!("OUT_DIR"), "/schema.sql"), "src").unwrap();
+
+    assert!(
+        problems.is_empty(),
+        "statements do not match the migrations\n{}",
+        problems
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+}
@@ -0,0 +1,220 @@
+//! Generates the `database` module of the backend from the schema its migrations build, a struct
+//! for every table and an enum for every enum type, and the same types in typescript for the
+//! faker. The migrations are embedded in the backend with the `migrations` module generated here
+//! as well.
+//!
+//! The backend runs it from its build script,
+//!
+//! ```
+//! let migrations = ger_codegen::Migrations::read("migrations")?;
+//! let database = ger_codegen::Database::parse(&migrations.schema()?)?.rename("t_user_role", "Role");
+//! std::fs::write(out_dir.join("database.rs"), database.rust()?)?;
+//! std::fs::write(out_dir.join("migrations.rs"), migrations.rust())?;
+//! ```
+//!
+//! and the binary writes the files by hand,
+//!
+//! ```sh
+//! cargo run -p ger-codegen -- packages/backend/migrations \
+//!     --rename t_user_role=Role \
+//!     --rust packages/backend/database.rs \
+//!     --typescript packages/faker/src/database.ts
+//! ```
+
+mod migrations;
+mod rust;
+mod typescript;
+
+use std::collections::HashMap;
+
+use ger_sql_check::sqlparser::ast::{ColumnOption, Statement, UserDefinedTypeRepresentation};
+
+pub use migrations::{Migration, Migrations};
+
+/// A postgres enum type with its labels in order.
+#[derive(Debug)]
+pub struct Enum {
+    pub name: String,
+    pub labels: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct Column {
+    pub name: String,
+    /// Name of the type without its precision, such as `numeric` for `numeric(6, 2)`.
+    pub data_type: String,
+    pub not_null: bool,
+}
+
+#[derive(Debug)]
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<Column>,
+}
+
+/// Tables and enum types of a schema.
+#[derive(Debug, Default)]
+pub struct Database {
+    pub enums: Vec<Enum>,
+    pub tables: Vec<Table>,
+    names: HashMap<String, String>,
+}
+
+impl Database {
+    /// Read every table and enum type of a schema, as they are after every `alter table` and
+    /// `drop` of it.
+    pub fn parse(sql: &str) -> Result<Database, String> {
+        let mut database = Database::default();
+
+        for statement in ger_sql_check::definitions(sql)? {
+            match statement {
+                Statement::CreateType {
+                    name,
+                    representation: UserDefinedTypeRepresentation::Enum { labels },
+                } => database.enums.push(Enum {
+                    name: name.to_string().to_lowercase(),
+                    labels: labels.into_iter().map(|l| l.value).collect(),
+                }),
+                Statement::CreateTable(table) => database.tables.push(Table {
+                    name: table.name.to_string().to_lowercase(),
+                    columns: table
+                        .columns
+                        .into_iter()
+                        .map(|c| Column {
+                            name: c.name.value.to_lowercase(),
+                            data_type: c
+                                .data_type
+                                .to_string()
+                                .to_lowercase()
+                                .split('(')
+                                .next()
+                                .unwrap_or_default()
+                                .trim()
+                                .to_string(),
+                            not_null: c.options.iter().any(|o| {
+                                matches!(
+                                    o.option,
+                                    ColumnOption::NotNull
+                                        | ColumnOption::Unique {
+                                            is_primary: true,
+                                            ..
+                                        }
+                                )
+                            }),
+                        })
+                        .collect(),
+                }),
+                _ => (),
+            }
+        }
+
+        // tables are written in the order of their name, so moving a table in the schema does
+        // not move it in the generated files.
+        database.tables.sort_by_key(|t| pascal_case(&t.name));
+
+        Ok(database)
+    }
+
+    /// Name the generated type of a postgres enum type, instead of its name without the `t_`
+    /// prefix such as `DayOfWeek` for `t_day_of_week`.
+    pub fn rename(mut self, postgres_name: &str, name: &str) -> Self {
+        self.names
+            .insert(postgres_name.to_lowercase(), name.to_string());
+        self
+    }
+
+    /// Generated type of a postgres enum type, `None` if the type is not an enum of the schema.
+    fn enum_name(&self, postgres_name: &str) -> Option<String> {
+        if !self.enums.iter().any(|e| e.name == postgres_name) {
+            return None;
+        }
+
+        Some(match self.names.get(postgres_name) {
+            Some(name) => name.clone(),
+            None => pascal_case(postgres_name.strip_prefix("t_").unwrap_or(postgres_name)),
+        })
+    }
+
+    /// The `database` module of the backend.
+    pub fn rust(&self) -> Result<String, String> {
+        rust::generate(self)
+    }
+
+    /// The `database.ts` module of the faker.
+    pub fn typescript(&self) -> Result<String, String> {
+        typescript::generate(self)
+    }
+}
+
+/// `forum_post_replies` to `ForumPostReplies`.
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Database;
+
+    pub const SCHEMA: &str = r##"
+        create extension if not exists pgroonga;
+        drop type t_user_role cascade;
+        create type t_user_role as enum ('admin', 'student');
+        create type t_day_of_week as enum ('sunday', 'monday');
+
+        create table user_sessions (
+            user_session_id text not null unique,
+            user_session_refresh_token text,
+            primary key (user_session_id)
+        );
+
+        create table users (
+            user_id text primary key,
+            user_role t_user_role not null,
+            user_gpa numeric(3, 2),
+            user_created_timestamp timestamptz not null default now()
+        );
+
+        create index if not exists pgroonga_users_index on users using pgroonga (
+            user_id pgroonga_text_full_text_search_ops_v2
+        );
+    "##;
+
+    #[test]
+    fn test_parse() {
+        let database = Database::parse(SCHEMA).unwrap();
+
+        assert_eq!(database.enums.len(), 2);
+        assert_eq!(database.enums[0].labels, vec!["admin", "student"]);
+        assert_eq!(database.tables[0].name, "user_sessions");
+        assert_eq!(database.tables[1].name, "users");
+
+        let users = &database.tables[1].columns;
+        assert!(users[0].not_null);
+        assert_eq!(users[1].data_type, "t_user_role");
+        assert_eq!(users[2].data_type, "numeric");
+        assert!(!users[2].not_null);
+        assert_eq!(users[3].data_type, "timestamptz");
+    }
+
+    #[test]
+    fn test_enum_names() {
+        let database = Database::parse(SCHEMA)
+            .unwrap()
+            .rename("t_user_role", "Role");
+
+        assert_eq!(database.enum_name("t_user_role"), Some("Role".to_string()));
+        assert_eq!(
+            database.enum_name("t_day_of_week"),
+            Some("DayOfWeek".to_string())
+        );
+        assert_eq!(database.enum_name("text"), None);
+    }
+}
@@ -0,0 +1,58 @@
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: ger-codegen <migrations directory> [--rename <type>=<name>]... [--rust <file>] [--typescript <file>]";
+
+fn run() -> Result<(), String> {
+    let mut args = std::env::args().skip(1);
+
+    let migrations_dir = args.next().ok_or(USAGE)?;
+    let mut renames = vec![];
+    let mut rust_file = None;
+    let mut typescript_file = None;
+
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or(USAGE)?;
+
+        match arg.as_str() {
+            "--rename" => renames.push(
+                value
+                    .split_once('=')
+                    .map(|(t, n)| (t.to_string(), n.to_string()))
+                    .ok_or(USAGE)?,
+            ),
+            "--rust" => rust_file = Some(value),
+            "--typescript" => typescript_file = Some(value),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let sql = ger_codegen::Migrations::read(&migrations_dir)?.schema()?;
+    let database = renames.iter().fold(
+        ger_codegen::Database::parse(&sql)?,
+        |database, (postgres_name, name)| database.rename(postgres_name, name),
+    );
+
+    if let Some(file) = rust_file {
+        std::fs::write(&file, database.rust()?)
+            .map_err(|e| format!("cannot write {}: {}", file, e))?;
+        println!("{} written", file);
+    }
+
+    if let Some(file) = typescript_file {
+        std::fs::write(&file, database.typescript()?)
+            .map_err(|e| format!("cannot write {}: {}", file, e))?;
+        println!("{} written", file);
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+/// A migration of the migrations directory, written as `0001_baseline.up.sql` and
+/// `0001_baseline.down.sql`.
+#[derive(Debug)]
+pub struct Migration {
+    pub version: u32,
+    pub name: String,
+    pub up: PathBuf,
+    pub down: PathBuf,
+}
+
+/// Read `0001_baseline.up.sql` as the version, the name and whether it is the up migration.
+fn parse_file_name(file_name: &str) -> Option<(u32, &str, bool)> {
+    let (rest, up) = match file_name.strip_suffix(".up.sql") {
+        Some(rest) => (rest, true),
+        None => (file_name.strip_suffix(".down.sql")?, false),
+    };
+    let (version, name) = rest.split_once('_')?;
+
+    if version.is_empty() || !version.bytes().all(|b| b.is_ascii_digit()) || name.is_empty() {
+        return None;
+    }
+
+    Some((version.parse().ok()?, name, up))
+}
+
+/// Migrations of a directory in the order of their versions.
+#[derive(Debug)]
+pub struct Migrations {
+    pub migrations: Vec<Migration>,
+}
+
+impl Migrations {
+    /// Read the migrations of a directory. Every migration needs both of its files, and versions
+    /// start at 1 and go up by one so two branches adding the same version do not both get in.
+    pub fn read(dir: impl AsRef<Path>) -> Result<Migrations, String> {
+        let dir = dir.as_ref();
+        let entries =
+            std::fs::read_dir(dir).map_err(|e| format!("cannot read {}: {}", dir.display(), e))?;
+
+        let mut files = vec![];
+        for entry in entries {
+            let path = entry
+                .map_err(|e| format!("cannot read {}: {}", dir.display(), e))?
+                .path();
+            let file_name = path
+                .file_name()
+                .and_then(|f| f.to_str())
+                .unwrap_or_default()
+                .to_string();
+            let (version, name, up) = parse_file_name(&file_name).ok_or_else(|| {
+                format!(
+                    "{} is not named like 0001_name.up.sql or 0001_name.down.sql",
+                    path.display()
+                )
+            })?;
+
+            files.push((version, name.to_string(), up, path));
+        }
+        files.sort();
+
+        let mut migrations: Vec<Migration> = vec![];
+        for (version, name, up, path) in files {
+            match migrations.last_mut() {
+                Some(last) if last.version == version => {
+                    if last.name != name {
+                        return Err(format!(
+                            "migration {} is named both `{}` and `{}`",
+                            version, last.name, name
+                        ));
+                    }
+                    // down sorts before up.
+                    last.up = path;
+                }
+                _ => {
+                    let expected = migrations.last().map_or(1, |m| m.version + 1);
+                    if version != expected {
+                        return Err(format!(
+                            "migration {} follows migration {}, versions go up by one",
+                            version,
+                            expected - 1
+                        ));
+                    }
+                    if up {
+                        return Err(format!("{} has no down migration", path.display()));
+                    }
+
+                    migrations.push(Migration {
+                        version,
+                        name,
+                        up: PathBuf::new(),
+                        down: path,
+                    });
+                }
+            }
+        }
+
+        if let Some(m) = migrations.iter().find(|m| m.up.as_os_str().is_empty()) {
+            return Err(format!("{} has no up migration", m.down.display()));
+        }
+
+        Ok(Migrations { migrations })
+    }
+
+    /// Every up migration in order, the schema of a database they are all applied to.
+    pub fn schema(&self) -> Result<String, String> {
+        let mut schema = String::new();
+
+        for migration in &self.migrations {
+            let up = std::fs::read_to_string(&migration.up)
+                .map_err(|e| format!("cannot read {}: {}", migration.up.display(), e))?;
+            schema.push_str(&up);
+            schema.push_str("\n;\n");
+        }
+
+        Ok(schema)
+    }
+
+    /// A `MIGRATIONS` slice of `Migration { version, name, up, down }`, with the files included
+    /// in the binary. `Migration` is the type of the module the code is included in.
+    pub fn rust(&self) -> String {
+        let mut output = String::from(
+            "// Generated by ger-codegen from the migrations directory, do not edit.\n\n",
+        );
+        output.push_str("pub static MIGRATIONS: &[Migration] = &[\n");
+
+        for migration in &self.migrations {
+            let _ = writeln!(
+                output,
+                "    Migration {{\n        version: {},\n        name: {:?},\n        up: include_str!({:?}),\n        down: include_str!({:?}),\n    }},",
+                migration.version,
+                migration.name,
+                migration.up.display().to_string(),
+                migration.down.display().to_string(),
+            );
+        }
+
+        output.push_str("];\n");
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_file_name, Migrations};
+
+    #[test]
+    fn test_parse_file_name() {
+        assert_eq!(
+            parse_file_name("0001_baseline.up.sql"),
+            Some((1, "baseline", true))
+        );
+        assert_eq!(
+            parse_file_name("0012_add_user_email.down.sql"),
+            Some((12, "add_user_email", false))
+        );
+        assert_eq!(parse_file_name("0001_baseline.sql"), None);
+        assert_eq!(parse_file_name("baseline.up.sql"), None);
+        assert_eq!(parse_file_name("0001_.up.sql"), None);
+    }
+
+    #[test]
+    fn test_read() {
+        let dir =
+            std::env::temp_dir().join(format!("ger-codegen-migrations-{}", std::process::id()));
+        let write = |files: &[&str]| {
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            for file in files {
+                std::fs::write(dir.join(file), format!("-- {}", file)).unwrap();
+            }
+        };
+
+        write(&[
+            "0001_baseline.up.sql",
+            "0001_baseline.down.sql",
+            "0002_emails.up.sql",
+            "0002_emails.down.sql",
+        ]);
+        let migrations = Migrations::read(&dir).unwrap();
+        assert_eq!(migrations.migrations.len(), 2);
+        assert_eq!(migrations.migrations[1].name, "emails");
+        assert!(migrations.migrations[1].up.ends_with("0002_emails.up.sql"));
+        assert_eq!(
+            migrations.schema().unwrap(),
+            "-- 0001_baseline.up.sql\n;\n-- 0002_emails.up.sql\n;\n"
+        );
+
+        write(&["0001_baseline.up.sql"]);
+        assert!(Migrations::read(&dir)
+            .unwrap_err()
+            .ends_with("has no down migration"));
+
+        write(&["0001_baseline.down.sql"]);
+        assert!(Migrations::read(&dir)
+            .unwrap_err()
+            .ends_with("has no up migration"));
+
+        write(&[
+            "0001_baseline.up.sql",
+            "0001_baseline.down.sql",
+            "0003_emails.up.sql",
+            "0003_emails.down.sql",
+        ]);
+        assert_eq!(
+            Migrations::read(&dir).unwrap_err(),
+            "migration 3 follows migration 1, versions go up by one"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
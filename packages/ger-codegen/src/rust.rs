@@ -0,0 +1,135 @@
+use std::fmt::Write;
+
+use crate::{pascal_case, Database};
+
+/// Rust type of a column, and the typescript type given to `ts-rs` for the types it does not
+/// know, without `Option`.
+fn column_type(
+    database: &Database,
+    data_type: &str,
+) -> Result<(String, Option<&'static str>), String> {
+    let (rust_type, ts_type) = match data_type {
+        "text" | "varchar" | "character varying" => ("String", None),
+        "float4" | "real" => ("f32", None),
+        "float8" | "double precision" => ("f64", None),
+        "numeric" | "decimal" => ("rust_decimal::Decimal", Some("string")),
+        "int8" | "bigint" => ("i64", None),
+        "int4" | "int" | "integer" => ("i32", None),
+        "int2" | "smallint" => ("i16", None),
+        "date" => ("time::Date", Some("string")),
+        "timestamptz" | "timestamp with time zone" => ("time::OffsetDateTime", Some("string")),
+        "time" => ("time::Time", Some("string")),
+        "point" => ("geo_types::Point<f64>", Some("{ x: number, y: number }")),
+        "boolean" | "bool" => ("bool", None),
+        _ => {
+            return database
+                .enum_name(data_type)
+                .map(|name| (name, None))
+                .ok_or_else(|| format!("type `{}` has no rust type", data_type))
+        }
+    };
+
+    Ok((rust_type.to_string(), ts_type))
+}
+
+pub fn generate(database: &Database) -> Result<String, String> {
+    let mut output =
+        String::from("// Generated by ger-codegen from the migrations, do not edit.\n");
+
+    for pg_enum in &database.enums {
+        let name = database
+            .enum_name(&pg_enum.name)
+            .unwrap_or_else(|| pascal_case(&pg_enum.name));
+
+        output.push_str(concat!(
+            "\n#[derive(\n",
+            "    Debug,\n",
+            "    PartialEq,\n",
+            "    serde::Serialize,\n",
+            "    serde::Deserialize,\n",
+            "    ger_from_row::FromSql,\n",
+            "    ger_from_row::ToSql,\n",
+            "    ts_rs::TS,\n",
+            ")]\n",
+        ));
+        let _ = writeln!(output, "#[fromrow(name = \"{}\")]", pg_enum.name);
+        let _ = writeln!(output, "#[ts(export)]\npub enum {} {{", name);
+
+        for label in &pg_enum.labels {
+            let _ = writeln!(output, "    #[fromrow(rename = \"{}\")]", label);
+            let _ = writeln!(output, "    #[serde(rename = \"{}\")]", label);
+            let _ = writeln!(output, "    {},", pascal_case(label));
+        }
+
+        output.push_str("}\n");
+    }
+
+    for table in &database.tables {
+        output.push_str(concat!(
+            "\n#[derive(ger_from_row::FromRow, serde::Serialize, serde::Deserialize, ts_rs::TS)]\n",
+            "#[ts(export)]\n",
+        ));
+        let _ = writeln!(output, "pub struct {} {{", pascal_case(&table.name));
+
+        for column in &table.columns {
+            let (rust_type, ts_type) = column_type(database, &column.data_type)
+                .map_err(|e| format!("column `{}.{}`: {}", table.name, column.name, e))?;
+
+            if column.not_null {
+                if let Some(ts_type) = ts_type {
+                    let _ = writeln!(output, "    #[ts(type = \"{}\")]", ts_type);
+                }
+                let _ = writeln!(output, "    pub {}: {},", column.name, rust_type);
+            } else {
+                if let Some(ts_type) = ts_type {
+                    let _ = writeln!(output, "    #[ts(type = \"{} | null\")]", ts_type);
+                }
+                let _ = writeln!(output, "    pub {}: Option<{}>,", column.name, rust_type);
+            }
+        }
+
+        output.push_str("}\n");
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests::SCHEMA, Database};
+
+    #[test]
+    fn test_generate() {
+        let rust = Database::parse(SCHEMA)
+            .unwrap()
+            .rename("t_user_role", "Role")
+            .rust()
+            .unwrap();
+
+        assert!(rust.contains("#[fromrow(name = \"t_user_role\")]\n#[ts(export)]\npub enum Role {"));
+        assert!(rust.contains(
+            "    #[fromrow(rename = \"sunday\")]\n    #[serde(rename = \"sunday\")]\n    Sunday,"
+        ));
+        assert!(rust.contains("pub struct UserSessions {\n    pub user_session_id: String,\n    pub user_session_refresh_token: Option<String>,\n}"));
+        assert!(rust.contains("    pub user_role: Role,"));
+        assert!(rust.contains(
+            "    #[ts(type = \"string | null\")]\n    pub user_gpa: Option<rust_decimal::Decimal>,"
+        ));
+        assert!(rust.contains(
+            "    #[ts(type = \"string\")]\n    pub user_created_timestamp: time::OffsetDateTime,"
+        ));
+    }
+
+    #[test]
+    fn test_unknown_type() {
+        let error = Database::parse("create table users (user_data jsonb);")
+            .unwrap()
+            .rust()
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            "column `users.user_data`: type `jsonb` has no rust type"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt::Write;
+
+use crate::{pascal_case, Database};
+
+/// Typescript type of a column, the types are the ones the backend serializes the rust types to.
+fn column_type(database: &Database, data_type: &str) -> Result<String, String> {
+    let ts_type = match data_type {
+        "text" | "varchar" | "character varying" => "string",
+        "float4" | "real" | "float8" | "double precision" | "numeric" | "decimal" | "int8"
+        | "bigint" | "int4" | "int" | "integer" | "int2" | "smallint" => "number",
+        "date" | "timestamptz" | "timestamp with time zone" | "time" => "string",
+        "point" => "Point",
+        "boolean" | "bool" => "boolean",
+        _ => {
+            return database
+                .enum_name(data_type)
+                .ok_or_else(|| format!("type `{}` has no typescript type", data_type))
+        }
+    };
+
+    Ok(ts_type.to_string())
+}
+
+pub fn generate(database: &Database) -> Result<String, String> {
+    let mut output = String::from("export interface Point {\n  x: number\n  y: number\n}\n");
+
+    for pg_enum in &database.enums {
+        let name = database
+            .enum_name(&pg_enum.name)
+            .unwrap_or_else(|| pascal_case(&pg_enum.name));
+
+        let _ = writeln!(output, "\nexport enum {} {{", name);
+        for label in &pg_enum.labels {
+            let _ = writeln!(output, "  {} = '{}',", pascal_case(label), label);
+        }
+        output.push_str("}\n");
+    }
+
+    for table in &database.tables {
+        let _ = writeln!(output, "\nexport interface {} {{", pascal_case(&table.name));
+
+        for column in &table.columns {
+            let ts_type = column_type(database, &column.data_type)
+                .map_err(|e| format!("column `{}.{}`: {}", table.name, column.name, e))?;
+
+            if column.not_null {
+                let _ = writeln!(output, "  {}: {}", column.name, ts_type);
+            } else {
+                let _ = writeln!(output, "  {}: {} | null", column.name, ts_type);
+            }
+        }
+
+        output.push_str("}\n");
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests::SCHEMA, Database};
+
+    #[test]
+    fn test_generate() {
+        let typescript = Database::parse(SCHEMA)
+            .unwrap()
+            .rename("t_user_role", "Role")
+            .typescript()
+            .unwrap();
+
+        assert!(typescript
+            .contains("export enum Role {\n  Admin = 'admin',\n  Student = 'student',\n}"));
+        assert!(typescript.contains("export interface Users {\n  user_id: string\n  user_role: Role\n  user_gpa: number | null\n"));
+    }
+}
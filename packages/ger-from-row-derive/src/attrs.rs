@@ -0,0 +1,411 @@
+use crate::case::RenameRule;
+
+/// One `key` or `key = "value"` written inside `#[fromrow(...)]` or `#[torow(...)]`.
+pub struct AttrItem {
+    /// Name of the attribute the item is written in.
+    pub attr: &'static str,
+    pub path: syn::Path,
+    pub value: Option<syn::LitStr>,
+}
+
+impl AttrItem {
+    fn key(&self) -> String {
+        self.path
+            .get_ident()
+            .map(|i| i.to_string())
+            .unwrap_or_default()
+    }
+
+    fn require_value(&self) -> syn::Result<String> {
+        match self.value {
+            Some(ref v) => Ok(v.value()),
+            None => Err(syn::Error::new_spanned(
+                &self.path,
+                format!("expected `{} = \"...\"`", self.key()),
+            )),
+        }
+    }
+
+    fn require_value_as<T: syn::parse::Parse>(&self) -> syn::Result<T> {
+        self.require_value()?;
+        self.value.as_ref().unwrap().parse()
+    }
+
+    /// Used for flags, returns the span of the flag for later errors.
+    fn forbid_value(&self) -> syn::Result<proc_macro2::Span> {
+        match self.value {
+            Some(ref v) => Err(syn::Error::new_spanned(
+                v,
+                format!("`{}` does not take a value", self.key()),
+            )),
+            None => Ok(syn::spanned::Spanned::span(&self.path)),
+        }
+    }
+
+    fn unknown(&self, place: &str) -> syn::Error {
+        syn::Error::new_spanned(
+            &self.path,
+            format!(
+                "unknown {} attribute `{}` on {}",
+                self.attr,
+                self.key(),
+                place
+            ),
+        )
+    }
+}
+
+/// Collect every item of the `#[<attr>(...)]` attributes, other attributes are left for the
+/// macros that own them.
+pub fn parse_attr_items(
+    attrs: &[syn::Attribute],
+    attr: &'static str,
+) -> syn::Result<Vec<AttrItem>> {
+    let mut items = vec![];
+
+    for a in attrs.iter().filter(|a| a.path.is_ident(attr)) {
+        let list = match a.parse_meta()? {
+            syn::Meta::List(list) => list,
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    format!("expected `#[{}(...)]` with a list of attributes", attr),
+                ))
+            }
+        };
+
+        for nested in list.nested {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) => items.push(AttrItem {
+                    attr,
+                    path,
+                    value: None,
+                }),
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(lit),
+                    ..
+                })) => items.push(AttrItem {
+                    attr,
+                    path,
+                    value: Some(lit),
+                }),
+                syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) => {
+                    return Err(syn::Error::new_spanned(
+                        nv.lit,
+                        format!("{} attribute values have to be string literals", attr),
+                    ))
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        format!("unsupported {} attribute", attr),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+/// Attributes on an enum mapped to a postgres enum type.
+#[derive(Default)]
+pub struct EnumAttrs {
+    /// Name of the postgres enum type, any enum type with the same labels is accepted when this is
+    /// not given.
+    pub name: Option<String>,
+    pub rename_all: Option<RenameRule>,
+}
+
+impl EnumAttrs {
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+
+        for item in parse_attr_items(attrs, "fromrow")? {
+            match item.key().as_str() {
+                "name" => parsed.name = Some(item.require_value()?),
+                "rename_all" => parsed.rename_all = Some(parse_rename_rule(&item)?),
+                _ => return Err(item.unknown("an enum")),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Attributes on a variant of an enum mapped to a postgres enum type.
+#[derive(Default)]
+pub struct VariantAttrs {
+    /// Label of the variant in postgres, this wins over `rename_all` of the enum.
+    pub rename: Option<String>,
+}
+
+impl VariantAttrs {
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+
+        for item in parse_attr_items(attrs, "fromrow")? {
+            match item.key().as_str() {
+                "rename" => parsed.rename = Some(item.require_value()?),
+                _ => return Err(item.unknown("an enum variant")),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Attributes on a struct mapped from a row.
+#[derive(Default)]
+pub struct StructAttrs {
+    /// How field names are turned into column names.
+    pub rename_all: Option<RenameRule>,
+    /// Prepended to every column name after `rename_all` is applied, such as `forum_post_` so
+    /// `id` is read from `forum_post_id`.
+    pub prefix: Option<String>,
+}
+
+impl StructAttrs {
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+
+        for item in parse_attr_items(attrs, "fromrow")? {
+            match item.key().as_str() {
+                "rename_all" => parsed.rename_all = Some(parse_rename_rule(&item)?),
+                "prefix" => parsed.prefix = Some(item.require_value()?),
+                _ => return Err(item.unknown("a struct")),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Column a field is read from when the field is not renamed with `field`.
+    pub fn column_of(&self, field: &str) -> String {
+        column_name(self.rename_all, self.prefix.as_deref(), field)
+    }
+}
+
+fn column_name(rename_all: Option<RenameRule>, prefix: Option<&str>, field: &str) -> String {
+    let renamed = match rename_all {
+        Some(rule) => rule.apply_to_field(field),
+        None => field.to_string(),
+    };
+
+    format!("{}{}", prefix.unwrap_or_default(), renamed)
+}
+
+/// How the value of a field is produced.
+pub enum FieldSource {
+    /// Read from a column.
+    Column,
+    /// Read as another type and converted with `TryFrom`.
+    TryFrom(Box<syn::Type>),
+    /// Read as the argument of a function, which returns a `Result` of the field.
+    With(syn::Path),
+    /// Read by the `FromRow` implementation of the field type from the same row, every column of
+    /// the nested struct is prefixed.
+    Flatten { prefix: String },
+    /// Not read from the row at all.
+    Skip,
+}
+
+/// Value used when a column is not in the row, or for skipped fields.
+pub enum FieldDefault {
+    Trait,
+    Function(syn::Path),
+}
+
+/// Attributes on a field of a struct mapped from a row.
+pub struct FieldAttrs {
+    /// Column the field is read from as it is, `rename_all` and `prefix` of the struct are not
+    /// applied to it. The field name is used when this is not given.
+    pub field: Option<String>,
+    pub source: FieldSource,
+    pub default: Option<FieldDefault>,
+}
+
+impl FieldAttrs {
+    /// Read the attributes of a field, `span` is where errors about conflicting attributes point.
+    pub fn parse(attrs: &[syn::Attribute], span: proc_macro2::Span) -> syn::Result<Self> {
+        let mut field = None;
+        let mut default = None;
+        let mut try_from = None;
+        let mut with = None;
+        let mut flatten = None;
+        let mut prefix = None;
+        let mut skip = None;
+
+        for item in parse_attr_items(attrs, "fromrow")? {
+            match item.key().as_str() {
+                "field" => field = Some(item.require_value()?),
+                "default" => {
+                    default = Some(match item.value {
+                        Some(ref v) => FieldDefault::Function(v.parse()?),
+                        None => FieldDefault::Trait,
+                    })
+                }
+                "try_from" => try_from = Some(Box::new(item.require_value_as::<syn::Type>()?)),
+                "with" => with = Some(item.require_value_as::<syn::Path>()?),
+                "flatten" => flatten = Some(item.forbid_value()?),
+                "prefix" => prefix = Some((item.require_value()?, item.path)),
+                "skip" => skip = Some(item.forbid_value()?),
+                _ => return Err(item.unknown("a field")),
+            }
+        }
+
+        if let (Some(ref path), None) = (&prefix, &flatten) {
+            return Err(syn::Error::new_spanned(
+                &path.1,
+                "`prefix` can only be used together with `flatten`",
+            ));
+        }
+
+        let sources = [
+            try_from.is_some(),
+            with.is_some(),
+            flatten.is_some(),
+            skip.is_some(),
+        ];
+        if sources.iter().filter(|s| **s).count() > 1 {
+            return Err(syn::Error::new(
+                span,
+                "only one of `try_from`, `with`, `flatten` and `skip` could be used on a field",
+            ));
+        }
+
+        if let Some(span) = flatten.as_ref().or(skip.as_ref()) {
+            if field.is_some() {
+                return Err(syn::Error::new(
+                    *span,
+                    "`field` cannot be used on fields that are not read from a column",
+                ));
+            }
+        }
+
+        if let (Some(span), Some(_)) = (&flatten, &default) {
+            return Err(syn::Error::new(
+                *span,
+                "`default` cannot be used together with `flatten`",
+            ));
+        }
+
+        let source = match (try_from, with, flatten, skip) {
+            (Some(ty), _, _, _) => FieldSource::TryFrom(ty),
+            (_, Some(path), _, _) => FieldSource::With(path),
+            (_, _, Some(_), _) => FieldSource::Flatten {
+                prefix: prefix.map(|p| p.0).unwrap_or_default(),
+            },
+            (_, _, _, Some(_)) => FieldSource::Skip,
+            _ => FieldSource::Column,
+        };
+
+        Ok(Self {
+            field,
+            source,
+            default,
+        })
+    }
+}
+
+/// Attributes on a struct written as a row of a table.
+pub struct TableAttrs {
+    pub table: String,
+    /// How field names are turned into column names, the same as `rename_all` of `FromRow`.
+    pub rename_all: Option<RenameRule>,
+    /// Prepended to every column name after `rename_all` is applied.
+    pub prefix: Option<String>,
+}
+
+impl TableAttrs {
+    /// Read the attributes of a struct, `span` is where the error of a missing `table` points.
+    pub fn parse(attrs: &[syn::Attribute], span: proc_macro2::Span) -> syn::Result<Self> {
+        let mut table = None;
+        let mut rename_all = None;
+        let mut prefix = None;
+
+        for item in parse_attr_items(attrs, "torow")? {
+            match item.key().as_str() {
+                "table" => table = Some(item.require_value()?),
+                "rename_all" => rename_all = Some(parse_rename_rule(&item)?),
+                "prefix" => prefix = Some(item.require_value()?),
+                _ => return Err(item.unknown("a struct")),
+            }
+        }
+
+        let table = table.ok_or_else(|| {
+            syn::Error::new(
+                span,
+                "ToRow needs the table, add `#[torow(table = \"...\")]`",
+            )
+        })?;
+
+        Ok(Self {
+            table,
+            rename_all,
+            prefix,
+        })
+    }
+
+    /// Column a field is written to when the field is not renamed with `field`.
+    pub fn column_of(&self, field: &str) -> String {
+        column_name(self.rename_all, self.prefix.as_deref(), field)
+    }
+}
+
+/// Attributes on a field of a struct written as a row.
+#[derive(Default)]
+pub struct ToRowFieldAttrs {
+    /// Column the field is written to as it is, `rename_all` and `prefix` of the struct are not
+    /// applied to it.
+    pub field: Option<String>,
+    /// Constant of `Type` used as the type of the parameter, such as `NUMERIC`.
+    pub sql_type: Option<syn::Ident>,
+    /// Whether the field is not written at all.
+    pub skip: bool,
+}
+
+impl ToRowFieldAttrs {
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+        let mut skip = None;
+
+        for item in parse_attr_items(attrs, "torow")? {
+            match item.key().as_str() {
+                "field" => parsed.field = Some(item.require_value()?),
+                "sql_type" => parsed.sql_type = Some(item.require_value_as::<syn::Ident>()?),
+                "skip" => skip = Some(item.forbid_value()?),
+                _ => return Err(item.unknown("a field")),
+            }
+        }
+
+        if let Some(span) = skip {
+            if parsed.field.is_some() || parsed.sql_type.is_some() {
+                return Err(syn::Error::new(
+                    span,
+                    "`field` and `sql_type` cannot be used on skipped fields",
+                ));
+            }
+
+            parsed.skip = true;
+        }
+
+        Ok(parsed)
+    }
+}
+
+fn parse_rename_rule(item: &AttrItem) -> syn::Result<RenameRule> {
+    let value = item.require_value()?;
+
+    RenameRule::from_str(&value).ok_or_else(|| {
+        syn::Error::new_spanned(
+            item.value.as_ref(),
+            format!(
+                "unknown rename rule `{}`, expected one of {}",
+                value,
+                RenameRule::NAMES.join(", ")
+            ),
+        )
+    })
+}
@@ -0,0 +1,137 @@
+/// How names written in rust are turned into names in postgres, these follow the rules of
+/// `#[serde(rename_all = "...")]`.
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    Lowercase,
+    Uppercase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    pub const NAMES: [&'static str; 8] = [
+        "lowercase",
+        "UPPERCASE",
+        "PascalCase",
+        "camelCase",
+        "snake_case",
+        "SCREAMING_SNAKE_CASE",
+        "kebab-case",
+        "SCREAMING-KEBAB-CASE",
+    ];
+
+    pub fn from_str(rule: &str) -> Option<Self> {
+        match rule {
+            "lowercase" => Some(Self::Lowercase),
+            "UPPERCASE" => Some(Self::Uppercase),
+            "PascalCase" => Some(Self::PascalCase),
+            "camelCase" => Some(Self::CamelCase),
+            "snake_case" => Some(Self::SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Some(Self::ScreamingSnakeCase),
+            "kebab-case" => Some(Self::KebabCase),
+            "SCREAMING-KEBAB-CASE" => Some(Self::ScreamingKebabCase),
+            _ => None,
+        }
+    }
+
+    /// Rename a field, which is written in `snake_case`.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lowercase | Self::SnakeCase => field.to_string(),
+            Self::Uppercase | Self::ScreamingSnakeCase => field.to_uppercase(),
+            Self::PascalCase => field
+                .split('_')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) => first.to_uppercase().chain(chars).collect(),
+                        None => String::new(),
+                    }
+                })
+                .collect(),
+            Self::CamelCase => {
+                Self::CamelCase.apply_to_variant(&Self::PascalCase.apply_to_field(field))
+            }
+            Self::KebabCase => field.replace('_', "-"),
+            Self::ScreamingKebabCase => field.to_uppercase().replace('_', "-"),
+        }
+    }
+
+    /// Rename a variant, which is written in `PascalCase`.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::Lowercase => variant.to_lowercase(),
+            Self::Uppercase => variant.to_uppercase(),
+            Self::PascalCase => variant.to_string(),
+            Self::CamelCase => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_lowercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            }
+            Self::SnakeCase => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.extend(ch.to_lowercase());
+                }
+                snake
+            }
+            Self::ScreamingSnakeCase => Self::SnakeCase.apply_to_variant(variant).to_uppercase(),
+            Self::KebabCase => Self::SnakeCase.apply_to_variant(variant).replace('_', "-"),
+            Self::ScreamingKebabCase => Self::ScreamingSnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenameRule;
+
+    #[test]
+    fn test_apply_to_field() {
+        let cases = [
+            ("lowercase", "forum_post_id"),
+            ("UPPERCASE", "FORUM_POST_ID"),
+            ("PascalCase", "ForumPostId"),
+            ("camelCase", "forumPostId"),
+            ("snake_case", "forum_post_id"),
+            ("SCREAMING_SNAKE_CASE", "FORUM_POST_ID"),
+            ("kebab-case", "forum-post-id"),
+            ("SCREAMING-KEBAB-CASE", "FORUM-POST-ID"),
+        ];
+
+        for (rule, expected) in cases {
+            let rule = RenameRule::from_str(rule).unwrap();
+            assert_eq!(rule.apply_to_field("forum_post_id"), expected);
+        }
+    }
+
+    #[test]
+    fn test_apply_to_variant() {
+        let cases = [
+            ("lowercase", "publicholiday"),
+            ("UPPERCASE", "PUBLICHOLIDAY"),
+            ("PascalCase", "PublicHoliday"),
+            ("camelCase", "publicHoliday"),
+            ("snake_case", "public_holiday"),
+            ("SCREAMING_SNAKE_CASE", "PUBLIC_HOLIDAY"),
+            ("kebab-case", "public-holiday"),
+            ("SCREAMING-KEBAB-CASE", "PUBLIC-HOLIDAY"),
+        ];
+
+        for (rule, expected) in cases {
+            let rule = RenameRule::from_str(rule).unwrap();
+            assert_eq!(rule.apply_to_variant("PublicHoliday"), expected);
+        }
+    }
+}
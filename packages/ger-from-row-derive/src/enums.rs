@@ -0,0 +1,157 @@
+use crate::attrs::{EnumAttrs, VariantAttrs};
+
+/// An enum whose every variant is a label of a postgres enum type.
+pub struct PgEnum {
+    ident: syn::Ident,
+    name: Option<String>,
+    variants: Vec<(syn::Ident, String)>,
+}
+
+impl PgEnum {
+    /// Read the enum and its attributes, only enums with unit variants could be mapped to labels.
+    pub fn parse(ast: &syn::DeriveInput, derive: &str) -> syn::Result<Self> {
+        let data = match ast.data {
+            syn::Data::Enum(ref e) => e,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &ast.ident,
+                    format!(
+                        "{} can only be derived for enums with unit variants",
+                        derive
+                    ),
+                ))
+            }
+        };
+
+        if !ast.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &ast.generics,
+                "enums mapped to postgres enum types cannot be generic",
+            ));
+        }
+
+        if data.variants.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &ast.ident,
+                "enums mapped to postgres enum types need at least one variant",
+            ));
+        }
+
+        let attrs = EnumAttrs::parse(&ast.attrs)?;
+
+        let mut variants = Vec::with_capacity(data.variants.len());
+        for variant in &data.variants {
+            if !matches!(variant.fields, syn::Fields::Unit) {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "variants with fields cannot be mapped to postgres enum labels",
+                ));
+            }
+
+            let label = match VariantAttrs::parse(&variant.attrs)?.rename {
+                Some(rename) => rename,
+                None => match attrs.rename_all {
+                    Some(rule) => rule.apply_to_variant(&variant.ident.to_string()),
+                    None => variant.ident.to_string(),
+                },
+            };
+
+            if let Some((other, _)) = variants.iter().find(|(_, l)| *l == label) {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    format!("label `{}` is already used by `{}`", label, other),
+                ));
+            }
+
+            variants.push((variant.ident.clone(), label));
+        }
+
+        Ok(Self {
+            ident: ast.ident.clone(),
+            name: attrs.name,
+            variants,
+        })
+    }
+
+    /// Body of `accepts` shared by both traits, the postgres type has to be an enum with exactly
+    /// the same labels, and the same name when the name is given.
+    fn accepts(&self) -> proc_macro2::TokenStream {
+        let labels = self.variants.iter().map(|(_, l)| l);
+        let count = self.variants.len();
+        let name_check = match self.name {
+            Some(ref name) => quote::quote! { ty.name() == #name && },
+            None => quote::quote! {},
+        };
+
+        quote::quote! {
+            #name_check match ty.kind() {
+                ::ger_from_row::__private::tokio_postgres::types::Kind::Enum(labels) => {
+                    labels.len() == #count
+                        && labels.iter().all(|l| matches!(l.as_str(), #(#labels)|*))
+                }
+                _ => false,
+            }
+        }
+    }
+
+    pub fn implement_from_sql(&self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let accepts = self.accepts();
+        let arms = self.variants.iter().map(|(variant, label)| {
+            quote::quote! { #label => Ok(#ident::#variant) }
+        });
+        let unknown = format!("unknown label `{{}}` of {}", ident);
+
+        quote::quote! {
+            impl<'a> ::ger_from_row::__private::tokio_postgres::types::FromSql<'a> for #ident {
+                fn from_sql(
+                    _ty: &::ger_from_row::__private::tokio_postgres::types::Type,
+                    raw: &'a [u8],
+                ) -> ::std::result::Result<Self, Box<dyn ::std::error::Error + Sync + Send>> {
+                    match ::std::str::from_utf8(raw)? {
+                        #(#arms,)*
+                        other => Err(format!(#unknown, other).into()),
+                    }
+                }
+
+                fn accepts(ty: &::ger_from_row::__private::tokio_postgres::types::Type) -> bool {
+                    #accepts
+                }
+            }
+        }
+    }
+
+    pub fn implement_to_sql(&self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let accepts = self.accepts();
+        let arms = self.variants.iter().map(|(variant, label)| {
+            quote::quote! { #ident::#variant => #label }
+        });
+
+        quote::quote! {
+            impl ::ger_from_row::__private::tokio_postgres::types::ToSql for #ident {
+                fn to_sql(
+                    &self,
+                    _ty: &::ger_from_row::__private::tokio_postgres::types::Type,
+                    out: &mut ::ger_from_row::__private::tokio_postgres::types::private::BytesMut,
+                ) -> ::std::result::Result<
+                    ::ger_from_row::__private::tokio_postgres::types::IsNull,
+                    Box<dyn ::std::error::Error + Sync + Send>,
+                > {
+                    let label = match self {
+                        #(#arms,)*
+                    };
+                    out.extend_from_slice(label.as_bytes());
+
+                    Ok(::ger_from_row::__private::tokio_postgres::types::IsNull::No)
+                }
+
+                fn accepts(ty: &::ger_from_row::__private::tokio_postgres::types::Type) -> bool {
+                    #accepts
+                }
+
+                ::ger_from_row::__private::tokio_postgres::types::to_sql_checked!();
+            }
+        }
+    }
+}
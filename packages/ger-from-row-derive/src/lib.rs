@@ -0,0 +1,62 @@
+//! Derive macros of `ger_from_row`, the macros are documented there. Generated code refers to
+//! the `ger_from_row` crate, so this crate is not meant to be used by itself.
+
+mod attrs;
+mod case;
+mod enums;
+mod structs;
+mod to_row;
+
+use enums::PgEnum;
+
+#[proc_macro_derive(FromRow, attributes(fromrow))]
+pub fn from_row(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    // match struct or enum implementation
+    let tokens = match ast.data {
+        syn::Data::Struct(ref s) => structs::implement_struct_try_from(&ast, s),
+        syn::Data::Enum(_) => PgEnum::parse(&ast, "FromRow").map(|e| e.implement_from_sql()),
+        syn::Data::Union(_) => Err(syn::Error::new_spanned(
+            &ast.ident,
+            "FromRow cannot be derived for unions",
+        )),
+    };
+
+    tokens.unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+#[proc_macro_derive(FromSql, attributes(fromrow))]
+pub fn from_sql(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    PgEnum::parse(&ast, "FromSql")
+        .map(|e| e.implement_from_sql())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(ToSql, attributes(fromrow))]
+pub fn to_sql(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    PgEnum::parse(&ast, "ToSql")
+        .map(|e| e.implement_to_sql())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(ToRow, attributes(torow))]
+pub fn to_row(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    let tokens = match ast.data {
+        syn::Data::Struct(ref s) => to_row::implement_to_row(&ast, s),
+        _ => Err(syn::Error::new_spanned(
+            &ast.ident,
+            "ToRow can only be derived for structs with named fields",
+        )),
+    };
+
+    tokens.unwrap_or_else(|e| e.to_compile_error()).into()
+}
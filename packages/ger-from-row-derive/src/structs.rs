@@ -0,0 +1,257 @@
+use syn::ext::IdentExt;
+
+use crate::attrs::{FieldAttrs, FieldDefault, FieldSource, StructAttrs};
+
+/// Generated code of one field, how its columns are found and how its value is read.
+struct FieldCode {
+    /// Pushes the indices of the columns of the field.
+    resolve: proc_macro2::TokenStream,
+    /// Number of indices pushed by `resolve`.
+    count: proc_macro2::TokenStream,
+    /// Produces the value from `row` and the indices starting at `offset`.
+    read: proc_macro2::TokenStream,
+    /// Whether the field takes a position of a tuple struct.
+    by_position: bool,
+}
+
+/// Where a field is read from, fields of tuple structs without `field` are read by position.
+enum ColumnKey {
+    Name(String),
+    Position(usize),
+}
+
+fn field_code(
+    field: &syn::Field,
+    position: usize,
+    struct_attrs: &StructAttrs,
+) -> syn::Result<FieldCode> {
+    let ty = &field.ty;
+    let span = match field.ident {
+        Some(ref ident) => ident.span(),
+        None => syn::spanned::Spanned::span(ty),
+    };
+    let attrs = FieldAttrs::parse(&field.attrs, span)?;
+
+    let key = match (attrs.field, &field.ident) {
+        (Some(column), _) => ColumnKey::Name(column),
+        (None, Some(ident)) => ColumnKey::Name(struct_attrs.column_of(&ident.unraw().to_string())),
+        (None, None) => ColumnKey::Position(position),
+    };
+
+    let default = attrs.default.map(|d| match d {
+        FieldDefault::Trait => quote::quote! { ::std::default::Default::default() },
+        FieldDefault::Function(path) => quote::quote! { #path() },
+    });
+
+    let read_at = match attrs.source {
+        FieldSource::Column => quote::quote! {
+            ::ger_from_row::__private::get::<#ty>(row, index)?
+        },
+        FieldSource::TryFrom(from) => quote::quote! {
+            ::ger_from_row::__private::convert(
+                row,
+                index,
+                <#ty as ::std::convert::TryFrom<#from>>::try_from(
+                    ::ger_from_row::__private::get::<#from>(row, index)?,
+                ),
+            )?
+        },
+        FieldSource::With(path) => quote::quote! {
+            ::ger_from_row::__private::convert(
+                row,
+                index,
+                #path(::ger_from_row::__private::get(row, index)?),
+            )?
+        },
+        FieldSource::Flatten { prefix } => {
+            if field.ident.is_none() {
+                return Err(syn::Error::new(
+                    span,
+                    "`flatten` cannot be used on fields of tuple structs",
+                ));
+            }
+
+            return Ok(FieldCode {
+                resolve: quote::quote! {
+                    <#ty as ::ger_from_row::FromRow>::resolve_columns(
+                        columns,
+                        &format!("{}{}", prefix, #prefix),
+                        indices,
+                    )?;
+                },
+                count: quote::quote! { <#ty as ::ger_from_row::FromRow>::COLUMN_COUNT },
+                read: quote::quote! {{
+                    let value = <#ty as ::ger_from_row::FromRow>::from_row_indexed(
+                        row,
+                        indices.get(offset..).unwrap_or_default(),
+                    )?;
+                    offset += <#ty as ::ger_from_row::FromRow>::COLUMN_COUNT;
+                    value
+                }},
+                by_position: false,
+            });
+        }
+        FieldSource::Skip => {
+            return Ok(FieldCode {
+                resolve: quote::quote! {},
+                count: quote::quote! { 0 },
+                read: default
+                    .unwrap_or_else(|| quote::quote! { ::std::default::Default::default() }),
+                by_position: false,
+            });
+        }
+    };
+
+    let by_position = matches!(key, ColumnKey::Position(_));
+
+    let (resolve, column) = match key {
+        ColumnKey::Name(column) => (
+            quote::quote! {
+                let name = format!("{}{}", prefix, #column);
+                let index = columns.iter().position(|c| c.name() == name);
+            },
+            column,
+        ),
+        ColumnKey::Position(position) => {
+            if default.is_some() {
+                return Err(syn::Error::new(
+                    span,
+                    "`default` cannot be used on fields of tuple structs read by position",
+                ));
+            }
+
+            (
+                quote::quote! {
+                    let name = #position.to_string();
+                    let index = Some(#position).filter(|i| *i < columns.len());
+                },
+                position.to_string(),
+            )
+        }
+    };
+
+    let (resolve_missing, read_missing) = match default {
+        Some(default) => (quote::quote! {}, default),
+        None => (
+            quote::quote! {
+                if index.is_none() {
+                    return Err(::ger_from_row::Error::ColumnNotFound { column: name });
+                }
+            },
+            quote::quote! {
+                return Err(::ger_from_row::Error::ColumnNotFound {
+                    column: #column.to_string(),
+                })
+            },
+        ),
+    };
+
+    Ok(FieldCode {
+        resolve: quote::quote! {{
+            #resolve
+            #resolve_missing
+            indices.push(index);
+        }},
+        count: quote::quote! { 1 },
+        read: quote::quote! {{
+            let index = indices.get(offset).copied().flatten();
+            offset += 1;
+
+            match index {
+                Some(index) => #read_at,
+                None => #read_missing,
+            }
+        }},
+        by_position,
+    })
+}
+
+/// Implement `FromRow`, `TryFrom<&Row>` and `TryFrom<Row>` for a struct. Fields of structs with
+/// named fields are read from the columns with the same names unless they are renamed by the
+/// field or the struct, fields of tuple structs are read by position.
+pub fn implement_struct_try_from(
+    ast: &syn::DeriveInput,
+    struct_data: &syn::DataStruct,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (impl_generics, ty_generics, where_clause) = &ast.generics.split_for_impl();
+    let name = &ast.ident;
+
+    let struct_attrs = StructAttrs::parse(&ast.attrs)?;
+
+    if let (syn::Fields::Unnamed(_), true) = (
+        &struct_data.fields,
+        struct_attrs.rename_all.is_some() || struct_attrs.prefix.is_some(),
+    ) {
+        return Err(syn::Error::new_spanned(
+            &ast.ident,
+            "`rename_all` and `prefix` cannot be used on tuple structs",
+        ));
+    }
+
+    // positions of tuple fields only count fields that are read by position.
+    let mut position = 0;
+    let mut fields = vec![];
+    for field in struct_data.fields.iter() {
+        let code = field_code(field, position, &struct_attrs)?;
+        if code.by_position {
+            position += 1;
+        }
+        fields.push(code);
+    }
+
+    let resolves = fields.iter().map(|f| &f.resolve);
+    let counts = fields.iter().map(|f| &f.count);
+    let reads = fields.iter().map(|f| &f.read);
+
+    let construct = match struct_data.fields {
+        syn::Fields::Named(ref named) => {
+            let idents = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+            quote::quote! { Self { #(#idents: #reads),* } }
+        }
+        syn::Fields::Unnamed(_) => quote::quote! { Self(#(#reads),*) },
+        syn::Fields::Unit => quote::quote! { Self },
+    };
+
+    Ok(quote::quote! {
+        impl #impl_generics ::ger_from_row::FromRow for #name #ty_generics #where_clause {
+            const COLUMN_COUNT: usize = 0 #(+ #counts)*;
+
+            #[allow(unused_variables)]
+            fn resolve_columns(
+                columns: &[::ger_from_row::__private::tokio_postgres::Column],
+                prefix: &str,
+                indices: &mut Vec<Option<usize>>,
+            ) -> ::std::result::Result<(), ::ger_from_row::Error> {
+                #(#resolves)*
+
+                Ok(())
+            }
+
+            #[allow(unused_variables, unused_mut, unused_assignments)]
+            fn from_row_indexed(
+                row: &::ger_from_row::__private::tokio_postgres::Row,
+                indices: &[Option<usize>],
+            ) -> ::std::result::Result<Self, ::ger_from_row::Error> {
+                let mut offset = 0usize;
+
+                Ok(#construct)
+            }
+        }
+
+        impl #impl_generics ::std::convert::TryFrom<&::ger_from_row::__private::tokio_postgres::Row> for #name #ty_generics #where_clause {
+            type Error = ::ger_from_row::Error;
+
+            fn try_from(row: &::ger_from_row::__private::tokio_postgres::Row) -> ::std::result::Result<Self, Self::Error> {
+                <Self as ::ger_from_row::FromRow>::from_row(row)
+            }
+        }
+
+        impl #impl_generics ::std::convert::TryFrom<::ger_from_row::__private::tokio_postgres::Row> for #name #ty_generics #where_clause {
+            type Error = ::ger_from_row::Error;
+
+            fn try_from(row: ::ger_from_row::__private::tokio_postgres::Row) -> ::std::result::Result<Self, Self::Error> {
+                <Self as ::ger_from_row::FromRow>::from_row(&row)
+            }
+        }
+    })
+}
@@ -0,0 +1,109 @@
+use syn::ext::IdentExt;
+
+use crate::attrs::{TableAttrs, ToRowFieldAttrs};
+
+/// Constant of `Type` for the rust types with an obvious postgres type, references and `Option`
+/// are looked through. Other types are inferred by postgres.
+fn infer_sql_type(ty: &syn::Type) -> Option<&'static str> {
+    match ty {
+        syn::Type::Reference(r) => infer_sql_type(&r.elem),
+        syn::Type::Group(g) => infer_sql_type(&g.elem),
+        syn::Type::Paren(p) => infer_sql_type(&p.elem),
+        syn::Type::Path(p) if p.qself.is_none() => {
+            let segment = p.path.segments.last()?;
+            let argument = match segment.arguments {
+                syn::PathArguments::AngleBracketed(ref a) if a.args.len() == 1 => {
+                    match a.args.first() {
+                        Some(syn::GenericArgument::Type(t)) => Some(t),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+
+            match (segment.ident.to_string().as_str(), argument) {
+                ("Option", Some(inner)) | ("Box", Some(inner)) => infer_sql_type(inner),
+                ("Vec", Some(syn::Type::Path(inner))) if inner.path.is_ident("u8") => Some("BYTEA"),
+                ("String" | "str", None) => Some("TEXT"),
+                ("bool", None) => Some("BOOL"),
+                ("i8", None) => Some("CHAR"),
+                ("i16", None) => Some("INT2"),
+                ("i32", None) => Some("INT4"),
+                ("i64", None) => Some("INT8"),
+                ("u32", None) => Some("OID"),
+                ("f32", None) => Some("FLOAT4"),
+                ("f64", None) => Some("FLOAT8"),
+                ("Decimal", None) => Some("NUMERIC"),
+                ("Date", None) => Some("DATE"),
+                ("Time", None) => Some("TIME"),
+                ("PrimitiveDateTime", None) => Some("TIMESTAMP"),
+                ("OffsetDateTime", None) => Some("TIMESTAMPTZ"),
+                ("Uuid", None) => Some("UUID"),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Implement `ToRow` for a struct with named fields, every field that is not skipped is a column.
+pub fn implement_to_row(
+    ast: &syn::DeriveInput,
+    struct_data: &syn::DataStruct,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (impl_generics, ty_generics, where_clause) = &ast.generics.split_for_impl();
+    let name = &ast.ident;
+
+    let named = match struct_data.fields {
+        syn::Fields::Named(ref named) => named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &ast.ident,
+                "ToRow can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let table_attrs = TableAttrs::parse(&ast.attrs, ast.ident.span())?;
+    let table = &table_attrs.table;
+
+    let mut columns = vec![];
+    let mut types = vec![];
+    let mut values = vec![];
+    for field in named.named.iter() {
+        let attrs = ToRowFieldAttrs::parse(&field.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().unwrap();
+
+        columns.push(
+            attrs
+                .field
+                .unwrap_or_else(|| table_attrs.column_of(&ident.unraw().to_string())),
+        );
+        types.push(match attrs.sql_type {
+            Some(sql_type) => sql_type,
+            None => syn::Ident::new(
+                infer_sql_type(&field.ty).unwrap_or("UNKNOWN"),
+                proc_macro2::Span::call_site(),
+            ),
+        });
+        values.push(ident);
+    }
+
+    Ok(quote::quote! {
+        impl #impl_generics ::ger_from_row::ToRow for #name #ty_generics #where_clause {
+            const TABLE: &'static str = #table;
+            const COLUMNS: &'static [&'static str] = &[#(#columns),*];
+            const TYPES: &'static [::ger_from_row::__private::tokio_postgres::types::Type] = &[
+                #(::ger_from_row::__private::tokio_postgres::types::Type::#types),*
+            ];
+
+            fn params(&self) -> ::std::vec::Vec<&(dyn ::ger_from_row::__private::tokio_postgres::types::ToSql + ::std::marker::Sync)> {
+                vec![#(&self.#values),*]
+            }
+        }
+    })
+}
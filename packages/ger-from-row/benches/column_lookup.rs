@@ -0,0 +1,119 @@
+//! Compares reading rows by looking up every column by name, which is what `FromRow` used to
+//! generate, with column indices resolved once for the statement.
+//!
+//! ```sh
+//! GER_FROM_ROW_BENCH_DATABASE="host=localhost user=postgres" cargo bench -p ger-from-row
+//! ```
+
+use std::time::{Duration, Instant};
+
+use ger_from_row::FromRow;
+use tokio_postgres::{NoTls, Row};
+
+const ROW_COUNT: i32 = 1000;
+const ITERATIONS: u32 = 200;
+
+/// Same shape as a post in the list of forum posts.
+#[derive(FromRow)]
+#[allow(dead_code)]
+struct Post {
+    id: String,
+    user_id: String,
+    username: String,
+    name: String,
+    category_id: String,
+    category_representative_id: String,
+    view_count: i64,
+    vote_count: i64,
+    reply_count: i64,
+    is_active: bool,
+}
+
+fn read_by_name(row: &Row) -> Result<Post, tokio_postgres::Error> {
+    Ok(Post {
+        id: row.try_get("id")?,
+        user_id: row.try_get("user_id")?,
+        username: row.try_get("username")?,
+        name: row.try_get("name")?,
+        category_id: row.try_get("category_id")?,
+        category_representative_id: row.try_get("category_representative_id")?,
+        view_count: row.try_get("view_count")?,
+        vote_count: row.try_get("vote_count")?,
+        reply_count: row.try_get("reply_count")?,
+        is_active: row.try_get("is_active")?,
+    })
+}
+
+fn measure(name: &str, rows: &[Row], read: impl Fn(&[Row]) -> usize) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        assert_eq!(read(rows), rows.len());
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{:<24} {:>10.2?} per {} rows",
+        name,
+        elapsed / ITERATIONS,
+        rows.len()
+    );
+
+    elapsed
+}
+
+fn main() {
+    let config = match std::env::var("GER_FROM_ROW_BENCH_DATABASE") {
+        Ok(c) => c,
+        Err(_) => {
+            println!("GER_FROM_ROW_BENCH_DATABASE is not set, skipped");
+            return;
+        }
+    };
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let rows = runtime.block_on(async {
+        let (client, connection) = tokio_postgres::connect(&config, NoTls).await.unwrap();
+        tokio::spawn(connection);
+
+        client
+            .query(
+                r##"
+                select
+                    'post ' || i as id,
+                    'user ' || i as user_id,
+                    'username ' || i as username,
+                    'name of post ' || i as name,
+                    'category ' || i % 10 as category_id,
+                    'c' || i % 10 as category_representative_id,
+                    i::int8 * 3 as view_count,
+                    i::int8 * 2 as vote_count,
+                    i::int8 as reply_count,
+                    i % 2 = 0 as is_active
+                from generate_series(1, $1) as i
+                "##,
+                &[&ROW_COUNT],
+            )
+            .await
+            .unwrap()
+    });
+
+    let by_name = measure("lookup by name", &rows, |rows| {
+        rows.iter()
+            .map(read_by_name)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .len()
+    });
+    let by_index = measure("indices resolved once", &rows, |rows| {
+        Post::from_rows(rows).unwrap().len()
+    });
+
+    println!(
+        "indices resolved once are {:.2}x as fast",
+        by_name.as_secs_f64() / by_index.as_secs_f64()
+    );
+}
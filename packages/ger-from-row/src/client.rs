@@ -0,0 +1,56 @@
+use std::future::Future;
+
+use tokio_postgres::{types::ToSql, GenericClient, ToStatement};
+
+use crate::{Error, FromRow};
+
+/// Queries of [GenericClient] that read their rows with [FromRow], implemented for clients and
+/// transactions.
+///
+/// ```
+/// let posts = client.query_as::<Post>(&statement, &[&category_id]).await?;
+/// let post = client.query_opt_as::<Post>(&statement, &[&post_id]).await?;
+/// ```
+pub trait GenericClientExt: GenericClient + Sync {
+    /// Like [GenericClient::query], every row is read as `T` with columns looked up once.
+    fn query_as<T: FromRow>(
+        &self,
+        statement: &(impl ToStatement + Sync + Send + ?Sized),
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Vec<T>, Error>> + Send {
+        async move {
+            let rows = self.query(statement, params).await?;
+
+            T::from_rows(&rows)
+        }
+    }
+
+    /// Like [GenericClient::query_one], the row is read as `T`.
+    fn query_one_as<T: FromRow>(
+        &self,
+        statement: &(impl ToStatement + Sync + Send + ?Sized),
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<T, Error>> + Send {
+        async move {
+            let row = self.query_one(statement, params).await?;
+
+            T::from_row(&row)
+        }
+    }
+
+    /// Like [GenericClient::query_opt], the row is read as `T` if there is one.
+    fn query_opt_as<T: FromRow>(
+        &self,
+        statement: &(impl ToStatement + Sync + Send + ?Sized),
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Option<T>, Error>> + Send {
+        async move {
+            match self.query_opt(statement, params).await? {
+                Some(row) => Ok(Some(T::from_row(&row)?)),
+                None => Ok(None),
+            }
+        }
+    }
+}
+
+impl<C: GenericClient + Sync> GenericClientExt for C {}
@@ -0,0 +1,78 @@
+use std::fmt;
+
+/// Errors of reading rows with [FromRow](crate::FromRow).
+#[derive(Debug)]
+pub enum Error {
+    /// The query sent by [GenericClientExt](crate::GenericClientExt) failed.
+    Query(tokio_postgres::Error),
+    /// A column that has no default is not in the row, columns of tuple structs are named by
+    /// their positions.
+    ColumnNotFound { column: String },
+    /// The value of a column could not be read as the rust type of its field.
+    Column {
+        column: String,
+        rust_type: &'static str,
+        source: tokio_postgres::Error,
+    },
+    /// The value of a column was read but `try_from` or `with` of its field failed.
+    Conversion {
+        column: String,
+        rust_type: &'static str,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl Error {
+    /// Name of the column the error is about, `None` if the query itself failed.
+    pub fn column(&self) -> Option<&str> {
+        match self {
+            Error::Query(_) => None,
+            Error::ColumnNotFound { column }
+            | Error::Column { column, .. }
+            | Error::Conversion { column, .. } => Some(column),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Query(source) => write!(f, "{}", source),
+            Error::ColumnNotFound { column } => write!(f, "column `{}` is not found", column),
+            Error::Column {
+                column,
+                rust_type,
+                source,
+            } => write!(
+                f,
+                "cannot read column `{}` as `{}`: {}",
+                column, rust_type, source
+            ),
+            Error::Conversion {
+                column,
+                rust_type,
+                source,
+            } => write!(
+                f,
+                "cannot convert column `{}` to `{}`: {}",
+                column, rust_type, source
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Query(source) | Error::Column { source, .. } => Some(source),
+            Error::Conversion { source, .. } => Some(source.as_ref()),
+            Error::ColumnNotFound { .. } => None,
+        }
+    }
+}
+
+impl From<tokio_postgres::Error> for Error {
+    fn from(error: tokio_postgres::Error) -> Self {
+        Error::Query(error)
+    }
+}
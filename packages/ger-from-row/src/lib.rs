@@ -1,130 +1,178 @@
 //! ```
-//! #[derive(ToSql, FromSql)]
+//! use ger_from_row::{FromRow, ToSql};
+//!
+//! #[derive(FromRow, ToSql, Debug)]
+//! #[fromrow(name = "t_user_role", rename_all = "lowercase")]
 //! enum UserRole {
 //!     Admin,
 //!     User,
+//!     #[fromrow(rename = "guest_user")]
+//!     Guest,
 //! }
 //!
 //! #[derive(FromRow)]
+//! #[fromrow(prefix = "user_")]
 //! struct User {
 //!     #[fromrow(field = "userUsername")]
-//!     user_username: String,
-//!     user_email: String,
-//!     user_role: UserRole,
+//!     username: String,
+//!     email: String,
+//!     role: UserRole,
 //! }
+//!
+//! #[derive(FromRow)]
+//! struct Count(i64);
+//!
+//! let users = client.query_as::<User>("select * from users", &[]).await?;
 //! ```
+//!
+//! Deriving `FromRow` on an enum maps its variants to the labels of a postgres enum type, which is
+//! the same as deriving `FromSql`, so the enum could be used as a field of structs deriving
+//! `FromRow`. Deriving `ToSql` as well lets the enum be used as a query parameter.
+//!
+//! Struct fields are read from the columns with the same names. `rename_all` and `prefix` on the
+//! struct change the column names of every field, the column given with `field` is used as it is.
+//! Fields of tuple structs are read by their position in the row.
+//!
+//! Columns are looked up by name once for the columns of a statement with [ColumnIndices], and
+//! then read by index from every row. [FromRow::from_rows] does this for a whole query result,
+//! and [GenericClientExt] does it right from the query.
+//!
+//! Reading fails with an [Error] that names the column and the rust type it is read as.
+//!
+//! Writes go the other way with `ToRow`, which gives the columns, the parameter types and the
+//! values of a struct for [Insert] to build inserts of many rows and upserts from.
+//!
+//! ```
+//! #[derive(ToRow)]
+//! #[torow(table = "student_names", prefix = "student_")]
+//! struct NewStudentName<'a> {
+//!     #[torow(field = "student_name_id")]
+//!     id: &'a str,
+//!     #[torow(field = "student_id")]
+//!     student_id: &'a str,
+//!     first_name: &'a str,
+//!     last_name: &'a str,
+//! }
+//!
+//! let insert = Insert::new(&names);
+//! let statement = client.prepare_typed(&insert.sql(), &insert.types()).await?;
+//! client.execute(&statement, &insert.params()).await?;
+//! ```
+//!
+//! Parameter types come from the rust types of the fields. Types the derive does not know, such
+//! as postgres enums, are left for postgres to infer unless `sql_type` names one of the
+//! constants of [Type](tokio_postgres::types::Type).
 
-use syn::ext::IdentExt;
+mod client;
+mod error;
+mod to_row;
 
-#[proc_macro_derive(FromRow, attributes(fromrow))]
-pub fn from_row(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let ast = syn::parse_macro_input!(input as syn::DeriveInput);
+use std::marker::PhantomData;
 
-    // match struct or enum implementation
-    match ast.data {
-        syn::Data::Struct(ref s) => {
-            return implement_struct_try_from(&ast, s);
-        }
-        _ => panic!("other than struct cannot be mapped"),
-    };
-}
+use tokio_postgres::{Column, Row};
 
-struct MacroAttr(String);
-
-/// Get field attribute from the struct/enum attributes. The function returns `None` for all failed
-/// cases and the actual value from the attribute in a form of a custom struct so the data can be
-/// used to further generate correct macro output.
-fn parse_field_attribute(attrs: &Vec<syn::Attribute>) -> Option<MacroAttr> {
-    for attr in attrs {
-        match attr.parse_meta() {
-            Ok(syn::Meta::List(ref ml)) => {
-                for seg in &ml.path.segments {
-                    if seg.ident == "fromrow" {
-                        for w in &ml.nested {
-                            match w {
-                                syn::NestedMeta::Meta(ref me) => match me {
-                                    syn::Meta::NameValue(ref nv) => {
-                                        if nv.path.is_ident("field") {
-                                            match nv.lit {
-                                                syn::Lit::Str(ref lit) => {
-                                                    return Some(MacroAttr(lit.value()));
-                                                }
-                                                _ => return None,
-                                            }
-                                        }
-                                    }
-                                    _ => {
-                                        return None;
-                                    }
-                                },
-                                _ => return None,
-                            }
-                        }
-                    }
-                }
-                return None;
-            }
-            _what => {
-                return None;
-            }
-        };
-    }
+pub use client::GenericClientExt;
+pub use error::Error;
+pub use ger_from_row_derive::{FromRow, FromSql, ToRow, ToSql};
+pub use to_row::{Insert, ToRow, MAX_PARAMS};
 
-    None
-}
+/// Used by the derived implementations, not part of the public api.
+#[doc(hidden)]
+pub mod __private {
+    use tokio_postgres::{types::FromSql, Row};
 
-fn implement_struct_try_from(
-    ast: &syn::DeriveInput,
-    struct_data: &syn::DataStruct,
-) -> proc_macro::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = &ast.generics.split_for_impl();
-    let name = &ast.ident;
+    use crate::Error;
 
-    let struct_fields = struct_data.fields.iter().map(|field| {
-        let ident = field.ident.as_ref().unwrap();
-        let ty = &field.ty;
+    pub use tokio_postgres;
 
-        let row_expr = format!(r##"{}"##, ident.unraw());
+    fn column_name(row: &Row, index: usize) -> String {
+        match row.columns().get(index) {
+            Some(c) => c.name().to_string(),
+            None => index.to_string(),
+        }
+    }
 
-        // field with attributes
-        if field.attrs.len() > 0 {
-            if let Some(new_name) = parse_field_attribute(&field.attrs) {
-                let type_name = new_name.0;
+    /// Read the column at `index` as `T`.
+    pub fn get<'a, T: FromSql<'a>>(row: &'a Row, index: usize) -> Result<T, Error> {
+        row.try_get(index).map_err(|source| Error::Column {
+            column: column_name(row, index),
+            rust_type: std::any::type_name::<T>(),
+            source,
+        })
+    }
 
-                return quote::quote! {
-                    #ident: row.try_get::<&str, #ty>(#type_name)?
-                };
-            }
-        }
+    /// Attach the column at `index` to the error of `try_from` or `with`.
+    pub fn convert<T, E>(row: &Row, index: usize, result: Result<T, E>) -> Result<T, Error>
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        result.map_err(|source| Error::Conversion {
+            column: column_name(row, index),
+            rust_type: std::any::type_name::<T>(),
+            source: source.into(),
+        })
+    }
+}
 
-        quote::quote! {
-            #ident: row.try_get::<&str, #ty>(#row_expr)?
-        }
-    });
+/// Types that could be read from a row of a query.
+///
+/// This is implemented with `#[derive(FromRow)]` on structs.
+pub trait FromRow: Sized {
+    /// Number of indices this type takes in [ColumnIndices], including flattened structs.
+    const COLUMN_COUNT: usize;
+
+    /// Push the indices of every column this type reads into `indices`, the names of the columns
+    /// are prefixed with `prefix`. Columns that are not found but have a default are pushed as
+    /// `None`, other missing columns are errors.
+    fn resolve_columns(
+        columns: &[Column],
+        prefix: &str,
+        indices: &mut Vec<Option<usize>>,
+    ) -> Result<(), Error>;
+
+    /// Read the type from a row with indices given by [FromRow::resolve_columns].
+    fn from_row_indexed(row: &Row, indices: &[Option<usize>]) -> Result<Self, Error>;
+
+    /// Read the type from a row, columns are looked up by name every time this is called.
+    fn from_row(row: &Row) -> Result<Self, Error> {
+        ColumnIndices::<Self>::new(row.columns())?.read(row)
+    }
 
-    let another_struct_fields = struct_fields.clone();
+    /// Read every row of a query result, columns are only looked up once since every row of a
+    /// statement has the same columns.
+    fn from_rows(rows: &[Row]) -> Result<Vec<Self>, Error> {
+        let first = match rows.first() {
+            Some(r) => r,
+            None => return Ok(vec![]),
+        };
 
-    let tokens = quote::quote! {
-        impl #impl_generics ::std::convert::TryFrom<&tokio_postgres::row::Row> for #name #ty_generics #where_clause {
-            type Error = anyhow::Error;
+        let indices = ColumnIndices::<Self>::new(first.columns())?;
 
-            fn try_from(row: &tokio_postgres::row::Row) -> ::std::result::Result<Self, Self::Error> {
-                Ok(Self {
-                    #(#struct_fields),*
-                })
-            }
-        }
+        rows.iter().map(|r| indices.read(r)).collect()
+    }
+}
 
-        impl #impl_generics ::std::convert::TryFrom<tokio_postgres::row::Row> for #name #ty_generics #where_clause {
-            type Error = anyhow::Error;
+/// Indices of the columns a type reads, resolved once from the columns of a statement and reused
+/// for every row of it.
+pub struct ColumnIndices<T> {
+    indices: Vec<Option<usize>>,
+    marker: PhantomData<fn() -> T>,
+}
 
-            fn try_from(row: tokio_postgres::row::Row) -> ::std::result::Result<Self, Self::Error> {
-                Ok(Self {
-                    #(#another_struct_fields),*
-                })
-            }
-        }
-    };
+impl<T: FromRow> ColumnIndices<T> {
+    /// Resolve indices from the columns of a statement, or of any row of it.
+    pub fn new(columns: &[Column]) -> Result<Self, Error> {
+        let mut indices = Vec::with_capacity(T::COLUMN_COUNT);
+        T::resolve_columns(columns, "", &mut indices)?;
+
+        Ok(Self {
+            indices,
+            marker: PhantomData,
+        })
+    }
 
-    tokens.into()
+    /// Read a row of the statement the indices are resolved from.
+    pub fn read(&self, row: &Row) -> Result<T, Error> {
+        T::from_row_indexed(row, &self.indices)
+    }
 }
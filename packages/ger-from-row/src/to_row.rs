@@ -0,0 +1,174 @@
+use tokio_postgres::types::{ToSql, Type};
+
+/// Most parameters postgres accepts in one statement.
+pub const MAX_PARAMS: usize = u16::MAX as usize;
+
+/// Types that could be written as a row of a table.
+///
+/// This is implemented with `#[derive(ToRow)]` on structs with named fields.
+pub trait ToRow {
+    /// Table the rows are written to.
+    const TABLE: &'static str;
+    /// Columns of the table, in the same order as [ToRow::TYPES] and [ToRow::params].
+    const COLUMNS: &'static [&'static str];
+    /// Types of the parameters, [Type::UNKNOWN] ones are left for postgres to infer, such as
+    /// postgres enums.
+    const TYPES: &'static [Type];
+
+    /// Values of the columns.
+    fn params(&self) -> Vec<&(dyn ToSql + Sync)>;
+
+    /// Columns joined for the column list of an insert, such as `user_id, user_username`.
+    fn column_list() -> String {
+        Self::COLUMNS.join(", ")
+    }
+
+    /// Placeholders of one row, numbered after the first `offset` parameters, such as
+    /// `($3, $4)` with an `offset` of 2.
+    fn placeholders(offset: usize) -> String {
+        let placeholders = (1..=Self::COLUMNS.len())
+            .map(|i| format!("${}", offset + i))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("({})", placeholders)
+    }
+}
+
+enum OnConflict<'a> {
+    DoNothing {
+        target: &'a [&'a str],
+    },
+    DoUpdate {
+        target: &'a [&'a str],
+        columns: &'a [&'a str],
+    },
+}
+
+/// Builder of an insert of one or more rows of the same type.
+///
+/// ```
+/// let insert = Insert::new(&students).on_conflict_do_nothing(&["student_nid"]);
+/// let statement = client
+///     .prepare_typed_cached(&insert.sql(), &insert.types())
+///     .await?;
+/// client.execute(&statement, &insert.params()).await?;
+/// ```
+pub struct Insert<'a, T> {
+    rows: &'a [T],
+    on_conflict: Option<OnConflict<'a>>,
+    returning: Option<&'a str>,
+}
+
+impl<'a, T: ToRow> Insert<'a, T> {
+    /// Insert every row of `rows`, which must not be empty.
+    pub fn new(rows: &'a [T]) -> Self {
+        assert!(!rows.is_empty(), "an insert needs at least one row");
+
+        Self {
+            rows,
+            on_conflict: None,
+            returning: None,
+        }
+    }
+
+    /// Split `rows` into inserts that each stay under [MAX_PARAMS], `rows` could be empty.
+    pub fn chunks(rows: &'a [T]) -> impl Iterator<Item = Self> {
+        let size = (MAX_PARAMS / T::COLUMNS.len().max(1)).max(1);
+
+        rows.chunks(size).map(Self::new)
+    }
+
+    /// Skip rows that conflict on the `target` columns, or on any constraint when `target` is
+    /// empty.
+    pub fn on_conflict_do_nothing(mut self, target: &'a [&'a str]) -> Self {
+        self.on_conflict = Some(OnConflict::DoNothing { target });
+        self
+    }
+
+    /// Update `columns` of rows that conflict on the `target` columns to the inserted values,
+    /// every column that is not in `target` is updated when `columns` is empty.
+    pub fn on_conflict_do_update(mut self, target: &'a [&'a str], columns: &'a [&'a str]) -> Self {
+        self.on_conflict = Some(OnConflict::DoUpdate { target, columns });
+        self
+    }
+
+    /// Return `returning` of the inserted rows, such as `student_id`.
+    pub fn returning(mut self, returning: &'a str) -> Self {
+        self.returning = Some(returning);
+        self
+    }
+
+    /// Statement of the insert.
+    pub fn sql(&self) -> String {
+        let values = (0..self.rows.len())
+            .map(|i| T::placeholders(i * T::COLUMNS.len()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut sql = format!(
+            "insert into {} ({}) values {}",
+            T::TABLE,
+            T::column_list(),
+            values
+        );
+
+        match self.on_conflict {
+            Some(OnConflict::DoNothing { target }) => {
+                sql.push_str(&format!(
+                    " on conflict {}do nothing",
+                    conflict_target(target)
+                ));
+            }
+            Some(OnConflict::DoUpdate { target, columns }) => {
+                let updated = match columns {
+                    [] => T::COLUMNS
+                        .iter()
+                        .filter(|c| !target.contains(c))
+                        .copied()
+                        .collect::<Vec<_>>(),
+                    columns => columns.to_vec(),
+                };
+                let assignments = updated
+                    .iter()
+                    .map(|c| format!("{} = excluded.{}", c, c))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                sql.push_str(&format!(
+                    " on conflict {}do update set {}",
+                    conflict_target(target),
+                    assignments
+                ));
+            }
+            None => (),
+        }
+
+        if let Some(returning) = self.returning {
+            sql.push_str(" returning ");
+            sql.push_str(returning);
+        }
+
+        sql
+    }
+
+    /// Types of every parameter of [Insert::sql].
+    pub fn types(&self) -> Vec<Type> {
+        self.rows
+            .iter()
+            .flat_map(|_| T::TYPES.iter().cloned())
+            .collect()
+    }
+
+    /// Values of every parameter of [Insert::sql].
+    pub fn params(&self) -> Vec<&(dyn ToSql + Sync)> {
+        self.rows.iter().flat_map(|r| r.params()).collect()
+    }
+}
+
+fn conflict_target(target: &[&str]) -> String {
+    match target {
+        [] => String::new(),
+        target => format!("({}) ", target.join(", ")),
+    }
+}
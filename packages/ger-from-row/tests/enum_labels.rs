@@ -0,0 +1,68 @@
+use bytes::BytesMut;
+use ger_from_row::*;
+// the traits are imported under other names so they do not shadow the derives
+use tokio_postgres::types::{self as pg, FromSql as _, Kind, ToSql as _, Type};
+
+#[derive(FromRow, ToSql, Debug, PartialEq)]
+#[fromrow(name = "t_day_of_week", rename_all = "snake_case")]
+enum DayOfWeek {
+    Sunday,
+    Monday,
+    #[fromrow(rename = "holiday")]
+    PublicHoliday,
+}
+
+#[derive(FromSql, ToSql, Debug, PartialEq)]
+#[fromrow(rename_all = "SCREAMING-KEBAB-CASE")]
+enum Status {
+    InProgress,
+    Done,
+}
+
+fn enum_type(name: &str, labels: &[&str]) -> Type {
+    Type::new(
+        name.to_string(),
+        0,
+        Kind::Enum(labels.iter().map(|l| l.to_string()).collect()),
+        "public".to_string(),
+    )
+}
+
+fn main() {
+    let day = enum_type("t_day_of_week", &["monday", "holiday", "sunday"]);
+
+    assert!(<DayOfWeek as pg::FromSql>::accepts(&day));
+    assert!(<DayOfWeek as pg::ToSql>::accepts(&day));
+    assert_eq!(
+        DayOfWeek::from_sql(&day, b"holiday").unwrap(),
+        DayOfWeek::PublicHoliday
+    );
+    assert!(DayOfWeek::from_sql(&day, b"public_holiday").is_err());
+
+    let mut out = BytesMut::new();
+    DayOfWeek::Sunday.to_sql_checked(&day, &mut out).unwrap();
+    assert_eq!(&out[..], b"sunday");
+
+    // another name, missing labels, or not an enum
+    assert!(!<DayOfWeek as pg::FromSql>::accepts(&enum_type(
+        "t_weekday",
+        &["monday", "holiday", "sunday"]
+    )));
+    assert!(!<DayOfWeek as pg::FromSql>::accepts(&enum_type(
+        "t_day_of_week",
+        &["monday", "sunday"]
+    )));
+    assert!(!<DayOfWeek as pg::FromSql>::accepts(&Type::TEXT));
+
+    // any name is accepted without `name`
+    let status = enum_type("t_status", &["IN-PROGRESS", "DONE"]);
+    assert!(<Status as pg::FromSql>::accepts(&status));
+    assert_eq!(
+        Status::from_sql(&status, b"IN-PROGRESS").unwrap(),
+        Status::InProgress
+    );
+
+    let mut out = BytesMut::new();
+    Status::Done.to_sql_checked(&status, &mut out).unwrap();
+    assert_eq!(&out[..], b"DONE");
+}
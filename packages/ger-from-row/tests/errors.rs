@@ -0,0 +1,60 @@
+use std::error::Error as _;
+
+use ger_from_row::{Error, FromRow, GenericClientExt};
+use tokio_postgres::GenericClient;
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+struct Post {
+    id: String,
+    #[fromrow(with = "parse_tags")]
+    tags: Vec<String>,
+}
+
+fn parse_tags(tags: String) -> Result<Vec<String>, String> {
+    if tags.is_empty() {
+        return Err("tags are empty".to_string());
+    }
+
+    Ok(tags.split(',').map(|t| t.to_string()).collect())
+}
+
+// the queries are not sent, this only has to compile for every client.
+#[allow(dead_code)]
+async fn read_posts<C: GenericClient + Sync>(client: &C) -> Result<(), Error> {
+    let _posts = client
+        .query_as::<Post>("select id, tags from posts", &[])
+        .await?;
+    let _post: Post = client
+        .query_one_as("select id, tags from posts where id = $1", &[&"id"])
+        .await?;
+    let _post = client
+        .query_opt_as::<Post>("select id, tags from posts where id = $1", &[&"id"])
+        .await?;
+
+    Ok(())
+}
+
+fn main() {
+    let not_found = Error::ColumnNotFound {
+        column: "author_user_id".to_string(),
+    };
+    assert_eq!(
+        not_found.to_string(),
+        "column `author_user_id` is not found"
+    );
+    assert_eq!(not_found.column(), Some("author_user_id"));
+    assert!(not_found.source().is_none());
+
+    let conversion = Error::Conversion {
+        column: "tags".to_string(),
+        rust_type: std::any::type_name::<Vec<String>>(),
+        source: parse_tags(String::new()).unwrap_err().into(),
+    };
+    assert_eq!(
+        conversion.to_string(),
+        "cannot convert column `tags` to `alloc::vec::Vec<alloc::string::String>`: tags are empty"
+    );
+    assert_eq!(conversion.column(), Some("tags"));
+    assert_eq!(conversion.source().unwrap().to_string(), "tags are empty");
+}
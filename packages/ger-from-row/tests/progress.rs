@@ -6,4 +6,11 @@ fn tests() {
     t.pass("tests/simple_struct_with_rename.rs");
     t.pass("tests/simple_struct_with_enum.rs");
     t.pass("tests/simple_struct_with_enum_without_rename.rs");
+    t.pass("tests/enum_labels.rs");
+    t.pass("tests/struct_with_field_attributes.rs");
+    t.pass("tests/struct_with_rename_all.rs");
+    t.pass("tests/tuple_struct.rs");
+    t.pass("tests/errors.rs");
+    t.pass("tests/to_row.rs");
+    t.compile_fail("tests/ui/*.rs");
 }
@@ -1,6 +1,7 @@
 use ger_from_row::*;
 
 #[derive(FromRow)]
+#[allow(dead_code)]
 enum SimpleEnum {
     Here,
     There,
@@ -14,7 +14,7 @@ struct SimpleStructWithRename {
     user_username: String,
     user_name: String,
     email: String,
-    #[fromrow(num = "user_role")]
+    #[fromrow(field = "user_role")]
     role: Role,
 }
 
@@ -14,7 +14,6 @@ struct SimpleStructWithRename {
     user_username: String,
     user_name: String,
     email: String,
-    #[fromrow(num)]
     role: Role,
 }
 
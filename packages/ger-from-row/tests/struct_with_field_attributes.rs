@@ -0,0 +1,52 @@
+// nothing here is read at runtime, the file only has to compile
+#![allow(dead_code)]
+
+use ger_from_row::*;
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+struct Author {
+    user_id: String,
+    user_username: String,
+}
+
+#[derive(Debug)]
+struct Credit(u8);
+
+impl TryFrom<i32> for Credit {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        Ok(Credit(u8::try_from(value)?))
+    }
+}
+
+fn parse_tags(tags: String) -> Result<Vec<String>, std::convert::Infallible> {
+    Ok(tags.split(',').map(|t| t.to_string()).collect())
+}
+
+fn default_title() -> String {
+    "untitled".to_string()
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+struct Post {
+    forum_post_id: String,
+    #[fromrow(default = "default_title")]
+    forum_post_name: String,
+    #[fromrow(flatten, prefix = "author_")]
+    author: Author,
+    #[fromrow(flatten)]
+    editor: Author,
+    #[fromrow(field = "subject_credit", try_from = "i32")]
+    credit: Credit,
+    #[fromrow(with = "parse_tags", default)]
+    tags: Vec<String>,
+    #[fromrow(skip)]
+    is_read: bool,
+    #[fromrow(skip, default = "default_title")]
+    note: String,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+#[fromrow(prefix = "forum_post_")]
+#[allow(dead_code)]
+struct ForumPost {
+    id: String,
+    name: String,
+    // read as it is, without the prefix
+    #[fromrow(field = "user_id")]
+    author_id: String,
+}
+
+#[derive(FromRow)]
+#[fromrow(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct UserSession {
+    user_session_id: String,
+    user_session_refresh_token: String,
+}
+
+fn main() {}
@@ -0,0 +1,110 @@
+use ger_from_row::{Insert, ToRow, ToSql};
+use rust_decimal::Decimal;
+use tokio_postgres::types::Type;
+
+#[derive(ToSql, Debug)]
+#[fromrow(name = "role", rename_all = "lowercase")]
+enum Role {
+    Student,
+}
+
+#[derive(ToRow)]
+#[torow(table = "users", prefix = "user_")]
+struct NewUser<'a> {
+    #[torow(field = "user_id")]
+    id: &'a str,
+    username: String,
+    email: Option<String>,
+    role: Role,
+    birthdate: time::Date,
+    #[torow(skip)]
+    #[allow(dead_code)]
+    password_plain: String,
+}
+
+#[derive(ToRow)]
+#[torow(table = "students")]
+struct NewStudent {
+    student_id: String,
+    #[torow(sql_type = "NUMERIC")]
+    student_previous_school_gpa: f64,
+    student_gpax: Decimal,
+}
+
+fn user(id: &str) -> NewUser<'_> {
+    NewUser {
+        id,
+        username: "grindarius".to_string(),
+        email: None,
+        role: Role::Student,
+        birthdate: time::Date::from_calendar_date(2001, time::Month::May, 12).unwrap(),
+        password_plain: "secret".to_string(),
+    }
+}
+
+fn main() {
+    assert_eq!(NewUser::TABLE, "users");
+    assert_eq!(
+        NewUser::COLUMNS,
+        &[
+            "user_id",
+            "user_username",
+            "user_email",
+            "user_role",
+            "user_birthdate"
+        ]
+    );
+    assert_eq!(
+        NewUser::TYPES,
+        &[
+            Type::TEXT,
+            Type::TEXT,
+            Type::TEXT,
+            Type::UNKNOWN,
+            Type::DATE
+        ]
+    );
+    assert_eq!(
+        NewStudent::TYPES,
+        &[Type::TEXT, Type::NUMERIC, Type::NUMERIC]
+    );
+    assert_eq!(NewUser::placeholders(5), "($6, $7, $8, $9, $10)");
+
+    let users = [user("a"), user("b")];
+    assert_eq!(users[0].params().len(), 5);
+
+    let insert = Insert::new(&users);
+    assert_eq!(
+        insert.sql(),
+        "insert into users (user_id, user_username, user_email, user_role, user_birthdate) \
+         values ($1, $2, $3, $4, $5), ($6, $7, $8, $9, $10)"
+    );
+    assert_eq!(insert.types().len(), 10);
+    assert_eq!(insert.params().len(), 10);
+
+    let insert = Insert::new(&users)
+        .on_conflict_do_nothing(&[])
+        .returning("user_id");
+    assert!(insert
+        .sql()
+        .ends_with("($6, $7, $8, $9, $10) on conflict do nothing returning user_id"));
+
+    let insert = Insert::new(&users[..1]).on_conflict_do_update(&["user_id"], &[]);
+    assert!(insert.sql().ends_with(
+        "on conflict (user_id) do update set user_username = excluded.user_username, \
+         user_email = excluded.user_email, user_role = excluded.user_role, \
+         user_birthdate = excluded.user_birthdate"
+    ));
+
+    let insert = Insert::new(&users[..1]).on_conflict_do_update(&["user_id"], &["user_email"]);
+    assert!(insert
+        .sql()
+        .ends_with("on conflict (user_id) do update set user_email = excluded.user_email"));
+
+    let many = (0..30000).map(|_| user("c")).collect::<Vec<_>>();
+    let sizes = Insert::chunks(&many)
+        .map(|i| i.params().len())
+        .collect::<Vec<_>>();
+    assert_eq!(sizes, vec![65535, 65535, 18930]);
+    assert_eq!(Insert::<NewUser>::chunks(&[]).count(), 0);
+}
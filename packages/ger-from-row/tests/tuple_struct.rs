@@ -0,0 +1,32 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+struct Count(i64);
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+struct Pair(
+    String,
+    #[fromrow(skip)] bool,
+    #[fromrow(field = "total")] i64,
+    i32,
+);
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+struct Page<T: FromRow> {
+    #[fromrow(flatten)]
+    item: T,
+    total: i64,
+}
+
+#[derive(FromRow)]
+struct Nothing;
+
+fn main() {
+    assert_eq!(Count::COLUMN_COUNT, 1);
+    assert_eq!(Pair::COLUMN_COUNT, 3);
+    assert_eq!(Page::<Pair>::COLUMN_COUNT, 4);
+    assert_eq!(Nothing::COLUMN_COUNT, 0);
+}
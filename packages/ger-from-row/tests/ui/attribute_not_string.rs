@@ -0,0 +1,9 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+struct User {
+    #[fromrow(field = 1)]
+    user_username: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+struct Post {
+    #[fromrow(skip, try_from = "i32")]
+    credit: i64,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+#[fromrow(rename_all = "lowercase")]
+enum Role {
+    Admin,
+    #[fromrow(rename = "admin")]
+    Root,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+enum Role {
+    Admin,
+    Student(String),
+}
+
+fn main() {}
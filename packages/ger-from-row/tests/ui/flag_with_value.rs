@@ -0,0 +1,9 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+struct Post {
+    #[fromrow(skip = "true")]
+    is_read: bool,
+}
+
+fn main() {}
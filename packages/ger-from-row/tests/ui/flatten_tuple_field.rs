@@ -0,0 +1,9 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+struct Count(i64);
+
+#[derive(FromRow)]
+struct Page(#[fromrow(flatten)] Count, i64);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use ger_from_row::*;
+
+#[derive(FromSql)]
+struct User {
+    user_username: String,
+}
+
+fn main() {}
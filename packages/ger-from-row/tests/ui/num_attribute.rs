@@ -0,0 +1,9 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+struct User {
+    #[fromrow(num = "user_role")]
+    role: String,
+}
+
+fn main() {}
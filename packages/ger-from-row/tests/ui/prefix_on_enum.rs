@@ -0,0 +1,10 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+#[fromrow(prefix = "role_")]
+enum Role {
+    Admin,
+    Student,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+#[fromrow(prefix = "forum_post_")]
+struct Post(String, String);
+
+fn main() {}
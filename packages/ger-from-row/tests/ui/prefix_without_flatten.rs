@@ -0,0 +1,9 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+struct Post {
+    #[fromrow(prefix = "author_")]
+    author_id: String,
+}
+
+fn main() {}
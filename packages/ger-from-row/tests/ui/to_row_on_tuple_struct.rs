@@ -0,0 +1,7 @@
+use ger_from_row::ToRow;
+
+#[derive(ToRow)]
+#[torow(table = "users")]
+struct NewUser(String);
+
+fn main() {}
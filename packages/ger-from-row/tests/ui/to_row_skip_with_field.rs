@@ -0,0 +1,11 @@
+use ger_from_row::ToRow;
+
+#[derive(ToRow)]
+#[torow(table = "users")]
+struct NewUser {
+    user_id: String,
+    #[torow(skip, field = "user_password")]
+    password: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use ger_from_row::ToRow;
+
+#[derive(ToRow)]
+struct NewUser {
+    user_id: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+union Number {
+    int: i32,
+    float: f32,
+}
+
+fn main() {}
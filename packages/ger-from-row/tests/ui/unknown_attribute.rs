@@ -0,0 +1,9 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+struct User {
+    #[fromrow(column = "userUsername")]
+    user_username: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use ger_from_row::*;
+
+#[derive(FromRow)]
+#[fromrow(rename_all = "Title Case")]
+enum Role {
+    Admin,
+    Student,
+}
+
+fn main() {}
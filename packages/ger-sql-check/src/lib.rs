@@ -0,0 +1,166 @@
+//! Checks the statements written in the backend against the schema of the database, without a
+//! database.
+//!
+//! Every string literal given to `prepare_typed_cached` and the other methods of clients that
+//! take a statement is parsed, together with the insert statement of every struct deriving
+//! `ToRow`, made from its table, its columns after `field`, `prefix` and `rename_all`, and the
+//! `sql_type` of its fields, and
+//!
+//! - tables have to be created in the schema,
+//! - columns have to exist in the tables, aliases and subqueries of the statement,
+//! - parameters stored in or compared to a column have to be of a type postgres converts to the
+//!   type of the column, when the statement is prepared with the types of its parameters.
+//!
+//! ```
+//! let problems = ger_sql_check::check_dir("schema.sql", "src")?;
+//! ```
+//!
+//! Statements built at runtime are not checked, and columns of subqueries and functions in the
+//! from clause are taken as they are.
+
+mod schema;
+mod sources;
+mod statements;
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+pub use schema::{definitions, Schema};
+pub use sqlparser;
+
+/// A problem of a statement, pointing at where the statement is written.
+#[derive(Debug)]
+pub struct Problem {
+    pub file: PathBuf,
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file.display(), self.line, self.message)
+    }
+}
+
+/// Check every statement of one rust source file.
+pub fn check_source(schema: &Schema, file: &Path, source: &str) -> Vec<Problem> {
+    let statements = match sources::find_statements(source) {
+        Ok(s) => s,
+        Err(e) => {
+            return vec![Problem {
+                file: file.to_path_buf(),
+                line: e.span().start().line,
+                message: format!("cannot parse the file: {}", e),
+            }]
+        }
+    };
+
+    statements
+        .iter()
+        .flat_map(|s| {
+            statements::check_statement(schema, &s.sql, s.types.as_deref())
+                .into_iter()
+                .map(|message| Problem {
+                    file: file.to_path_buf(),
+                    line: s.line,
+                    message,
+                })
+        })
+        .collect()
+}
+
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            rust_files(&path, files)?;
+        } else if path.extension().is_some_and(|e| e == "rs") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Check every `.rs` file under `source_dir` against the schema file.
+pub fn check_dir(
+    schema_file: impl AsRef<Path>,
+    source_dir: impl AsRef<Path>,
+) -> Result<Vec<Problem>, String> {
+    let schema_file = schema_file.as_ref();
+    let schema = std::fs::read_to_string(schema_file)
+        .map_err(|e| format!("cannot read {}: {}", schema_file.display(), e))?;
+    let schema = Schema::parse(&schema)?;
+
+    let mut files = vec![];
+    rust_files(source_dir.as_ref(), &mut files)
+        .map_err(|e| format!("cannot read {}: {}", source_dir.as_ref().display(), e))?;
+    files.sort();
+
+    let mut problems = vec![];
+    for file in files {
+        let source = std::fs::read_to_string(&file)
+            .map_err(|e| format!("cannot read {}: {}", file.display(), e))?;
+
+        problems.append(&mut check_source(&schema, &file, &source));
+    }
+
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{check_source, Schema};
+
+    #[test]
+    fn test_check_to_row_structs() {
+        let schema = Schema::parse(
+            r##"
+            create table student_names (
+                student_id text not null,
+                student_first_name text not null,
+                student_last_name text not null
+            );
+            "##,
+        )
+        .unwrap();
+
+        let problems = check_source(
+            &schema,
+            Path::new("src/names.rs"),
+            r####"
+            #[derive(ToRow)]
+            #[torow(table = "student_names", prefix = "student_")]
+            struct NewStudentName<'a> {
+                id: &'a str,
+                firstname: &'a str,
+                #[torow(sql_type = "INT4")]
+                last_name: &'a str,
+            }
+
+            #[derive(ToRow)]
+            #[torow(table = "student_nicknames")]
+            struct NewStudentNickname<'a> {
+                student_id: &'a str,
+            }
+            "####,
+        )
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            problems,
+            vec![
+                "src/names.rs:4: column `student_firstname` does not exist in table `student_names`",
+                "src/names.rs:4: parameter `$3` is `Type::INT4` but column `student_names.student_last_name` does not take it",
+                "src/names.rs:13: table `student_nicknames` does not exist",
+            ]
+        );
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::{HashMap, HashSet};
+
+use sqlparser::{
+    ast::{
+        AlterColumnOperation, AlterTableOperation, ColumnOption, ColumnOptionDef, CreateTable,
+        DataType, Ident, ObjectType, Statement, UserDefinedTypeRepresentation,
+    },
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+    tokenizer::{Token, Tokenizer},
+};
+
+/// Group of postgres types that postgres converts between on its own, such as `int4` and
+/// `numeric`, a parameter of one type could be compared to or stored in a column of another
+/// type in the same group.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeFamily {
+    Number,
+    Text,
+    /// `date`, `timestamp` and `timestamptz`.
+    DateTime,
+    Time,
+    Bool,
+    Json,
+    Point,
+    /// A postgres enum with its name, only parameters left for postgres to infer fit in it.
+    Enum(String),
+}
+
+impl TypeFamily {
+    /// Family of a constant of `postgres_types::Type`, `None` for types that are not checked
+    /// such as arrays and `UNKNOWN`.
+    pub fn of_param(constant: &str) -> Option<TypeFamily> {
+        Some(match constant {
+            "INT2" | "INT4" | "INT8" | "NUMERIC" | "FLOAT4" | "FLOAT8" | "OID" => {
+                TypeFamily::Number
+            }
+            "TEXT" | "VARCHAR" | "BPCHAR" | "NAME" | "CHAR" => TypeFamily::Text,
+            "DATE" | "TIMESTAMP" | "TIMESTAMPTZ" => TypeFamily::DateTime,
+            "TIME" | "TIMETZ" => TypeFamily::Time,
+            "BOOL" => TypeFamily::Bool,
+            "JSON" | "JSONB" => TypeFamily::Json,
+            "POINT" => TypeFamily::Point,
+            _ => return None,
+        })
+    }
+
+    /// Family of the type of a column, `None` for types that are not checked.
+    fn of_column(data_type: &DataType, enums: &HashSet<String>) -> Option<TypeFamily> {
+        let name = data_type.to_string().to_lowercase();
+
+        if enums.contains(&name) {
+            return Some(TypeFamily::Enum(name));
+        }
+
+        // only the name of the type matters, not its precision such as `numeric(6, 2)`.
+        let base = name.split('(').next().unwrap_or_default().trim();
+
+        Some(match base {
+            "smallint" | "int" | "integer" | "bigint" | "int2" | "int4" | "int8" | "serial"
+            | "bigserial" | "numeric" | "decimal" | "real" | "double precision" | "float4"
+            | "float8" => TypeFamily::Number,
+            "text" | "varchar" | "character varying" | "char" | "character" => TypeFamily::Text,
+            "date" | "timestamp" | "timestamptz" | "timestamp with time zone" => {
+                TypeFamily::DateTime
+            }
+            "time" | "timetz" | "time with time zone" => TypeFamily::Time,
+            "boolean" | "bool" => TypeFamily::Bool,
+            "json" | "jsonb" => TypeFamily::Json,
+            "point" => TypeFamily::Point,
+            _ => return None,
+        })
+    }
+}
+
+/// Name of the table or type created by a definition.
+fn defined_name(definition: &Statement) -> Option<String> {
+    match definition {
+        Statement::CreateTable(table) => Some(table.name.to_string().to_lowercase()),
+        Statement::CreateType { name, .. } => Some(name.to_string().to_lowercase()),
+        _ => None,
+    }
+}
+
+/// Apply `alter table` to the columns of a created table.
+fn alter_table(table: &mut CreateTable, operations: Vec<AlterTableOperation>) {
+    let same = |a: &Ident, b: &Ident| a.value.to_lowercase() == b.value.to_lowercase();
+
+    for operation in operations {
+        match operation {
+            AlterTableOperation::AddColumn { column_def, .. } => table.columns.push(column_def),
+            AlterTableOperation::DropColumn { column_name, .. } => {
+                table.columns.retain(|c| !same(&c.name, &column_name))
+            }
+            AlterTableOperation::RenameColumn {
+                old_column_name,
+                new_column_name,
+            } => {
+                if let Some(column) = table
+                    .columns
+                    .iter_mut()
+                    .find(|c| same(&c.name, &old_column_name))
+                {
+                    column.name = new_column_name;
+                }
+            }
+            AlterTableOperation::RenameTable { table_name } => table.name = table_name,
+            AlterTableOperation::AlterColumn { column_name, op } => {
+                let Some(column) = table
+                    .columns
+                    .iter_mut()
+                    .find(|c| same(&c.name, &column_name))
+                else {
+                    continue;
+                };
+
+                match op {
+                    AlterColumnOperation::SetNotNull => column.options.push(ColumnOptionDef {
+                        name: None,
+                        option: ColumnOption::NotNull,
+                    }),
+                    AlterColumnOperation::DropNotNull => column
+                        .options
+                        .retain(|o| !matches!(o.option, ColumnOption::NotNull)),
+                    AlterColumnOperation::SetDataType { data_type, .. } => {
+                        column.data_type = data_type
+                    }
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Parse the statements of a schema that define tables and types, and give back the `create
+/// table` and `create type` statements as they are at the end of the schema, with `alter table`
+/// and `drop` applied to them. Other statements are not parsed, so the ones the parser does not
+/// understand such as pgroonga indexes do not fail the whole schema.
+pub fn definitions(sql: &str) -> Result<Vec<Statement>, String> {
+    let dialect = PostgreSqlDialect {};
+    let tokens = Tokenizer::new(&dialect, sql)
+        .tokenize()
+        .map_err(|e| format!("cannot read the schema: {}", e))?;
+
+    let mut definitions = vec![];
+
+    for statement in tokens.split(|t| *t == Token::SemiColon) {
+        let words = statement
+            .iter()
+            .filter_map(|t| match t {
+                Token::Word(w) => Some(w.value.to_lowercase()),
+                Token::Whitespace(_) => None,
+                _ => Some(String::new()),
+            })
+            .take(2)
+            .collect::<Vec<_>>();
+        let is_definition = matches!(
+            words.iter().map(|w| w.as_str()).collect::<Vec<_>>()[..],
+            ["create" | "alter" | "drop", "table" | "type"]
+        );
+        if !is_definition {
+            continue;
+        }
+
+        let parsed = Parser::new(&dialect)
+            .with_tokens(statement.to_vec())
+            .parse_statement()
+            .map_err(|e| format!("cannot read the schema: {}", e))?;
+
+        match parsed {
+            Statement::CreateTable(_) | Statement::CreateType { .. } => definitions.push(parsed),
+            Statement::Drop {
+                object_type: ObjectType::Table | ObjectType::Type,
+                names,
+                ..
+            } => {
+                let names = names
+                    .iter()
+                    .map(|n| n.to_string().to_lowercase())
+                    .collect::<Vec<_>>();
+                definitions.retain(|d| !defined_name(d).is_some_and(|n| names.contains(&n)));
+            }
+            Statement::AlterTable {
+                name, operations, ..
+            } => {
+                let name = name.to_string().to_lowercase();
+                let table = definitions
+                    .iter_mut()
+                    .find_map(|d| match d {
+                        Statement::CreateTable(t) if t.name.to_string().to_lowercase() == name => {
+                            Some(t)
+                        }
+                        _ => None,
+                    })
+                    .ok_or_else(|| format!("cannot alter table `{}` before it is created", name))?;
+
+                alter_table(table, operations);
+            }
+            _ => (),
+        }
+    }
+
+    Ok(definitions)
+}
+
+/// A column of a table of the schema.
+#[derive(Debug)]
+pub struct Column {
+    pub name: String,
+    pub family: Option<TypeFamily>,
+}
+
+/// Tables of the schema.
+#[derive(Debug, Default)]
+pub struct Schema {
+    tables: HashMap<String, Vec<Column>>,
+}
+
+impl Schema {
+    /// Read every table and enum type of a schema, as they are after every `alter table` and
+    /// `drop` of it.
+    pub fn parse(sql: &str) -> Result<Schema, String> {
+        let mut enums = HashSet::new();
+        let mut tables = vec![];
+
+        for statement in definitions(sql)? {
+            match statement {
+                Statement::CreateType {
+                    name,
+                    representation: UserDefinedTypeRepresentation::Enum { .. },
+                } => {
+                    enums.insert(name.to_string().to_lowercase());
+                }
+                Statement::CreateTable(table) => tables.push(table),
+                _ => (),
+            }
+        }
+
+        let tables = tables
+            .into_iter()
+            .map(|t| {
+                let columns = t
+                    .columns
+                    .iter()
+                    .map(|c| Column {
+                        name: c.name.value.to_lowercase(),
+                        family: TypeFamily::of_column(&c.data_type, &enums),
+                    })
+                    .collect();
+
+                (t.name.to_string().to_lowercase(), columns)
+            })
+            .collect();
+
+        Ok(Schema { tables })
+    }
+
+    /// Columns of a table, `None` if the table does not exist.
+    pub fn table(&self, name: &str) -> Option<&[Column]> {
+        self.tables.get(name).map(|c| c.as_slice())
+    }
+
+    pub fn column(&self, table: &str, column: &str) -> Option<&Column> {
+        self.table(table)?.iter().find(|c| c.name == column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Schema, TypeFamily};
+
+    #[test]
+    fn test_parse() {
+        let schema = Schema::parse(
+            r##"
+            create extension if not exists pgroonga;
+            drop type t_user_role cascade;
+            create type t_user_role as enum ('admin', 'student');
+
+            create table users (
+                user_id text not null unique,
+                user_role t_user_role not null,
+                user_gpa numeric(3, 2),
+                user_created_timestamp timestamptz not null default now(),
+                primary key (user_id)
+            );
+
+            create index if not exists pgroonga_users_index on users using pgroonga (
+                user_id pgroonga_text_full_text_search_ops_v2
+            );
+            "##,
+        )
+        .unwrap();
+
+        let family = |column| schema.column("users", column).unwrap().family.clone();
+
+        assert_eq!(schema.table("users").unwrap().len(), 4);
+        assert_eq!(family("user_id"), Some(TypeFamily::Text));
+        assert_eq!(
+            family("user_role"),
+            Some(TypeFamily::Enum("t_user_role".to_string()))
+        );
+        assert_eq!(family("user_gpa"), Some(TypeFamily::Number));
+        assert_eq!(family("user_created_timestamp"), Some(TypeFamily::DateTime));
+        assert!(schema.table("students").is_none());
+    }
+
+    #[test]
+    fn test_parse_alter_and_drop() {
+        let schema = Schema::parse(
+            r##"
+            create table users (
+                user_id text not null unique,
+                user_name text not null,
+                user_gpa numeric(3, 2)
+            );
+            create table sessions (session_id text not null);
+
+            alter table users add column user_email text not null default '';
+            alter table users drop column user_gpa;
+            alter table users rename column user_name to user_username;
+            alter table users alter column user_id type int8;
+            alter table users add constraint email_constraint check (user_email ~* '@');
+            drop table sessions cascade;
+            "##,
+        )
+        .unwrap();
+
+        let columns = schema
+            .table("users")
+            .unwrap()
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(columns, vec!["user_id", "user_username", "user_email"]);
+        assert_eq!(
+            schema.column("users", "user_id").unwrap().family,
+            Some(TypeFamily::Number)
+        );
+        assert!(schema.table("sessions").is_none());
+        assert!(Schema::parse("alter table users add column user_id text;").is_err());
+    }
+}
@@ -0,0 +1,317 @@
+use syn::{ext::IdentExt, visit::Visit};
+
+/// Methods of clients and transactions whose first argument is a statement.
+const STATEMENT_METHODS: &[&str] = &[
+    "prepare",
+    "prepare_cached",
+    "prepare_typed",
+    "prepare_typed_cached",
+    "query",
+    "query_one",
+    "query_opt",
+    "query_raw",
+    "execute",
+    "query_as",
+    "query_one_as",
+    "query_opt_as",
+];
+
+/// A statement written as a string literal in the source, or the insert statement of a struct
+/// deriving `ToRow`.
+#[derive(Debug)]
+pub struct SourceStatement {
+    pub line: usize,
+    pub sql: String,
+    /// Constants of `Type` given to `prepare_typed` and `prepare_typed_cached`, `None` when the
+    /// types are not written as an array of constants.
+    pub types: Option<Vec<String>>,
+}
+
+/// Take the string literal out of `"..."`, `&"..."` and `("...")`.
+fn string_literal(expr: &syn::Expr) -> Option<&syn::LitStr> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(s),
+            ..
+        }) => Some(s),
+        syn::Expr::Reference(r) => string_literal(&r.expr),
+        syn::Expr::Paren(p) => string_literal(&p.expr),
+        syn::Expr::Group(g) => string_literal(&g.expr),
+        _ => None,
+    }
+}
+
+/// Read `&[Type::TEXT, Type::INT4]` as the names of the constants.
+fn type_constants(expr: &syn::Expr) -> Option<Vec<String>> {
+    match expr {
+        syn::Expr::Reference(r) => type_constants(&r.expr),
+        syn::Expr::Array(array) => array
+            .elems
+            .iter()
+            .map(|e| match e {
+                syn::Expr::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+/// Read the `key = "value"` and `key` items of the `#[torow(...)]` attributes, attributes that do
+/// not parse are left for the derive to report.
+fn torow_items(attrs: &[syn::Attribute]) -> Vec<(String, Option<String>)> {
+    let mut items = vec![];
+
+    for a in attrs.iter().filter(|a| a.path.is_ident("torow")) {
+        let list = match a.parse_meta() {
+            Ok(syn::Meta::List(list)) => list,
+            _ => continue,
+        };
+
+        for nested in list.nested {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) => {
+                    if let Some(key) = path.get_ident() {
+                        items.push((key.to_string(), None));
+                    }
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(lit),
+                    ..
+                })) => {
+                    if let Some(key) = path.get_ident() {
+                        items.push((key.to_string(), Some(lit.value())));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    items
+}
+
+/// Rename a field written in `snake_case` the same way as `rename_all` of `ToRow`.
+fn rename_field(rule: &str, field: &str) -> String {
+    let pascal_case = || {
+        field
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            })
+            .collect::<String>()
+    };
+
+    match rule {
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field.to_uppercase(),
+        "PascalCase" => pascal_case(),
+        "camelCase" => {
+            let pascal = pascal_case();
+            let mut chars = pascal.chars();
+            match chars.next() {
+                Some(first) => first.to_lowercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.to_uppercase().replace('_', "-"),
+        _ => field.to_string(),
+    }
+}
+
+/// Write the insert statement of a struct deriving `ToRow` with its `#[torow(...)]` attributes,
+/// `None` for other structs. Only the types given with `sql_type` are checked, the ones the
+/// derive infers from rust types are left as `UNKNOWN`.
+fn to_row_statement(item: &syn::ItemStruct) -> Option<SourceStatement> {
+    let mut table = None;
+    let mut rename_all = None;
+    let mut prefix = None;
+    for (key, value) in torow_items(&item.attrs) {
+        match key.as_str() {
+            "table" => table = value,
+            "rename_all" => rename_all = value,
+            "prefix" => prefix = value,
+            _ => {}
+        }
+    }
+    let table = table?;
+
+    let named = match item.fields {
+        syn::Fields::Named(ref named) => named,
+        _ => return None,
+    };
+
+    let mut columns = vec![];
+    let mut types = vec![];
+    for field in named.named.iter() {
+        let mut column = None;
+        let mut sql_type = None;
+        let mut skip = false;
+        for (key, value) in torow_items(&field.attrs) {
+            match key.as_str() {
+                "field" => column = value,
+                "sql_type" => sql_type = value,
+                "skip" => skip = true,
+                _ => {}
+            }
+        }
+
+        if skip {
+            continue;
+        }
+
+        columns.push(column.unwrap_or_else(|| {
+            let name = field.ident.as_ref().unwrap().unraw().to_string();
+            let renamed = match rename_all {
+                Some(ref rule) => rename_field(rule, &name),
+                None => name,
+            };
+
+            format!("{}{}", prefix.as_deref().unwrap_or_default(), renamed)
+        }));
+        types.push(sql_type.unwrap_or_else(|| "UNKNOWN".to_string()));
+    }
+
+    let parameters = (1..=columns.len())
+        .map(|i| format!("${}", i))
+        .collect::<Vec<_>>();
+
+    Some(SourceStatement {
+        line: item.ident.span().start().line,
+        sql: format!(
+            "insert into {} ({}) values ({})",
+            table,
+            columns.join(", "),
+            parameters.join(", ")
+        ),
+        types: Some(types),
+    })
+}
+
+#[derive(Default)]
+struct StatementVisitor {
+    statements: Vec<SourceStatement>,
+}
+
+impl<'ast> Visit<'ast> for StatementVisitor {
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        let method = call.method.to_string();
+
+        if STATEMENT_METHODS.contains(&method.as_str()) {
+            if let Some(sql) = call.args.first().and_then(string_literal) {
+                let types = match method.as_str() {
+                    "prepare_typed" | "prepare_typed_cached" => {
+                        call.args.iter().nth(1).and_then(type_constants)
+                    }
+                    _ => None,
+                };
+
+                self.statements.push(SourceStatement {
+                    line: sql.span().start().line,
+                    sql: sql.value(),
+                    types,
+                });
+            }
+        }
+
+        syn::visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_item_struct(&mut self, item: &'ast syn::ItemStruct) {
+        if let Some(statement) = to_row_statement(item) {
+            self.statements.push(statement);
+        }
+
+        syn::visit::visit_item_struct(self, item);
+    }
+}
+
+/// Find every statement written as a string literal in a rust source file, and the insert
+/// statements of the structs deriving `ToRow`. Statements built at runtime, such as with
+/// `format!`, are not found.
+pub fn find_statements(source: &str) -> syn::Result<Vec<SourceStatement>> {
+    let file = syn::parse_file(source)?;
+    let mut visitor = StatementVisitor::default();
+    visitor.visit_file(&file);
+
+    Ok(visitor.statements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_statements;
+
+    #[test]
+    fn test_find_statements() {
+        let statements = find_statements(
+            r####"
+            async fn handler(client: &Client) {
+                let statement = client
+                    .prepare_typed_cached(
+                        r##"select user_id from users where user_id = $1"##,
+                        &[Type::TEXT],
+                    )
+                    .await?;
+                client.execute("delete from users", &[]).await?;
+                client.query(&format!("select {} from users", column), &[]).await?;
+                client.query(&statement, &[]).await?;
+            }
+            "####,
+        )
+        .unwrap();
+
+        assert_eq!(statements.len(), 2);
+        assert_eq!(
+            statements[0].sql,
+            "select user_id from users where user_id = $1"
+        );
+        assert_eq!(statements[0].types, Some(vec!["TEXT".to_string()]));
+        assert_eq!(statements[0].line, 5);
+        assert_eq!(statements[1].sql, "delete from users");
+        assert_eq!(statements[1].types, None);
+    }
+
+    #[test]
+    fn test_find_to_row_statements() {
+        let statements = find_statements(
+            r####"
+            #[derive(ToRow)]
+            #[torow(table = "forum_posts", prefix = "forum_post_", rename_all = "camelCase")]
+            struct NewForumPost<'a> {
+                #[torow(field = "forum_post_id")]
+                id: &'a str,
+                created_timestamp: OffsetDateTime,
+                #[torow(sql_type = "NUMERIC")]
+                r#score: Decimal,
+                #[torow(skip)]
+                views: i64,
+            }
+
+            struct NotARow {
+                id: String,
+            }
+            "####,
+        )
+        .unwrap();
+
+        assert_eq!(statements.len(), 1);
+        assert_eq!(
+            statements[0].sql,
+            "insert into forum_posts (forum_post_id, forum_post_createdTimestamp, forum_post_score) values ($1, $2, $3)"
+        );
+        assert_eq!(
+            statements[0].types,
+            Some(vec![
+                "UNKNOWN".to_string(),
+                "UNKNOWN".to_string(),
+                "NUMERIC".to_string()
+            ])
+        );
+        assert_eq!(statements[0].line, 4);
+    }
+}
@@ -0,0 +1,572 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::ControlFlow,
+};
+
+use sqlparser::{
+    ast::{
+        AssignmentTarget, BinaryOperator, ConflictTarget, Expr, Ident, ObjectName,
+        OnConflictAction, OnInsert, Query, SelectItem, SetExpr, Statement, TableAlias, TableFactor,
+        Value, Visit, Visitor,
+    },
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+};
+
+use crate::schema::{Schema, TypeFamily};
+
+/// Names that look like columns to the parser but are not, and system columns every table has.
+const KEYWORDS: &[&str] = &[
+    "default",
+    "current_date",
+    "current_time",
+    "current_timestamp",
+    "localtime",
+    "localtimestamp",
+    "ctid",
+    "tableoid",
+    "xmin",
+    "xmax",
+    "cmin",
+    "cmax",
+];
+
+/// What a name in the from clause stands for.
+enum Relation {
+    Table(String),
+    /// Subqueries, common table expressions and functions, their columns are not looked up.
+    Opaque,
+}
+
+fn ident_name(ident: &Ident) -> String {
+    match ident.quote_style {
+        Some(_) => ident.value.clone(),
+        None => ident.value.to_lowercase(),
+    }
+}
+
+fn object_name(name: &ObjectName) -> String {
+    name.0.iter().map(ident_name).collect::<Vec<_>>().join(".")
+}
+
+fn column_parts(expr: &Expr) -> Option<Vec<String>> {
+    match expr {
+        Expr::Identifier(ident) => Some(vec![ident_name(ident)]),
+        Expr::CompoundIdentifier(idents) => Some(idents.iter().map(ident_name).collect()),
+        Expr::Nested(inner) => column_parts(inner),
+        _ => None,
+    }
+}
+
+fn placeholder(expr: &Expr) -> Option<usize> {
+    match expr {
+        Expr::Value(Value::Placeholder(p)) => p.strip_prefix('$')?.parse().ok(),
+        Expr::Nested(inner) => placeholder(inner),
+        _ => None,
+    }
+}
+
+/// Everything a statement refers to, collected before anything is checked because aliases
+/// could be used before the from clause that declares them.
+struct Collector<'a> {
+    schema: &'a Schema,
+    /// Names of common table expressions.
+    ctes: HashSet<String>,
+    /// Table names and aliases to what they stand for, the same alias could be declared by
+    /// different subqueries.
+    relations: HashMap<String, Vec<Relation>>,
+    /// Names given by select lists and aliases, which could be used as columns elsewhere.
+    outputs: HashSet<String>,
+    /// Every column used in an expression.
+    columns: Vec<Vec<String>>,
+    /// Columns given the value of a parameter, or compared to one.
+    parameters: Vec<(Vec<String>, usize)>,
+    problems: Vec<String>,
+}
+
+impl<'a> Collector<'a> {
+    fn relation(&mut self, name: String, relation: Relation) {
+        self.relations.entry(name).or_default().push(relation);
+    }
+
+    fn opaque(&mut self, alias: &Option<TableAlias>) {
+        if let Some(alias) = alias {
+            let name = ident_name(&alias.name);
+            self.outputs.insert(name.clone());
+            self.outputs
+                .extend(alias.columns.iter().map(|c| ident_name(&c.name)));
+            self.relation(name, Relation::Opaque);
+        }
+    }
+
+    fn outputs_of(&mut self, body: &SetExpr) {
+        match body {
+            SetExpr::Select(select) => {
+                for item in select.projection.iter() {
+                    let name = match item {
+                        SelectItem::ExprWithAlias { alias, .. } => Some(ident_name(alias)),
+                        SelectItem::UnnamedExpr(Expr::Function(f)) => {
+                            f.name.0.last().map(ident_name)
+                        }
+                        SelectItem::UnnamedExpr(expr) => {
+                            column_parts(expr).and_then(|p| p.last().cloned())
+                        }
+                        _ => None,
+                    };
+
+                    self.outputs.extend(name);
+                }
+            }
+            SetExpr::SetOperation { left, right, .. } => {
+                self.outputs_of(left);
+                self.outputs_of(right);
+            }
+            _ => (),
+        }
+    }
+
+    /// Tables a column could belong to, an empty list if the column is not found or belongs to
+    /// a relation that is not looked up.
+    fn tables_of(&self, parts: &[String]) -> Vec<&str> {
+        let (relations, column) = match parts {
+            [column] => (
+                self.relations.values().flatten().collect::<Vec<_>>(),
+                column,
+            ),
+            [relation, column] => match self.relations.get(relation) {
+                Some(r) => (r.iter().collect(), column),
+                None => return vec![],
+            },
+            _ => return vec![],
+        };
+
+        relations
+            .into_iter()
+            .filter_map(|r| match r {
+                Relation::Table(t) if self.schema.column(t, column).is_some() => Some(t.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn check_column(&self, parts: &[String]) -> Option<String> {
+        match parts {
+            [column] => {
+                let known = KEYWORDS.contains(&column.as_str())
+                    || self.outputs.contains(column)
+                    || self.relations.contains_key(column)
+                    || !self.tables_of(parts).is_empty();
+
+                match known {
+                    true => None,
+                    false => Some(format!(
+                        "column `{}` does not exist in the tables of the statement",
+                        column
+                    )),
+                }
+            }
+            [relation, column] => {
+                let relations = match self.relations.get(relation) {
+                    Some(r) => r,
+                    None => {
+                        return Some(format!(
+                            "`{}` is not a table or an alias in the statement",
+                            relation
+                        ))
+                    }
+                };
+
+                let known = relations.iter().any(|r| match r {
+                    Relation::Table(t) => self.schema.column(t, column).is_some(),
+                    Relation::Opaque => true,
+                });
+
+                match (known, relations.first()) {
+                    (false, Some(Relation::Table(t))) => Some(format!(
+                        "column `{}` does not exist in table `{}`",
+                        column, t
+                    )),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn check_parameter(&self, parts: &[String], index: usize, types: &[String]) -> Option<String> {
+        let declared = types.get(index.checked_sub(1)?)?;
+        let param_family = TypeFamily::of_param(declared);
+
+        let column = parts.last()?;
+        let tables = self.tables_of(parts);
+        let families = tables
+            .iter()
+            .filter_map(|t| self.schema.column(t, column))
+            .map(|c| c.family.clone())
+            .collect::<Vec<_>>();
+
+        // a column found in more than one table with different types is not checked.
+        let column_family = match families.split_first() {
+            Some((first, rest)) if rest.iter().all(|f| f == first) => first.clone()?,
+            _ => return None,
+        };
+
+        let fits = match (&column_family, &param_family) {
+            (_, None) => true,
+            (TypeFamily::Enum(_), Some(_)) => false,
+            (column, Some(param)) => column == param,
+        };
+
+        match fits {
+            true => None,
+            false => Some(format!(
+                "parameter `${}` is `Type::{}` but column `{}.{}` does not take it",
+                index, declared, tables[0], column
+            )),
+        }
+    }
+
+    fn insert_columns(&mut self, table: &str, columns: &[Ident]) -> Vec<Vec<String>> {
+        columns
+            .iter()
+            .map(|c| vec![table.to_string(), ident_name(c)])
+            .collect()
+    }
+}
+
+impl Visitor for Collector<'_> {
+    type Break = ();
+
+    fn pre_visit_statement(&mut self, statement: &Statement) -> ControlFlow<()> {
+        match statement {
+            Statement::Insert(insert) => {
+                let table = object_name(&insert.table_name);
+                if self.schema.table(&table).is_none() {
+                    self.problems
+                        .push(format!("table `{}` does not exist", table));
+                    return ControlFlow::Continue(());
+                }
+
+                self.relation(table.clone(), Relation::Table(table.clone()));
+                self.relation("excluded".to_string(), Relation::Table(table.clone()));
+                if let Some(ref alias) = insert.table_alias {
+                    self.relation(ident_name(alias), Relation::Table(table.clone()));
+                }
+
+                let columns = self.insert_columns(&table, &insert.columns);
+                self.columns.extend(columns.iter().cloned());
+
+                if let Some(SetExpr::Values(values)) = insert.source.as_ref().map(|s| &*s.body) {
+                    for row in values.rows.iter() {
+                        for (column, expr) in columns.iter().zip(row.iter()) {
+                            if let Some(index) = placeholder(expr) {
+                                self.parameters.push((column.clone(), index));
+                            }
+                        }
+                    }
+                }
+
+                if let Some(OnInsert::OnConflict(ref on_conflict)) = insert.on {
+                    if let Some(ConflictTarget::Columns(ref target)) = on_conflict.conflict_target {
+                        let target = self.insert_columns(&table, target);
+                        self.columns.extend(target);
+                    }
+
+                    if let OnConflictAction::DoUpdate(ref update) = on_conflict.action {
+                        for assignment in update.assignments.iter() {
+                            if let AssignmentTarget::ColumnName(ref name) = assignment.target {
+                                let column = vec![table.clone(), object_name(name)];
+                                if let Some(index) = placeholder(&assignment.value) {
+                                    self.parameters.push((column.clone(), index));
+                                }
+                                self.columns.push(column);
+                            }
+                        }
+                    }
+                }
+            }
+            Statement::Update {
+                table, assignments, ..
+            } => {
+                if let TableFactor::Table { ref name, .. } = table.relation {
+                    let table = object_name(name);
+
+                    for assignment in assignments.iter() {
+                        if let AssignmentTarget::ColumnName(ref name) = assignment.target {
+                            let column = vec![table.clone(), object_name(name)];
+                            if let Some(index) = placeholder(&assignment.value) {
+                                self.parameters.push((column.clone(), index));
+                            }
+                            self.columns.push(column);
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<()> {
+        if let Some(ref with) = query.with {
+            for cte in with.cte_tables.iter() {
+                self.ctes.insert(ident_name(&cte.alias.name));
+                self.opaque(&Some(cte.alias.clone()));
+            }
+        }
+
+        self.outputs_of(&query.body);
+
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_table_factor(&mut self, table_factor: &TableFactor) -> ControlFlow<()> {
+        match table_factor {
+            // functions such as `generate_series(1, $1) as i`.
+            TableFactor::Table {
+                name,
+                alias,
+                args: Some(_),
+                ..
+            } => match alias {
+                Some(_) => self.opaque(alias),
+                None => {
+                    let function = object_name(name);
+                    self.outputs.insert(function.clone());
+                    self.relation(function, Relation::Opaque);
+                }
+            },
+            TableFactor::Table { name, alias, .. } => {
+                let table = object_name(name);
+                let relation = if self.ctes.contains(&table) {
+                    Relation::Opaque
+                } else if self.schema.table(&table).is_some() {
+                    Relation::Table(table.clone())
+                } else {
+                    self.problems
+                        .push(format!("table `{}` does not exist", table));
+                    Relation::Opaque
+                };
+
+                let name = match alias {
+                    Some(alias) => ident_name(&alias.name),
+                    None => table,
+                };
+                self.relation(name, relation);
+            }
+            TableFactor::Derived { alias, .. }
+            | TableFactor::Function { alias, .. }
+            | TableFactor::TableFunction { alias, .. }
+            | TableFactor::UNNEST { alias, .. } => self.opaque(alias),
+            _ => (),
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<()> {
+        if let Some(parts) = column_parts(expr) {
+            self.columns.push(parts);
+        }
+
+        if let Expr::BinaryOp { left, op, right } = expr {
+            let compared = matches!(
+                op,
+                BinaryOperator::Eq
+                    | BinaryOperator::NotEq
+                    | BinaryOperator::Lt
+                    | BinaryOperator::LtEq
+                    | BinaryOperator::Gt
+                    | BinaryOperator::GtEq
+            );
+
+            if compared {
+                let pair = match (column_parts(left), placeholder(right)) {
+                    (Some(column), Some(index)) => Some((column, index)),
+                    _ => column_parts(right).zip(placeholder(left)),
+                };
+
+                self.parameters.extend(pair);
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+/// Check a statement against the schema, returns the problems found. `types` are the names of
+/// the constants of `Type` the statement is prepared with, parameters are not checked without
+/// them.
+pub fn check_statement(schema: &Schema, sql: &str, types: Option<&[String]>) -> Vec<String> {
+    let statements = match Parser::parse_sql(&PostgreSqlDialect {}, sql) {
+        Ok(s) => s,
+        Err(e) => return vec![format!("cannot parse the statement: {}", e)],
+    };
+
+    let mut problems = vec![];
+
+    for statement in statements.iter() {
+        let mut collector = Collector {
+            schema,
+            ctes: HashSet::new(),
+            relations: HashMap::new(),
+            outputs: HashSet::new(),
+            columns: vec![],
+            parameters: vec![],
+            problems: vec![],
+        };
+        let _ = statement.visit(&mut collector);
+
+        problems.append(&mut collector.problems);
+        problems.extend(
+            collector
+                .columns
+                .iter()
+                .filter_map(|c| collector.check_column(c)),
+        );
+
+        if let Some(types) = types {
+            problems.extend(
+                collector
+                    .parameters
+                    .iter()
+                    .filter_map(|(c, i)| collector.check_parameter(c, *i, types)),
+            );
+        }
+    }
+
+    // a wrong column is usually used more than once in the same statement.
+    let mut seen = HashSet::new();
+    problems.retain(|p| seen.insert(p.clone()));
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::Schema;
+
+    use super::check_statement;
+
+    fn schema() -> Schema {
+        Schema::parse(
+            r##"
+            create type t_user_role as enum ('admin', 'student');
+
+            create table users (
+                user_id text not null,
+                user_role t_user_role not null,
+                user_birthdate date not null
+            );
+
+            create table student_names (
+                student_name_id text not null,
+                student_id text not null references users(user_id),
+                student_first_name text not null,
+                student_middle_name text not null
+            );
+
+            create table student_grades (
+                student_id text not null,
+                student_grade_point numeric(3, 2) not null
+            );
+            "##,
+        )
+        .unwrap()
+    }
+
+    fn check(sql: &str, types: &[&str]) -> Vec<String> {
+        let types = types.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+
+        check_statement(&schema(), sql, Some(&types))
+    }
+
+    #[test]
+    fn test_valid_statements() {
+        let valid = [
+            "select u.user_id, n.student_first_name as first_name from users u inner join student_names n on u.user_id = n.student_id where u.user_id = $1 order by first_name",
+            "with grades as (select student_id, avg(student_grade_point) as gpa from student_grades group by student_id) select users.user_id, grades.gpa from users left join grades on grades.student_id = users.user_id where gpa > $1",
+            "select count(*) from (select student_id from student_grades) as s where s.student_id = $1",
+            "insert into student_grades (student_id, student_grade_point) values ($1, $2) on conflict (student_id) do update set student_grade_point = excluded.student_grade_point",
+            "update student_grades set student_grade_point = $2 where student_id = $1 and student_id in (select user_id from users where user_role = 'admin')",
+            "delete from student_names where student_id = $1 and student_name_id in (select student_name_id from student_names where student_first_name = $1)",
+            "select i from generate_series(1, $1) as i",
+        ];
+
+        for sql in valid {
+            assert_eq!(
+                check(sql, &["TEXT", "NUMERIC"]),
+                Vec::<String>::new(),
+                "{}",
+                sql
+            );
+        }
+    }
+
+    #[test]
+    fn test_wrong_columns_and_tables() {
+        assert_eq!(
+            check(
+                "insert into student_names (student_name_id, student_id, student_firstname, student_middlename) values ($1, $2, $3, $4)",
+                &["TEXT", "TEXT", "TEXT", "TEXT"],
+            ),
+            vec![
+                "column `student_firstname` does not exist in table `student_names`",
+                "column `student_middlename` does not exist in table `student_names`",
+            ]
+        );
+        assert_eq!(
+            check("select u.user_name from users u where user_idd = $1", &[]),
+            vec![
+                "column `user_name` does not exist in table `users`",
+                "column `user_idd` does not exist in the tables of the statement",
+            ]
+        );
+        assert_eq!(
+            check("select * from user_sessions where s.user_id = $1", &[]),
+            vec![
+                "table `user_sessions` does not exist",
+                "`s` is not a table or an alias in the statement",
+            ]
+        );
+        assert_eq!(
+            check("select user_id from users where", &[]),
+            vec![
+                "cannot parse the statement: sql parser error: Expected: an expression, found: EOF"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parameter_types() {
+        assert_eq!(
+            check(
+                "update student_grades set student_grade_point = $2 where student_id = $1",
+                &["INT4", "TEXT"],
+            ),
+            vec![
+                "parameter `$2` is `Type::TEXT` but column `student_grades.student_grade_point` does not take it",
+                "parameter `$1` is `Type::INT4` but column `student_grades.student_id` does not take it",
+            ]
+        );
+        assert_eq!(
+            check(
+                "select user_id from users where user_role = $1 and user_birthdate < $2",
+                &["TEXT", "TIMESTAMPTZ"],
+            ),
+            vec!["parameter `$1` is `Type::TEXT` but column `users.user_role` does not take it"]
+        );
+        // integers fit in numeric columns, and enums could be left for postgres to infer.
+        assert!(check(
+            "insert into users (user_id, user_role) values ($1, $2)",
+            &["TEXT"]
+        )
+        .is_empty());
+        assert!(check(
+            "select student_id from student_grades where student_grade_point >= $1",
+            &["INT8"]
+        )
+        .is_empty());
+    }
+}
@@ -1,13 +0,0 @@
-module github.com/grindarius/sql-rs
-
-go 1.19
-
-require (
-	github.com/gobeam/stringy v0.0.5
-	github.com/pganalyze/pg_query_go/v2 v2.2.0
-)
-
-require (
-	github.com/golang/protobuf v1.4.2 // indirect
-	google.golang.org/protobuf v1.23.0 // indirect
-)